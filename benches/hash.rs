@@ -0,0 +1,48 @@
+use blstrs::Scalar as Fr;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use lurk::{ptr::Ptr, store::Store};
+
+const LEAVES: u64 = 5_000;
+
+/// A balanced tree of conses with distinct leaves, so that no subtrees are shared. With 5k leaves
+/// it has about 10k nodes.
+fn tree(store: &mut Store<Fr>, leaves: std::ops::Range<u64>) -> Ptr<Fr> {
+    if leaves.end - leaves.start == 1 {
+        store.num(leaves.start)
+    } else {
+        let mid = leaves.start + (leaves.end - leaves.start) / 2;
+        let car = tree(store, leaves.start..mid);
+        let cdr = tree(store, mid..leaves.end);
+        store.cons(car, cdr)
+    }
+}
+
+/// To run these benchmarks, do `cargo criterion hash_benchmark`.
+fn hash_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hash_benchmark");
+
+    group.bench_function("cold", |b| {
+        b.iter_batched_ref(
+            || {
+                let mut store = Store::<Fr>::default();
+                let root = tree(&mut store, 0..LEAVES);
+                (store, root)
+            },
+            |(store, root)| black_box(store.hash_expr(root)),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.bench_function("warm", |b| {
+        let mut store = Store::<Fr>::default();
+        let root = tree(&mut store, 0..LEAVES);
+        store.hash_expr(&root);
+        b.iter(|| black_box(store.hash_expr(&root)))
+    });
+}
+
+criterion_group!(benches, hash_benchmark);
+
+// To run these benchmarks, first download `criterion` with `cargo install cargo-criterion`.
+// Then `cargo criterion --bench hash`. The results are located in `target/criterion/data/<name-of-benchmark>`.
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+use blstrs::Scalar as Fr;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use lurk::{expr::Expression, store::Store, Num};
+
+const BATCH_SIZE: u64 = 10_000;
+
+fn items(store: &mut Store<Fr>) -> Vec<Expression<Fr>> {
+    (0..BATCH_SIZE)
+        .map(|i| {
+            let car = store.num(i);
+            let cdr = store.num(i % 100);
+            if i % 2 == 0 {
+                Expression::Cons(car, cdr)
+            } else {
+                Expression::Num(Num::U64(i * 7))
+            }
+        })
+        .collect()
+}
+
+/// To run these benchmarks, do `cargo criterion intern_benchmark`.
+fn intern_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("intern_benchmark");
+
+    group.bench_function("intern_loop", |b| {
+        b.iter_batched(
+            || {
+                let mut store = Store::<Fr>::default();
+                let items = items(&mut store);
+                (store, items)
+            },
+            |(mut store, items)| {
+                let ptrs = items
+                    .iter()
+                    .map(|expr| store.intern_expression(expr))
+                    .collect::<Vec<_>>();
+                black_box(ptrs)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.bench_function("intern_batch", |b| {
+        b.iter_batched(
+            || {
+                let mut store = Store::<Fr>::default();
+                let items = items(&mut store);
+                (store, items)
+            },
+            |(mut store, items)| black_box(store.intern_batch(&items)),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, intern_benchmark);
+
+// To run these benchmarks, first download `criterion` with `cargo install cargo-criterion`.
+// Then `cargo criterion --bench intern`. The results are located in `target/criterion/data/<name-of-benchmark>`.
+criterion_main!(benches);
@@ -0,0 +1,80 @@
+use std::{sync::Arc, time::Duration};
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, SamplingMode};
+use pasta_curves::pallas;
+
+use lurk::{
+    circuit::circuit_frame::MultiFrame,
+    config::Flow,
+    eval::{
+        empty_sym_env,
+        lang::{Coproc, Lang},
+    },
+    proof::nova::{public_params, NovaProver, Proof},
+    proof::Prover,
+    store::Store,
+};
+
+const STEPS: usize = 256;
+const REDUCTION_COUNT: usize = 1;
+
+/// To run these benchmarks, do `cargo criterion parallel_witness_benchmark`.
+/// This compares proving the same 256 recursive steps with witnesses computed sequentially and
+/// in parallel.
+fn parallel_witness_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel_witness_benchmark");
+    group
+        .sampling_mode(SamplingMode::Flat)
+        .measurement_time(Duration::from_secs(120))
+        .sample_size(10);
+
+    let lang = Lang::<pallas::Scalar, Coproc<pallas::Scalar>>::new();
+    let lang_rc = Arc::new(lang.clone());
+    let store = &mut Store::default();
+    let env = empty_sym_env(store);
+    let prover = NovaProver::new(REDUCTION_COUNT, lang.clone());
+    let pp = public_params(REDUCTION_COUNT, lang_rc.clone());
+
+    // counting down from 40 takes well over 256 iterations
+    let expr = store
+        .read("(letrec ((count (lambda (n) (if (= n 0) n (count (- n 1)))))) (count 40))")
+        .unwrap();
+    let frames = prover
+        .get_evaluation_frames(expr, env, store, 1_000_000, &lang)
+        .unwrap();
+    let store = &*store;
+    let circuits = MultiFrame::from_frames(REDUCTION_COUNT, &frames, store, lang_rc.clone());
+    let circuits = &circuits[..STEPS];
+    let z0 = frames[0].input.to_vector(store).unwrap();
+
+    for (name, flow) in [
+        ("sequential", Flow::Sequential),
+        ("parallel", Flow::Parallel),
+    ] {
+        group.bench_with_input(BenchmarkId::new(name, STEPS), &flow, |b, flow| {
+            b.iter(|| {
+                let proof = Proof::prove_recursively_with_flow(
+                    &pp,
+                    store,
+                    black_box(circuits),
+                    REDUCTION_COUNT,
+                    z0.clone(),
+                    lang_rc.clone(),
+                    flow,
+                )
+                .unwrap();
+                black_box(proof)
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default();
+    targets = parallel_witness_benchmark,
+}
+
+criterion_main!(benches);
@@ -1,8 +1,30 @@
 use std::error::Error;
 use vergen::EmitBuilder;
 
+/// Writes the header for the `capi` module to `$OUT_DIR/include/lurk.h`, since a build script
+/// mustn't write outside of `OUT_DIR`
+#[cfg(feature = "capi")]
+fn generate_c_header() -> Result<(), Box<dyn Error>> {
+    use std::path::PathBuf;
+
+    let crate_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR")?);
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR")?);
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    let include_dir = out_dir.join("include");
+    std::fs::create_dir_all(&include_dir)?;
+    cbindgen::generate_with_config(
+        &crate_dir,
+        cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))?,
+    )?
+    .write_to_file(include_dir.join("lurk.h"));
+    Ok(())
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     // Emit the instructions
     EmitBuilder::builder().all_git().emit()?;
+    #[cfg(feature = "capi")]
+    generate_c_header()?;
     Ok(())
 }
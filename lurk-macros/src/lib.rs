@@ -35,6 +35,7 @@ fn impl_enum_coproc(name: &Ident, variants: &DataEnum) -> TokenStream {
     let eval_arity_arms = eval_arity_match_arms(name, variants);
     let evaluate_arms = evaluate_match_arms(name, variants);
     let simple_evaluate_arms = simple_evaluate_match_arms(name, variants);
+    let simple_evaluate_multi_arms = simple_evaluate_multi_match_arms(name, variants);
     let has_circuit_arms = has_circuit_match_arms(name, variants);
 
     let arity_arms = arity_match_arms(name, variants);
@@ -62,6 +63,12 @@ fn impl_enum_coproc(name: &Ident, variants: &DataEnum) -> TokenStream {
                 }
             }
 
+            fn simple_evaluate_multi(&self, s: &mut lurk::store::Store<F>, args: &[lurk::ptr::Ptr<F>]) -> lurk::coprocessor::Values<F> {
+                match self {
+                    #simple_evaluate_multi_arms
+                }
+            }
+
             fn has_circuit(&self) -> bool {
                 match self {
                     #has_circuit_arms
@@ -132,6 +139,18 @@ fn simple_evaluate_match_arms(name: &Ident, variants: &DataEnum) -> proc_macro2:
     match_arms
 }
 
+fn simple_evaluate_multi_match_arms(name: &Ident, variants: &DataEnum) -> proc_macro2::TokenStream {
+    let mut match_arms = quote! {};
+    for variant in variants.variants.iter() {
+        let variant_ident = &variant.ident;
+
+        match_arms.extend(quote! {
+            #name::#variant_ident(coprocessor) => coprocessor.simple_evaluate_multi(s, args),
+        });
+    }
+    match_arms
+}
+
 fn has_circuit_match_arms(name: &Ident, variants: &DataEnum) -> proc_macro2::TokenStream {
     let mut match_arms = quote! {};
     for variant in variants.variants.iter() {
@@ -20,6 +20,15 @@ impl<K, V> Default for CacheMap<K, V> {
     }
 }
 
+/// Clones the entries into fresh boxes, so references into one map never alias the other.
+impl<K: Clone, V: Clone> Clone for CacheMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            map: RwLock::new(self.map.read().unwrap().clone()),
+        }
+    }
+}
+
 impl<K, V> CacheMap<K, V> {
     pub fn new() -> Self {
         Self::default()
@@ -206,6 +215,23 @@ impl<K: Eq + Hash, V: StableDeref> CacheMap<K, V> {
         map.is_empty()
     }
 
+    /// Removes all entries. This takes `&mut self`, since references returned by other methods
+    /// must not outlive their entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lurk::cache_map::CacheMap;
+    ///
+    /// let mut map = CacheMap::new();
+    /// map.insert(1, Box::new("a"));
+    /// map.clear();
+    /// assert_eq!(map.is_empty(), true);
+    /// ```
+    pub fn clear(&mut self) {
+        self.map.get_mut().unwrap().clear()
+    }
+
     // TODO add more
 }
 
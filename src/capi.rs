@@ -0,0 +1,179 @@
+//! A C interface for embedding the evaluator, over the Pallas scalar field. Building with the
+//! `capi` feature also writes the matching header to `include/lurk.h` in the build script's
+//! `OUT_DIR`. The libraries a C host links against are built with
+//! `cargo rustc --release --features capi --crate-type staticlib,cdylib`, and the header can also
+//! be written anywhere with `cbindgen --config cbindgen.toml -o lurk.h`.
+//!
+//! Expressions are referred to by `LurkExpr` handles, which are only meaningful to the
+//! `LurkStore` that returned them. Every fallible function returns a `LurkStatus` and writes its
+//! result through an out-pointer only on `LURK_STATUS_OK`.
+
+use std::ffi::{c_char, CStr, CString};
+
+use indexmap::IndexSet;
+use pasta_curves::pallas::Scalar;
+
+use crate::eval::{
+    empty_sym_env,
+    lang::{Coproc, Lang},
+    Evaluator,
+};
+use crate::ptr::Ptr;
+use crate::state::initial_lurk_state;
+use crate::store::Store;
+use crate::tag::ContTag;
+use crate::writer::Write;
+
+/// The most reduction steps `lurk_eval` takes before giving up
+const EVAL_LIMIT: usize = 1_000_000;
+
+/// A store of Lurk data, along with the handles given out for it
+pub struct LurkStore {
+    store: Store<Scalar>,
+    lang: Lang<Scalar, Coproc<Scalar>>,
+    handles: IndexSet<Ptr<Scalar>>,
+}
+
+impl LurkStore {
+    fn handle(&mut self, ptr: Ptr<Scalar>) -> LurkExpr {
+        self.handles.insert_full(ptr).0 as LurkExpr
+    }
+
+    fn ptr(&self, handle: LurkExpr) -> Option<Ptr<Scalar>> {
+        self.handles
+            .get_index(usize::try_from(handle).ok()?)
+            .copied()
+    }
+}
+
+/// A handle to an expression of a `LurkStore`
+pub type LurkExpr = u64;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LurkStatus {
+    Ok = 0,
+    /// A required pointer argument was null
+    NullArgument,
+    /// The source wasn't valid UTF-8
+    InvalidUtf8,
+    /// The source couldn't be read as an expression
+    ReadError,
+    /// Evaluation ended in an error
+    EvalError,
+    /// Evaluation didn't finish within the step limit
+    LimitReached,
+    /// The handle wasn't given out by this store
+    InvalidHandle,
+    /// The printed result contains a NUL character, so it can't be a C string
+    NulInResult,
+}
+
+/// Creates an empty store, to be freed with `lurk_store_free`
+#[no_mangle]
+pub extern "C" fn lurk_store_new() -> *mut LurkStore {
+    Box::into_raw(Box::new(LurkStore {
+        store: Store::default(),
+        lang: Lang::new(),
+        handles: IndexSet::new(),
+    }))
+}
+
+/// # Safety
+/// `store` must be null or returned by `lurk_store_new`, and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn lurk_store_free(store: *mut LurkStore) {
+    if !store.is_null() {
+        drop(Box::from_raw(store));
+    }
+}
+
+/// Reads the first expression of the NUL-terminated `src` into `store`
+///
+/// # Safety
+/// `store` must come from `lurk_store_new`, `src` must be null or NUL-terminated, and `out` must
+/// be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn lurk_read(
+    store: *mut LurkStore,
+    src: *const c_char,
+    out: *mut LurkExpr,
+) -> LurkStatus {
+    let (Some(store), Some(out)) = (store.as_mut(), out.as_mut()) else {
+        return LurkStatus::NullArgument;
+    };
+    if src.is_null() {
+        return LurkStatus::NullArgument;
+    }
+    let Ok(src) = CStr::from_ptr(src).to_str() else {
+        return LurkStatus::InvalidUtf8;
+    };
+    match store.store.read(src) {
+        Ok(ptr) => {
+            *out = store.handle(ptr);
+            LurkStatus::Ok
+        }
+        Err(_) => LurkStatus::ReadError,
+    }
+}
+
+/// Evaluates `expr` in the empty environment
+///
+/// # Safety
+/// `store` must come from `lurk_store_new`, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn lurk_eval(
+    store: *mut LurkStore,
+    expr: LurkExpr,
+    out: *mut LurkExpr,
+) -> LurkStatus {
+    let (Some(store), Some(out)) = (store.as_mut(), out.as_mut()) else {
+        return LurkStatus::NullArgument;
+    };
+    let Some(expr) = store.ptr(expr) else {
+        return LurkStatus::InvalidHandle;
+    };
+    let env = empty_sym_env(&store.store);
+    match Evaluator::new(expr, env, &mut store.store, EVAL_LIMIT, &store.lang).eval_checked() {
+        Ok((io, ..)) if io.cont.tag == ContTag::Terminal => {
+            *out = store.handle(io.expr);
+            LurkStatus::Ok
+        }
+        Ok(_) => LurkStatus::LimitReached,
+        Err(_) => LurkStatus::EvalError,
+    }
+}
+
+/// Prints `expr` as a NUL-terminated UTF-8 string, to be freed with `lurk_string_free`
+///
+/// # Safety
+/// `store` must come from `lurk_store_new`, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn lurk_print(
+    store: *const LurkStore,
+    expr: LurkExpr,
+    out: *mut *mut c_char,
+) -> LurkStatus {
+    let (Some(store), Some(out)) = (store.as_ref(), out.as_mut()) else {
+        return LurkStatus::NullArgument;
+    };
+    let Some(expr) = store.ptr(expr) else {
+        return LurkStatus::InvalidHandle;
+    };
+    match CString::new(expr.fmt_to_string(&store.store, initial_lurk_state())) {
+        Ok(printed) => {
+            *out = printed.into_raw();
+            LurkStatus::Ok
+        }
+        Err(_) => LurkStatus::NulInResult,
+    }
+}
+
+/// # Safety
+/// `string` must be null or returned by `lurk_print`, and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn lurk_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
-use bellpepper::util_cs::{witness_cs::WitnessCS, Comparable};
+use bellpepper::util_cs::{metric_cs::MetricCS, witness_cs::WitnessCS, Comparable};
 use bellpepper_core::{
     boolean::Boolean, num::AllocatedNum, Circuit, ConstraintSystem, SynthesisError,
 };
@@ -29,7 +29,7 @@ use super::gadgets::constraints::{
     pick, pick_const, sub,
 };
 use crate::circuit::circuit_frame::constraints::{
-    add, allocate_is_negative, boolean_to_num, enforce_pack, linear, mul,
+    add, allocate_is_negative, boolean_to_num, enforce_pack, linear, mul, u64_bitwise, U64Bitwise,
 };
 use crate::circuit::gadgets::hashes::{AllocatedConsWitness, AllocatedContWitness};
 use crate::circuit::ToInputs;
@@ -56,6 +56,16 @@ pub struct CircuitFrame<'a, F: LurkField, T, W, C: Coprocessor<F>> {
     _p: PhantomData<C>,
 }
 
+/// The size of a circuit, as counted by `MultiFrame::metrics`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CircuitMetrics {
+    pub constraints: usize,
+    /// The number of witness (auxiliary) variables
+    pub aux: usize,
+    /// The number of public inputs, including the constant one
+    pub inputs: usize,
+}
+
 #[derive(Clone)]
 pub struct MultiFrame<'a, F: LurkField, T: Copy + Sync, W: Sync, C: Coprocessor<F>> {
     pub store: Option<&'a Store<F>>,
@@ -114,6 +124,21 @@ impl<
         self.store.expect("store missing")
     }
 
+    /// Counts the constraints and variables of `MultiFrame`s with this one's reduction count and
+    /// language, by synthesizing a blank one, which computes no witness
+    pub fn metrics(&self) -> CircuitMetrics {
+        let lang = self.lang.clone().expect("lang missing");
+        let mut cs = MetricCS::<F>::new();
+        Self::blank(self.count, lang)
+            .synthesize(&mut cs)
+            .expect("failed to synthesize blank");
+        CircuitMetrics {
+            constraints: cs.num_constraints(),
+            aux: cs.aux().len(),
+            inputs: cs.num_inputs(),
+        }
+    }
+
     pub fn from_frames(
         count: usize,
         frames: &[Frame<IO<F>, Witness<F>, C>],
@@ -1519,6 +1544,11 @@ fn reduce_cons<F: LurkField, CS: ConstraintSystem<F>, C: Coprocessor<F>>(
     def_head_val!(head_is_less_equal, c.less_equal);
     def_head_val!(head_is_greater, c.greater);
     def_head_val!(head_is_greater_equal, c.greater_equal);
+    def_head_val!(head_is_bit_and, c.bit_and);
+    def_head_val!(head_is_bit_or, c.bit_or);
+    def_head_val!(head_is_bit_xor, c.bit_xor);
+    def_head_val!(head_is_shift_left, c.shift_left);
+    def_head_val!(head_is_shift_right, c.shift_right);
     def_head_val!(head_is_if0, c.if_);
     def_head_val!(head_is_current_env0, c.current_env);
 
@@ -1544,6 +1574,11 @@ fn reduce_cons<F: LurkField, CS: ConstraintSystem<F>, C: Coprocessor<F>>(
         &head_is_less_equal,
         &head_is_greater,
         &head_is_greater_equal,
+        &head_is_bit_and,
+        &head_is_bit_or,
+        &head_is_bit_xor,
+        &head_is_shift_left,
+        &head_is_shift_right,
         &head_is_eval
     )?;
 
@@ -1700,31 +1735,41 @@ fn reduce_cons<F: LurkField, CS: ConstraintSystem<F>, C: Coprocessor<F>>(
         let lambda_not_dummy = and!(cs, &head_is_lambda, not_dummy, &more_is_nil.not())?;
         let inner_not_dummy = and!(cs, &lambda_not_dummy, &cdr_args_is_nil.not())?;
 
+        // In (LAMBDA (A . REST) STUFF), the variadic function's body is the form
+        // (LAMBDA (REST) . STUFF), built from the same conses as the curried
+        // (LAMBDA (A) (LAMBDA REST-ARGS STUFF)).
+        let is_variadic = cdr_args.is_sym(&mut cs.namespace(|| "is_variadic"))?;
+
+        let inner_cdr = pick_ptr!(cs, &is_variadic, &g.nil_ptr, &body)?;
         let inner = AllocatedPtr::construct_cons_named(
             &mut cs.namespace(|| "inner"),
             g,
             &cdr_args,
-            &body,
+            &inner_cdr,
             ConsName::InnerLambda,
             allocated_cons_witness,
             &inner_not_dummy,
         )?;
 
+        let l_car = pick_ptr!(cs, &is_variadic, &inner, &g.lambda_sym)?;
+        let l_cdr = pick_ptr!(cs, &is_variadic, &body, &inner)?;
         let l = AllocatedPtr::construct_cons_named(
             &mut cs.namespace(|| "l"),
             g,
-            &g.lambda_sym,
-            &inner,
+            &l_car,
+            &l_cdr,
             ConsName::Lambda,
             allocated_cons_witness,
             &inner_not_dummy,
         )?;
 
+        let list_car = pick_ptr!(cs, &is_variadic, &g.lambda_sym, &l)?;
+        let list_cdr = pick_ptr!(cs, &is_variadic, &l, &g.nil_ptr)?;
         let list = AllocatedPtr::construct_cons_named(
             &mut cs.namespace(|| "list"),
             g,
-            &l,
-            &g.nil_ptr,
+            &list_car,
+            &list_cdr,
             ConsName::InnerBody,
             allocated_cons_witness,
             &inner_not_dummy,
@@ -2365,6 +2410,56 @@ fn reduce_cons<F: LurkField, CS: ConstraintSystem<F>, C: Coprocessor<F>>(
         greater_equal_continuation_components,
     );
 
+    // head == AND preimage
+    /////////////////////////////////////////////////////////////////////////////
+    let bit_and_continuation_components: &[&dyn AsAllocatedHashComponents<F>; 4] =
+        &[&[&g.op2_bit_and_tag, &g.default_num], env, &more, cont];
+    hash_default_results.add_hash_input_clauses(
+        c.bit_and.value(),
+        &g.binop_cont_tag,
+        bit_and_continuation_components,
+    );
+
+    // head == OR preimage
+    /////////////////////////////////////////////////////////////////////////////
+    let bit_or_continuation_components: &[&dyn AsAllocatedHashComponents<F>; 4] =
+        &[&[&g.op2_bit_or_tag, &g.default_num], env, &more, cont];
+    hash_default_results.add_hash_input_clauses(
+        c.bit_or.value(),
+        &g.binop_cont_tag,
+        bit_or_continuation_components,
+    );
+
+    // head == XOR preimage
+    /////////////////////////////////////////////////////////////////////////////
+    let bit_xor_continuation_components: &[&dyn AsAllocatedHashComponents<F>; 4] =
+        &[&[&g.op2_bit_xor_tag, &g.default_num], env, &more, cont];
+    hash_default_results.add_hash_input_clauses(
+        c.bit_xor.value(),
+        &g.binop_cont_tag,
+        bit_xor_continuation_components,
+    );
+
+    // head == SHL preimage
+    /////////////////////////////////////////////////////////////////////////////
+    let shift_left_continuation_components: &[&dyn AsAllocatedHashComponents<F>; 4] =
+        &[&[&g.op2_shift_left_tag, &g.default_num], env, &more, cont];
+    hash_default_results.add_hash_input_clauses(
+        c.shift_left.value(),
+        &g.binop_cont_tag,
+        shift_left_continuation_components,
+    );
+
+    // head == SHR preimage
+    /////////////////////////////////////////////////////////////////////////////
+    let shift_right_continuation_components: &[&dyn AsAllocatedHashComponents<F>; 4] =
+        &[&[&g.op2_shift_right_tag, &g.default_num], env, &more, cont];
+    hash_default_results.add_hash_input_clauses(
+        c.shift_right.value(),
+        &g.binop_cont_tag,
+        shift_right_continuation_components,
+    );
+
     // head == IF preimage
     /////////////////////////////////////////////////////////////////////////////
     let if_continuation_components: &[&dyn AsAllocatedHashComponents<F>; 4] = &[
@@ -2396,6 +2491,10 @@ fn reduce_cons<F: LurkField, CS: ConstraintSystem<F>, C: Coprocessor<F>>(
 
     // head == fn . args preimage
     /////////////////////////////////////////////////////////////////////////////
+    // (FN ARG . _) is a call expanded from a multi-arg call, see below.
+    let more_is_dummy =
+        more.alloc_equal(&mut cs.namespace(|| "more_is_dummy"), &g.dummy_arg_ptr)?;
+    let call_expanded = boolean_num!(cs, &more_is_dummy)?;
     let (
         cont_tag,
         component0_tag,
@@ -2456,7 +2555,7 @@ fn reduce_cons<F: LurkField, CS: ConstraintSystem<F>, C: Coprocessor<F>>(
         &component1_hash,
         &component2_tag,
         &component2_hash,
-        &g.default_num,
+        &call_expanded,
         &g.default_num,
     ];
 
@@ -2780,6 +2879,26 @@ fn reduce_cons<F: LurkField, CS: ConstraintSystem<F>, C: Coprocessor<F>>(
         &g.false_num,
     );
 
+    // head == AND, newer_cont is allocated
+    /////////////////////////////////////////////////////////////////////////////
+    results.add_clauses_cons(c.bit_and.value(), &arg1, env, &newer_cont, &g.false_num);
+
+    // head == OR, newer_cont is allocated
+    /////////////////////////////////////////////////////////////////////////////
+    results.add_clauses_cons(c.bit_or.value(), &arg1, env, &newer_cont, &g.false_num);
+
+    // head == XOR, newer_cont is allocated
+    /////////////////////////////////////////////////////////////////////////////
+    results.add_clauses_cons(c.bit_xor.value(), &arg1, env, &newer_cont, &g.false_num);
+
+    // head == SHL, newer_cont is allocated
+    /////////////////////////////////////////////////////////////////////////////
+    results.add_clauses_cons(c.shift_left.value(), &arg1, env, &newer_cont, &g.false_num);
+
+    // head == SHR, newer_cont is allocated
+    /////////////////////////////////////////////////////////////////////////////
+    results.add_clauses_cons(c.shift_right.value(), &arg1, env, &newer_cont, &g.false_num);
+
     // head == IF, newer_cont is allocated
     /////////////////////////////////////////////////////////////////////////////
     results.add_clauses_cons(c.if_.value(), &arg1, env, &newer_cont, &g.false_num);
@@ -2845,11 +2964,8 @@ fn reduce_cons<F: LurkField, CS: ConstraintSystem<F>, C: Coprocessor<F>>(
         let fun_form = &head;
 
         let more_args_is_nil = more.is_nil(&mut cs.namespace(|| "more_args_is_nil"), g)?;
-        let args_is_nil_or_more_is_nil = or(
-            &mut cs.namespace(|| "args is nil or more is nil"),
-            &is_zero_arg_call,
-            &more_args_is_nil,
-        )?;
+        let args_is_nil_or_more_is_nil =
+            or!(cs, &is_zero_arg_call, &more_args_is_nil, &more_is_dummy)?;
 
         // NOTE: not_dummy was moved last to avoid namespace conflicts. Until automatic-namespacing of and! and or! get
         // smarter, put shared elements last and unique ones first.
@@ -2861,11 +2977,13 @@ fn reduce_cons<F: LurkField, CS: ConstraintSystem<F>, C: Coprocessor<F>>(
             not_dummy
         )?;
 
+        // (FN ARG . MORE) => ((FN ARG . _) . MORE)
+        // The dummy marks the inner call as expanded.
         let expanded_inner0 = AllocatedPtr::construct_cons_named(
             &mut cs.namespace(|| "expanded_inner0"),
             g,
             &arg1,
-            &g.nil_ptr,
+            &g.dummy_arg_ptr,
             ConsName::ExpandedInner0,
             allocated_cons_witness,
             &fn_not_dummy,
@@ -3198,15 +3316,16 @@ fn apply_continuation<F: LurkField, CS: ConstraintSystem<F>>(
 
     // Continuation::Call preimage
     /////////////////////////////////////////////////////////////////////////////
-    let (saved_env, continuation, function) = {
+    let (saved_env, continuation, function, expanded) = {
         (
             AllocatedPtr::by_index(0, &continuation_components),
             AllocatedContPtr::by_index(2, &continuation_components),
             result,
+            AllocatedPtr::by_index(3, &continuation_components),
         )
     };
     let call_components: &[&dyn AsAllocatedHashComponents<F>; 4] =
-        &[&saved_env, function, &continuation, default_num_pair];
+        &[&saved_env, function, &continuation, &expanded];
     hash_default_results.add_hash_input_clauses(
         ContTag::Call.to_field(),
         &g.call2_cont_tag,
@@ -3215,23 +3334,105 @@ fn apply_continuation<F: LurkField, CS: ConstraintSystem<F>>(
 
     // Continuation::Call2 preimage
     /////////////////////////////////////////////////////////////////////////////
-    let (saved_env, continuation) = {
+    // The function is allocated here, since a variadic function's newer cont is not a Tail.
+    let call2_fun = {
+        let mut cs = cs.namespace(|| "Call2 preimage");
+        let (saved_env, fun, continuation, expanded) = {
+            (
+                AllocatedPtr::by_index(0, &continuation_components),
+                AllocatedPtr::by_index(1, &continuation_components),
+                AllocatedContPtr::by_index(2, &continuation_components),
+                AllocatedPtr::by_index(3, &continuation_components),
+            )
+        };
+
+        let cont_is_call2_precomp = cont.alloc_tag_equal(
+            &mut cs.namespace(|| "cont_is_call2_precomp"),
+            ContTag::Call2.to_field(),
+        )?;
+        let cont_is_call2_and_not_dummy = and!(cs, &cont_is_call2_precomp, not_dummy)?;
+
+        // NOTE: this allocation is unconstrained. See necessary constraint immediately below.
+        let (hash, arg_t, body_t, closed_env) = Ptr::allocate_maybe_fun_unconstrained(
+            &mut cs.namespace(|| "allocate fun"),
+            store,
+            fun.ptr(store).as_ref(),
+        )?;
+
+        // Without this, fun is unconstrained.
+        implies_equal!(cs, &cont_is_call2_and_not_dummy, fun.hash(), &hash);
+
+        let args_is_dummy = arg_t.alloc_equal(
+            &mut cs.namespace(|| "cont2 args_is_dummy"),
+            &g.dummy_arg_ptr,
+        )?;
+
+        let cont_is_call2_and_not_dummy_and_not_dummy_args =
+            and!(cs, &cont_is_call2_and_not_dummy, &args_is_dummy.not())?;
+
+        let (body_form, end) = car_cdr_named(
+            &mut cs.namespace(|| "body_form"),
+            g,
+            &body_t,
+            ConsName::FunBody,
+            allocated_cons_witness,
+            &cont_is_call2_and_not_dummy_and_not_dummy_args,
+            store,
+        )?;
+
+        let end_is_nil = end.is_nil(&mut cs.namespace(|| "end_is_nil"), g)?;
+
+        // A variadic function's body is the form (LAMBDA (REST) . STUFF) rather than a list of one
+        // form. It continues with a Call2 of the rest gatherer if its call was expanded from a
+        // multi-arg call, and with a Call of NIL otherwise. Both keep the saved env and
+        // continuation.
+        let body_form_is_lambda =
+            body_form.alloc_equal(&mut cs.namespace(|| "body_form_is_lambda"), &g.lambda_sym)?;
+        let fun_is_variadic = and!(cs, &body_form_is_lambda, &end_is_nil.not())?;
+        let is_expanded =
+            alloc_equal_const(&mut cs.namespace(|| "is_expanded"), expanded.tag(), F::ONE)?;
+        let continuation_is_call = continuation.alloc_tag_equal(
+            &mut cs.namespace(|| "continuation_is_call"),
+            ContTag::Call.to_field(),
+        )?;
+        let more_args = and!(cs, &is_expanded, &continuation_is_call)?;
+
+        let variadic_cont_tag = pick!(cs, &more_args, &g.call2_cont_tag, &g.call_cont_tag)?;
+        let newer_cont_tag = pick!(cs, &fun_is_variadic, &variadic_cont_tag, &g.tail_cont_tag)?;
+        let variadic_arg = pick_ptr!(cs, &more_args, &g.rest_gatherer_ptr, &g.nil_ptr)?;
+        let component1 = AllocatedPtr::from_parts(
+            pick!(cs, &fun_is_variadic, variadic_arg.tag(), continuation.tag())?,
+            pick!(
+                cs,
+                &fun_is_variadic,
+                variadic_arg.hash(),
+                continuation.hash()
+            )?,
+        );
+        let component2 = AllocatedPtr::from_parts(
+            pick!(cs, &fun_is_variadic, continuation.tag(), &g.default_num)?,
+            pick!(cs, &fun_is_variadic, continuation.hash(), &g.default_num)?,
+        );
+
+        let call2_components: &[&dyn AsAllocatedHashComponents<F>; 4] =
+            &[&saved_env, &component1, &component2, default_num_pair];
+        hash_default_results.add_hash_input_clauses(
+            ContTag::Call2.to_field(),
+            &newer_cont_tag,
+            call2_components,
+        );
+
         (
-            AllocatedPtr::by_index(0, &continuation_components),
-            AllocatedContPtr::by_index(2, &continuation_components),
+            cont_is_call2_and_not_dummy,
+            args_is_dummy,
+            arg_t,
+            body_t,
+            closed_env,
+            body_form,
+            end_is_nil,
+            fun_is_variadic,
         )
     };
-    let call2_components: &[&dyn AsAllocatedHashComponents<F>; 4] = &[
-        &saved_env,
-        &continuation,
-        default_num_pair,
-        default_num_pair,
-    ];
-    hash_default_results.add_hash_input_clauses(
-        ContTag::Call2.to_field(),
-        &g.tail_cont_tag,
-        call2_components,
-    );
 
     // Continuation::Let preimage
     /////////////////////////////////////////////////////////////////////////////
@@ -3658,49 +3859,25 @@ fn apply_continuation<F: LurkField, CS: ConstraintSystem<F>>(
     /////////////////////////////////////////////////////////////////////////////
     let (the_expr, the_env, the_cont, newer_cont2_not_dummy) = {
         let mut cs = cs.namespace(|| "Call2");
-        let fun = AllocatedPtr::by_index(1, &continuation_components);
         let continuation = AllocatedContPtr::by_index(2, &continuation_components);
+        let (
+            cont_is_call2_and_not_dummy,
+            args_is_dummy,
+            arg_t,
+            body_t,
+            closed_env,
+            body_form,
+            end_is_nil,
+            fun_is_variadic,
+        ) = call2_fun;
 
         {
-            let cont_is_call2_precomp = cont.alloc_tag_equal(
-                &mut cs.namespace(|| "cont_is_call2_precomp"),
-                ContTag::Call2.to_field(),
-            )?;
-            let cont_is_call2_and_not_dummy = and!(cs, &cont_is_call2_precomp, not_dummy)?;
-
-            // NOTE: this allocation is unconstrained. See necessary constraint immediately below.
-            let (hash, arg_t, body_t, closed_env) = Ptr::allocate_maybe_fun_unconstrained(
-                &mut cs.namespace(|| "allocate fun"),
-                store,
-                fun.ptr(store).as_ref(),
-            )?;
-
-            // Without this, fun is unconstrained.
-            implies_equal!(cs, &cont_is_call2_and_not_dummy, fun.hash(), &hash);
-
-            let args_is_dummy = arg_t.alloc_equal(
-                &mut cs.namespace(|| "cont2 args_is_dummy"),
-                &g.dummy_arg_ptr,
-            )?;
-            let args_is_not_dummy = args_is_dummy.not();
-
-            let cont_is_call2_and_not_dummy_and_not_dummy_args =
-                and!(cs, &cont_is_call2_and_not_dummy, &args_is_not_dummy)?;
-
             let body_t_is_nil = body_t.is_nil(&mut cs.namespace(|| "body_t_is_nil"), g)?;
 
-            let (body_form, end) = car_cdr_named(
-                &mut cs.namespace(|| "body_form"),
-                g,
-                &body_t,
-                ConsName::FunBody,
-                allocated_cons_witness,
-                &cont_is_call2_and_not_dummy_and_not_dummy_args,
-                store,
-            )?;
+            let body_is_well_formed0 = and!(cs, &body_t_is_nil.not(), &end_is_nil)?;
+            let body_is_well_formed = or!(cs, &body_is_well_formed0, &fun_is_variadic)?;
 
-            let end_is_nil = end.is_nil(&mut cs.namespace(|| "end_is_nil"), g)?;
-            let body_is_well_formed = and!(cs, &body_t_is_nil.not(), &end_is_nil)?;
+            let body_expr = pick_ptr!(cs, &fun_is_variadic, &body_t, &body_form)?;
 
             let extend_not_dummy = and!(
                 cs,
@@ -3724,10 +3901,11 @@ fn apply_continuation<F: LurkField, CS: ConstraintSystem<F>>(
                 &mut cs.namespace(|| "continuation is tail"),
                 ContTag::Tail.to_field(),
             )?;
+            let reuse_continuation = and!(cs, &continuation_is_tail, &fun_is_variadic.not())?;
 
             let tail_cont = AllocatedContPtr::pick(
                 &mut cs.namespace(|| "the tail continuation"),
-                &continuation_is_tail,
+                &reuse_continuation,
                 &continuation,
                 &newer_cont2,
             );
@@ -3746,9 +3924,9 @@ fn apply_continuation<F: LurkField, CS: ConstraintSystem<F>>(
                 AllocatedPtr::pick(&mut cs.namespace(|| "the_env"), &cond, &newer_env, env)?;
 
             let the_expr =
-                AllocatedPtr::pick(&mut cs.namespace(|| "the_expr"), &cond, &body_form, result)?;
+                AllocatedPtr::pick(&mut cs.namespace(|| "the_expr"), &cond, &body_expr, result)?;
 
-            let newer_cont2_not_dummy0 = and!(cs, &continuation_is_tail.not(), &cond)?;
+            let newer_cont2_not_dummy0 = and!(cs, &reuse_continuation.not(), &cond)?;
             let newer_cont2_not_dummy = boolean_num!(cs, &newer_cont2_not_dummy0)?;
 
             (the_expr, the_env, the_cont, newer_cont2_not_dummy)
@@ -4199,14 +4377,46 @@ fn apply_continuation<F: LurkField, CS: ConstraintSystem<F>>(
             &op2_is_mod,
             &both_args_are_u64s.not(),
         )?;
-        // include u64 mod
-        let arithmetic_result = AllocatedPtr::pick(
-            &mut cs.namespace(|| "arithmetic result"),
+        let include_u64_mod = AllocatedPtr::pick(
+            &mut cs.namespace(|| "include u64 mod"),
             &op2_is_mod_and_args_are_u64s,
             &alloc_r_ptr,
             &include_u64_quotient,
         )?;
 
+        let both_args_are_u64s_and_not_dummy = Boolean::and(
+            &mut cs.namespace(|| "both args are u64s and not dummy"),
+            &both_args_are_u64s,
+            &not_dummy,
+        )?;
+        let (is_bitwise_tag, bitwise_val) = bitwise_helper(
+            &mut cs.namespace(|| "enforce bitwise"),
+            g,
+            &both_args_are_u64s_and_not_dummy,
+            arg1.hash(),
+            arg2.hash(),
+            op2.tag(),
+        )?;
+        let bitwise_ptr = AllocatedPtr::from_parts(g.u64_tag.clone(), bitwise_val);
+
+        let op2_is_bitwise_and_args_are_u64s = Boolean::and(
+            &mut cs.namespace(|| "op2 is bitwise and args are u64s"),
+            &is_bitwise_tag,
+            &both_args_are_u64s,
+        )?;
+        let op2_is_bitwise_and_args_are_not_u64s = Boolean::and(
+            &mut cs.namespace(|| "op2 is bitwise and args are not u64s"),
+            &is_bitwise_tag,
+            &both_args_are_u64s.not(),
+        )?;
+        // include u64 bitwise operations and shifts
+        let arithmetic_result = AllocatedPtr::pick(
+            &mut cs.namespace(|| "arithmetic result"),
+            &op2_is_bitwise_and_args_are_u64s,
+            &bitwise_ptr,
+            &include_u64_mod,
+        )?;
+
         let valid_types = or(
             &mut cs.namespace(|| "Op2 called with valid types"),
             &is_cons_or_strcons_or_hide_or_equal,
@@ -4244,6 +4454,7 @@ fn apply_continuation<F: LurkField, CS: ConstraintSystem<F>>(
             &invalid_strcons_tag,
             &op2_is_hide_and_arg1_is_not_num,
             &op2_is_mod_and_args_are_not_u64s,
+            &op2_is_bitwise_and_args_are_not_u64s,
             &invalid_secret_tag_hide
         )?;
 
@@ -5056,6 +5267,43 @@ fn enforce_u64_div_mod<F: LurkField, CS: ConstraintSystem<F>>(
     Ok((alloc_q_num, alloc_r_num))
 }
 
+// This function helps to enforce the bitwise operations and shifts on U64s `a` and `b`.
+// Given that `cond` is satisfied, the results of all five operations are enforced by
+// `u64_bitwise`. The last argument is `op2`, which selects among the results. The returned
+// Boolean is true iff `op2` is one of AND, OR, XOR, SHL or SHR.
+fn bitwise_helper<F: LurkField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    g: &GlobalAllocations<F>,
+    cond: &Boolean,
+    a: &AllocatedNum<F>,
+    b: &AllocatedNum<F>,
+    op2: &AllocatedNum<F>,
+) -> Result<(Boolean, AllocatedNum<F>), SynthesisError> {
+    let U64Bitwise {
+        and: and_num,
+        or: or_num,
+        xor: xor_num,
+        shl: shl_num,
+        shr: shr_num,
+    } = u64_bitwise(&mut cs.namespace(|| "u64 bitwise"), cond, a, b)?;
+
+    let (bitwise_result, is_default) = multi_case_aux(
+        &mut cs.namespace(|| "bitwise case"),
+        op2,
+        &[&[
+            CaseClause::new(Op2::BitAnd.to_field(), &and_num),
+            CaseClause::new(Op2::BitOr.to_field(), &or_num),
+            CaseClause::new(Op2::BitXor.to_field(), &xor_num),
+            CaseClause::new(Op2::ShiftLeft.to_field(), &shl_num),
+            CaseClause::new(Op2::ShiftRight.to_field(), &shr_num),
+        ]],
+        &[&g.default_num],
+        g,
+    )?;
+
+    Ok((is_default.not(), bitwise_result[0].clone()))
+}
+
 // Given that `cond` is satisfied, enforce the num < bound.
 // This is done by proving (bound - num) is positive.
 // `num` and `bound` must be a positive field element.
@@ -5406,6 +5654,8 @@ pub(crate) fn print_cs<F: LurkField, C: Comparable<F>>(this: &C) -> String {
 mod tests {
     use super::*;
     use crate::circuit::circuit_frame::constraints::{popcount_equal, sub};
+    use crate::coprocessor::test::DumbCoprocessor;
+    use crate::eval::tests::coproc::DumbCoproc;
     use crate::eval::{
         empty_sym_env,
         lang::{Coproc, Lang},
@@ -5413,6 +5663,7 @@ mod tests {
     };
     use crate::proof::groth16::Groth16Prover;
     use crate::proof::{Provable, Prover};
+    use crate::state::user_sym;
     use crate::store::Store;
     use bellpepper::util_cs::metric_cs::MetricCS;
     use bellpepper_core::test_cs::TestConstraintSystem;
@@ -5424,6 +5675,30 @@ mod tests {
 
     const DEFAULT_REDUCTION_COUNT: usize = 1;
 
+    #[test]
+    fn test_circuit_metrics() {
+        let lang = Arc::new(Lang::<Fr, Coproc<Fr>>::new());
+        let blank = MultiFrame::<Fr, IO<Fr>, Witness<Fr>, _>::blank(DEFAULT_REDUCTION_COUNT, lang);
+        let metrics = blank.metrics();
+        // the same counts as `num_self_evaluating` checks for a real frame
+        assert_eq!(12684, metrics.constraints);
+        assert_eq!(13, metrics.inputs);
+        assert!(metrics.aux > 0);
+        assert_eq!(metrics, blank.metrics());
+
+        let s = &mut Store::<Fr>::default();
+        let lang = Lang::<Fr, DumbCoproc<Fr>>::new_with_bindings(
+            s,
+            vec![(user_sym("cproc-dumb"), DumbCoprocessor::new().into())],
+        );
+        let with_coprocessor = MultiFrame::<Fr, IO<Fr>, Witness<Fr>, _>::blank(
+            DEFAULT_REDUCTION_COUNT,
+            Arc::new(lang),
+        )
+        .metrics();
+        assert!(with_coprocessor.constraints > metrics.constraints);
+    }
+
     #[test]
     fn num_self_evaluating() {
         let mut store = Store::default();
@@ -5490,9 +5765,9 @@ mod tests {
             assert!(delta == Delta::Equal);
 
             //println!("{}", print_cs(&cs));
-            assert_eq!(12032, cs.num_constraints());
+            assert_eq!(12684, cs.num_constraints());
             assert_eq!(13, cs.num_inputs());
-            assert_eq!(11688, cs.aux().len());
+            assert_eq!(12323, cs.aux().len());
 
             let public_inputs = multiframe.public_inputs();
             let mut rng = rand::thread_rng();
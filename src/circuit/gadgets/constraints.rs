@@ -802,6 +802,230 @@ pub(crate) fn allocate_is_negative<F: LurkField, CS: ConstraintSystem<F>>(
     Ok(num_is_negative.clone())
 }
 
+/// The results of the bitwise operations and shifts on two U64s, as computed by `u64_bitwise`.
+pub(crate) struct U64Bitwise<F: PrimeField> {
+    pub(crate) and: AllocatedNum<F>,
+    pub(crate) or: AllocatedNum<F>,
+    pub(crate) xor: AllocatedNum<F>,
+    pub(crate) shl: AllocatedNum<F>,
+    pub(crate) shr: AllocatedNum<F>,
+}
+
+// Allocate the 64 little-endian bits of `num`, which are enforced to pack to `num` iff `cond`
+// is true. Otherwise, `num` need not fit in 64 bits, and the bits are unconstrained.
+fn alloc_u64_bits<F: LurkField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    cond: &Boolean,
+    num: &AllocatedNum<F>,
+) -> Result<Vec<Boolean>, SynthesisError> {
+    let value = num.get_value().map(|v| v.to_u64_unchecked());
+
+    let bits = (0..64)
+        .map(|i| {
+            AllocatedBit::alloc(
+                cs.namespace(|| format!("bit {i}")),
+                value.map(|v| (v >> i) & 1 == 1),
+            )
+            .map(Boolean::Is)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let bits_lc = pack_lc::<F, CS>(&bits)?;
+    enforce_implication_lc_zero(&mut cs.namespace(|| "pack"), cond, |_| {
+        bits_lc - num.get_variable()
+    })?;
+
+    Ok(bits)
+}
+
+// The linear combination packing the little-endian `bits`.
+fn pack_lc<F: PrimeField, CS: ConstraintSystem<F>>(
+    bits: &[Boolean],
+) -> Result<LinearCombination<F>, SynthesisError> {
+    let mut coeff = F::ONE;
+    let mut lc = LinearCombination::<F>::zero();
+    for bit in bits {
+        lc = add_to_lc::<F, CS>(bit, lc, coeff)?;
+        coeff = coeff.double();
+    }
+    Ok(lc)
+}
+
+// The value packed from the little-endian `bits`, if they are all known.
+fn pack_value<F: PrimeField>(bits: &[Boolean]) -> Option<F> {
+    bits.iter().rev().try_fold(F::ZERO, |acc, bit| {
+        bit.get_value()
+            .map(|bit| acc.double() + if bit { F::ONE } else { F::ZERO })
+    })
+}
+
+// Allocate a number equal to `a_lc * b_lc`, whose value is `value`.
+fn alloc_product<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    value: Option<F>,
+    a_lc: &LinearCombination<F>,
+    b_lc: &LinearCombination<F>,
+) -> Result<AllocatedNum<F>, SynthesisError> {
+    let num = AllocatedNum::alloc(cs.namespace(|| "num"), || {
+        value.ok_or(SynthesisError::AssignmentMissing)
+    })?;
+    cs.enforce(
+        || "product",
+        |_| a_lc.clone(),
+        |_| b_lc.clone(),
+        |lc| lc + num.get_variable(),
+    );
+    Ok(num)
+}
+
+/// Computes the bitwise AND, OR and XOR of the U64s `a` and `b`, and the shifts of `a` by `b`
+/// bits to the left and to the right. Shifting by 64 or more bits gives zero.
+///
+/// The results are only enforced iff `cond` is true; otherwise `a` and `b` need not fit in 64
+/// bits and the results are unconstrained, but always satisfiable. Only the two inputs are
+/// decomposed into bits: OR and XOR follow from AND as `a + b - and` and `a + b - 2 * and`,
+/// and with `k` the low 6 bits of `b`, the shifts follow from `2^k` and from the bits of `a`
+/// below and above position `k`, so that `a = shr * 2^k + low` and `shl = high * 2^k`.
+pub(crate) fn u64_bitwise<F: LurkField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    cond: &Boolean,
+    a: &AllocatedNum<F>,
+    b: &AllocatedNum<F>,
+) -> Result<U64Bitwise<F>, SynthesisError> {
+    let a_bits = alloc_u64_bits(&mut cs.namespace(|| "a bits"), cond, a)?;
+    let b_bits = alloc_u64_bits(&mut cs.namespace(|| "b bits"), cond, b)?;
+    let ab_lc = LinearCombination::<F>::zero() + a.get_variable() + b.get_variable();
+    let ab_value = a.get_value().zip(b.get_value()).map(|(a, b)| a + b);
+    let one_lc = LinearCombination::<F>::zero() + CS::one();
+
+    let and_bits = a_bits
+        .iter()
+        .zip(&b_bits)
+        .enumerate()
+        .map(|(i, (x, y))| Boolean::and(cs.namespace(|| format!("and {i}")), x, y))
+        .collect::<Result<Vec<_>, _>>()?;
+    let and_lc = pack_lc::<F, CS>(&and_bits)?;
+    let and_value = pack_value::<F>(&and_bits);
+    let and_num = alloc_product(cs.namespace(|| "and"), and_value, &and_lc, &one_lc)?;
+    let or_num = alloc_product(
+        cs.namespace(|| "or"),
+        ab_value.zip(and_value).map(|(ab, and)| ab - and),
+        &(ab_lc.clone() - &and_lc),
+        &one_lc,
+    )?;
+    let xor_num = alloc_product(
+        cs.namespace(|| "xor"),
+        ab_value.zip(and_value).map(|(ab, and)| ab - and.double()),
+        &(ab_lc - (F::ONE.double(), &and_lc)),
+        &one_lc,
+    )?;
+
+    // If any bit of `b` above the low 6 is set, the shift amount is at least 64.
+    let b_is_at_least_64 = or_v_unchecked_for_optimization(
+        &mut cs.namespace(|| "b is at least 64"),
+        &b_bits[6..].iter().collect::<Vec<_>>(),
+    )?;
+
+    // `greater[i]` is true iff `k > i`. Adding the bit of weight `2^s` on top of the lower
+    // bits, for which `greater` has `2^s` entries, `k > i` holds iff the bit is set or the
+    // lower bits were already greater than `i` (for `i < 2^s`), or both the bit is set and
+    // the lower bits are greater than `i - 2^s` (otherwise).
+    let mut greater = vec![Boolean::Constant(false)];
+    for (s, bit) in b_bits[..6].iter().enumerate() {
+        let mut cs = cs.namespace(|| format!("greater {s}"));
+        let mut next = Vec::with_capacity(2 * greater.len());
+        for (i, g) in greater.iter().enumerate() {
+            next.push(or(cs.namespace(|| format!("or {i}")), bit, g)?);
+        }
+        for (i, g) in greater.iter().enumerate() {
+            next.push(Boolean::and(cs.namespace(|| format!("and {i}")), bit, g)?);
+        }
+        greater = next;
+    }
+
+    // The bits of `a` below position `k`, and those that stay within 64 bits when shifted
+    // left by `k`, i.e. below position `64 - k`.
+    let low_bits = a_bits
+        .iter()
+        .zip(&greater)
+        .enumerate()
+        .map(|(i, (x, g))| Boolean::and(cs.namespace(|| format!("low {i}")), x, g))
+        .collect::<Result<Vec<_>, _>>()?;
+    let kept_bits = a_bits
+        .iter()
+        .zip(greater.iter().rev())
+        .enumerate()
+        .map(|(i, (x, g))| Boolean::and(cs.namespace(|| format!("kept {i}")), x, &g.not()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // `pow = 2^k`, as the product of `2^(2^s)` over the set bits `s` of `k`.
+    let mut pow_lc = one_lc.clone();
+    let mut pow_value = Some(F::ONE);
+    let mut factor = F::ONE.double();
+    for (s, bit) in b_bits[..6].iter().enumerate() {
+        let bit_lc = bit.lc(CS::one(), factor - F::ONE) + CS::one();
+        let value = pow_value
+            .zip(bit.get_value())
+            .map(|(pow, bit)| if bit { pow * factor } else { pow });
+        let pow = alloc_product(cs.namespace(|| format!("pow {s}")), value, &pow_lc, &bit_lc)?;
+        pow_lc = LinearCombination::<F>::zero() + pow.get_variable();
+        pow_value = value;
+        factor = factor.square();
+    }
+
+    // `shr * pow = a - low`, which has a unique solution since `pow` is never zero.
+    let low_lc = pack_lc::<F, CS>(&low_bits)?;
+    let shr_value = a
+        .get_value()
+        .zip(pack_value::<F>(&low_bits))
+        .zip(pow_value)
+        .map(|((a, low), pow)| (a - low) * pow.invert().unwrap());
+    let shr_raw = AllocatedNum::alloc(cs.namespace(|| "shr raw"), || {
+        shr_value.ok_or(SynthesisError::AssignmentMissing)
+    })?;
+    cs.enforce(
+        || "shr raw times pow",
+        |lc| lc + shr_raw.get_variable(),
+        |_| pow_lc.clone(),
+        |lc| lc + a.get_variable() - &low_lc,
+    );
+    let kept_lc = pack_lc::<F, CS>(&kept_bits)?;
+    let shl_raw = alloc_product(
+        cs.namespace(|| "shl raw"),
+        pack_value::<F>(&kept_bits)
+            .zip(pow_value)
+            .map(|(kept, pow)| kept * pow),
+        &kept_lc,
+        &pow_lc,
+    )?;
+
+    let below_64 = b_is_at_least_64.not();
+    let below_64_lc = below_64.lc(CS::one(), F::ONE);
+    let below_64_value = below_64
+        .get_value()
+        .map(|b| if b { F::ONE } else { F::ZERO });
+    let shl = alloc_product(
+        cs.namespace(|| "shl"),
+        shl_raw.get_value().zip(below_64_value).map(|(x, b)| x * b),
+        &(LinearCombination::<F>::zero() + shl_raw.get_variable()),
+        &below_64_lc,
+    )?;
+    let shr = alloc_product(
+        cs.namespace(|| "shr"),
+        shr_raw.get_value().zip(below_64_value).map(|(x, b)| x * b),
+        &(LinearCombination::<F>::zero() + shr_raw.get_variable()),
+        &below_64_lc,
+    )?;
+
+    Ok(U64Bitwise {
+        and: and_num,
+        or: or_num,
+        xor: xor_num,
+        shl,
+        shr,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1025,6 +1249,41 @@ mod tests {
 
         }
 
+        #[test]
+        fn prop_u64_bitwise((a, b) in any::<(u64, u64)>(), shift in 0u64..80) {
+            let test_a_b = |a: u64, b: u64| {
+                let mut cs = TestConstraintSystem::<Fr>::new();
+                let cond = Boolean::Constant(true);
+                let a_num = AllocatedNum::alloc(cs.namespace(|| "a"), || Ok(Fr::from(a))).unwrap();
+                let b_num = AllocatedNum::alloc(cs.namespace(|| "b"), || Ok(Fr::from(b))).unwrap();
+                let res = u64_bitwise(cs.namespace(|| "bitwise"), &cond, &a_num, &b_num).unwrap();
+
+                let shl = if b < 64 { a << b } else { 0 };
+                let shr = if b < 64 { a >> b } else { 0 };
+                assert_eq!(res.and.get_value(), Some(Fr::from(a & b)));
+                assert_eq!(res.or.get_value(), Some(Fr::from(a | b)));
+                assert_eq!(res.xor.get_value(), Some(Fr::from(a ^ b)));
+                assert_eq!(res.shl.get_value(), Some(Fr::from(shl)));
+                assert_eq!(res.shr.get_value(), Some(Fr::from(shr)));
+                assert!(cs.is_satisfied());
+                assert_eq!(450, cs.num_constraints());
+                assert_eq!(449, cs.aux().len());
+            };
+
+            test_a_b(a, b);
+            test_a_b(a, shift);
+        }
+
+        #[test]
+        fn prop_u64_bitwise_unenforced(x in any::<FWrap<Fr>>()) {
+            // Without `cond`, arguments which don't fit in 64 bits must still be satisfiable.
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let cond = Boolean::Constant(false);
+            let a = AllocatedNum::alloc(cs.namespace(|| "a"), || Ok(x.0)).unwrap();
+            u64_bitwise(cs.namespace(|| "bitwise"), &cond, &a, &a).unwrap();
+            assert!(cs.is_satisfied());
+        }
+
         #[test]
         fn prop_alloc_equal_const((x, y) in any::<(FWrap<Fr>, FWrap<Fr>)>()) {
             let mut cs = TestConstraintSystem::<Fr>::new();
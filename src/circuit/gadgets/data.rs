@@ -75,8 +75,14 @@ pub struct GlobalAllocations<F: LurkField> {
     pub op2_less_equal_tag: AllocatedNum<F>,
     pub op2_greater_tag: AllocatedNum<F>,
     pub op2_greater_equal_tag: AllocatedNum<F>,
+    pub op2_bit_and_tag: AllocatedNum<F>,
+    pub op2_bit_or_tag: AllocatedNum<F>,
+    pub op2_bit_xor_tag: AllocatedNum<F>,
+    pub op2_shift_left_tag: AllocatedNum<F>,
+    pub op2_shift_right_tag: AllocatedNum<F>,
 
     pub lambda_sym: AllocatedPtr<F>,
+    pub rest_gatherer_ptr: AllocatedPtr<F>,
 
     pub true_num: AllocatedNum<F>,
     pub false_num: AllocatedNum<F>,
@@ -188,6 +194,16 @@ impl<F: LurkField> GlobalAllocations<F> {
             Op2::Greater.allocate_constant(&mut cs.namespace(|| "op2_greater_tag"))?;
         let op2_greater_equal_tag =
             Op2::GreaterEqual.allocate_constant(&mut cs.namespace(|| "op2_greater_equal_tag"))?;
+        let op2_bit_and_tag =
+            Op2::BitAnd.allocate_constant(&mut cs.namespace(|| "op2_bit_and_tag"))?;
+        let op2_bit_or_tag =
+            Op2::BitOr.allocate_constant(&mut cs.namespace(|| "op2_bit_or_tag"))?;
+        let op2_bit_xor_tag =
+            Op2::BitXor.allocate_constant(&mut cs.namespace(|| "op2_bit_xor_tag"))?;
+        let op2_shift_left_tag =
+            Op2::ShiftLeft.allocate_constant(&mut cs.namespace(|| "op2_shift_left_tag"))?;
+        let op2_shift_right_tag =
+            Op2::ShiftRight.allocate_constant(&mut cs.namespace(|| "op2_shift_right_tag"))?;
         let op2_equal_tag = AllocatedNum::alloc(&mut cs.namespace(|| "op2_equal_tag"), || {
             Ok(Op2::Equal.to_field())
         })?;
@@ -205,6 +221,7 @@ impl<F: LurkField> GlobalAllocations<F> {
         defsym!(t_ptr, "t", t);
         defsym!(dummy_arg_ptr, "_", dummy);
         defsym!(lambda_sym, "lambda", lambda);
+        defsym!(rest_gatherer_ptr, "rest_gatherer", rest_gatherer);
 
         let true_num = allocate_constant(&mut cs.namespace(|| "true"), F::ONE)?;
         let false_num = allocate_constant(&mut cs.namespace(|| "false"), F::ZERO)?;
@@ -272,7 +289,13 @@ impl<F: LurkField> GlobalAllocations<F> {
             op2_less_equal_tag,
             op2_greater_tag,
             op2_greater_equal_tag,
+            op2_bit_and_tag,
+            op2_bit_or_tag,
+            op2_bit_xor_tag,
+            op2_shift_left_tag,
+            op2_shift_right_tag,
             lambda_sym,
+            rest_gatherer_ptr,
             true_num,
             false_num,
             default_num,
@@ -1,6 +1,7 @@
 use ::nova::traits::Group;
 use abomonation::Abomonation;
 use anyhow::Result;
+use camino::Utf8Path;
 use pasta_curves::pallas::Scalar;
 use serde::{Deserialize, Serialize};
 
@@ -15,7 +16,7 @@ use crate::{
 };
 
 use crate::cli::{
-    field_data::{dump, load},
+    field_data::{de, dump, load, ser},
     paths::{proof_meta_path, proof_path, public_params_dir},
 };
 
@@ -39,12 +40,37 @@ pub(crate) struct LurkProofMeta<F: LurkField> {
     pub(crate) zstore: ZStore<F>,
 }
 
+impl<F: LurkField> LurkProofMeta<F> {
+    /// The public inputs and outputs that a proof of this claim must have
+    fn public_io(&self) -> (Vec<F>, Vec<F>) {
+        let io = |expr: &ZExprPtr<F>, env: &ZExprPtr<F>, cont: &ZContPtr<F>| {
+            let ((expr_tag, expr), (env_tag, env)) = (expr.parts(), env.parts());
+            let (cont_tag, cont) = cont.parts();
+            vec![expr_tag, expr, env_tag, env, cont_tag, cont]
+        };
+        (
+            io(&self.expr, &self.env, &self.cont),
+            io(&self.expr_out, &self.env_out, &self.cont_out),
+        )
+    }
+}
+
 impl<F: LurkField> HasFieldModulus for LurkProofMeta<F> {
     fn field_modulus() -> String {
         F::MODULUS.to_owned()
     }
 }
 
+/// A proof bundled with its claim, so that it can be verified from a single file. Both are stored
+/// with the `field_data` protocol.
+#[derive(Serialize, Deserialize)]
+struct LurkProofArtifact {
+    #[serde(with = "serde_bytes")]
+    claim: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    proof: Vec<u8>,
+}
+
 /// Minimal data structure containing just enough for proof verification
 #[derive(Serialize, Deserialize)]
 pub(crate) enum LurkProof<'a, F: CurveCycleEquipped>
@@ -76,6 +102,7 @@ where
 
 impl<F: LurkField + Serialize> LurkProofMeta<F> {
     #[inline]
+    #[cfg_attr(not(feature = "prover"), allow(dead_code))]
     pub(crate) fn persist(self, proof_key: &str) -> Result<()> {
         dump(self, proof_meta_path(proof_key))
     }
@@ -88,6 +115,7 @@ where
     <<G2<F> as Group>::Scalar as ff::PrimeField>::Repr: Abomonation,
 {
     #[inline]
+    #[cfg_attr(not(feature = "prover"), allow(dead_code))]
     pub(crate) fn persist(self, proof_key: &str) -> Result<()> {
         dump(self, proof_path(proof_key))
     }
@@ -111,6 +139,37 @@ impl<'a> LurkProof<'a, Scalar> {
         }
     }
 
+    /// Writes the proof persisted as `proof_key`, along with its claim, as an artifact at `path`
+    pub(crate) fn write_artifact(proof_key: &str, path: &Utf8Path) -> Result<()> {
+        let claim: LurkProofMeta<Scalar> = load(proof_meta_path(proof_key))?;
+        let proof: LurkProof<'_, Scalar> = load(proof_path(proof_key))?;
+        let artifact = LurkProofArtifact {
+            claim: ser(claim)?,
+            proof: ser(proof)?,
+        };
+        Ok(std::fs::write(path, bincode::serialize(&artifact)?)?)
+    }
+
+    /// Verifies the proof in the artifact at `path`, checking that it proves the claim bundled
+    /// with it
+    pub(crate) fn verify_artifact(path: &Utf8Path) -> Result<()> {
+        let artifact: LurkProofArtifact = bincode::deserialize(&std::fs::read(path)?)?;
+        let claim: LurkProofMeta<Scalar> = de(&artifact.claim)?;
+        let lurk_proof: LurkProof<'_, Scalar> = de(&artifact.proof)?;
+        let Self::Nova {
+            public_inputs,
+            public_outputs,
+            ..
+        } = &lurk_proof;
+        let proves_claim = (public_inputs.clone(), public_outputs.clone()) == claim.public_io();
+        if proves_claim && lurk_proof.verify()? {
+            println!("✓ Proof artifact \"{path}\" verified");
+        } else {
+            println!("✗ Proof artifact \"{path}\" failed on verification");
+        }
+        Ok(())
+    }
+
     pub(crate) fn verify_proof(proof_key: &str) -> Result<()> {
         let lurk_proof: LurkProof<'_, Scalar> = load(proof_path(proof_key))?;
         if lurk_proof.verify()? {
@@ -17,8 +17,14 @@ use rustyline_derive::{Completer, Helper, Highlighter, Hinter};
 
 use super::{commitment::Commitment, field_data::load, paths::commitment_path};
 
+#[cfg(feature = "prover")]
 use crate::{
     cli::paths::{proof_path, public_params_dir},
+    proof::{nova::NovaProver, Prover},
+    public_parameters::public_params,
+    z_store::ZStore,
+};
+use crate::{
     eval::{
         lang::{Coproc, Lang},
         Evaluator, Frame, Witness, IO,
@@ -27,19 +33,18 @@ use crate::{
     lurk_sym_ptr,
     package::{Package, SymbolRef},
     parser,
-    proof::{nova::NovaProver, Prover},
     ptr::Ptr,
-    public_parameters::public_params,
     state::State,
     store::Store,
     tag::{ContTag, ExprTag},
-    writer::Write,
+    writer::{pretty, Write},
     z_ptr::ZExprPtr,
-    z_store::ZStore,
     Num, Symbol,
 };
 
-use super::lurk_proof::{LurkProof, LurkProofMeta};
+use super::lurk_proof::LurkProof;
+#[cfg(feature = "prover")]
+use super::lurk_proof::LurkProofMeta;
 
 #[derive(Completer, Helper, Highlighter, Hinter)]
 struct InputValidator {
@@ -131,6 +136,9 @@ fn pad(a: usize, m: usize) -> usize {
     (a + m - 1) / m * m
 }
 
+/// The number of columns `:trace` tries to keep expressions within
+const TRACE_WIDTH: usize = 80;
+
 type F = pasta_curves::pallas::Scalar; // TODO: generalize this
 
 impl Repl<F> {
@@ -199,7 +207,9 @@ impl Repl<F> {
         format!("{backend}_{field}_{rc}_{claim_hash}")
     }
 
-    pub(crate) fn prove_last_frames(&mut self) -> Result<()> {
+    /// Proves the last evaluation, caching the proof in the proofs directory, and returns its key
+    #[cfg(feature = "prover")]
+    pub(crate) fn prove_last_frames(&mut self) -> Result<String> {
         match self.evaluation.as_mut() {
             None => bail!("No evaluation to prove"),
             Some(Evaluation { frames, iterations }) => match self.backend {
@@ -284,13 +294,19 @@ impl Repl<F> {
                     }
                     println!("Claim hash: 0x{claim_hash}");
                     println!("Proof key: \"{proof_key}\"");
-                    Ok(())
+                    Ok(proof_key.to_owned())
                 }
                 Backend::SnarkPackPlus => todo!(),
             },
         }
     }
 
+    /// Builds without the prover can't prove, so `:prove` and `lurk load --prove` fail
+    #[cfg(not(feature = "prover"))]
+    pub(crate) fn prove_last_frames(&mut self) -> Result<String> {
+        bail!("Proving isn't available in a build without the `prover` feature")
+    }
+
     fn hide(&mut self, secret: F, payload: Ptr<F>) -> Result<()> {
         let commitment = Commitment::new(Some(secret), payload, &mut self.store)?;
         let hash_str = &commitment.hash.hex_digits();
@@ -325,6 +341,33 @@ impl Repl<F> {
         Ok(())
     }
 
+    /// Evaluates `expr_ptr`, printing the input of each reduction step, up to `max_frames` of them
+    fn trace(&mut self, expr_ptr: Ptr<F>, max_frames: usize) -> Result<()> {
+        let frames =
+            Evaluator::new(expr_ptr, self.env, &mut self.store, self.limit, &self.lang).trace()?;
+        let state = self.state.borrow();
+        for frame in frames.iter().take(max_frames) {
+            let IO { expr, env, cont } = &frame.input;
+            println!(
+                "{} [{}]: {}",
+                frame.i,
+                cont.tag,
+                pretty(expr, &self.store, &state, TRACE_WIDTH)
+            );
+            println!("  env: {}", pretty(env, &self.store, &state, TRACE_WIDTH));
+        }
+        if frames.len() > max_frames {
+            println!("... {} more frames", frames.len() - max_frames);
+        }
+        if let Some(frame) = frames.last() {
+            println!(
+                "=> {}",
+                pretty(&frame.output.expr, &self.store, &state, TRACE_WIDTH)
+            );
+        }
+        Ok(())
+    }
+
     fn pretty_iterations_display(iterations: usize) -> String {
         if iterations != 1 {
             format!("{iterations} iterations")
@@ -592,15 +635,57 @@ impl Repl<F> {
                 self.env = first_io.expr;
             }
             "prove" => {
+                // With a string argument, the proof of the last evaluation is also written, along
+                // with its claim, as an artifact at that path. Any other argument is evaluated and
+                // then proven.
+                let mut artifact_path = None;
                 if !args.is_nil() {
-                    self.eval_expr_and_memoize(self.peek1(cmd, args)?)?;
+                    let first = self.peek1(cmd, args)?;
+                    if first.tag == ExprTag::Str {
+                        artifact_path = Some(pwd_path.join(self.get_string(&first)?));
+                    } else {
+                        self.eval_expr_and_memoize(first)?;
+                    }
+                }
+                let proof_key = self.prove_last_frames()?;
+                if let Some(artifact_path) = artifact_path {
+                    LurkProof::write_artifact(&proof_key, &artifact_path)?;
+                    println!("Proof artifact: \"{artifact_path}\"");
                 }
-                self.prove_last_frames()?;
             }
             "verify" => {
+                // The argument is either the path of a proof artifact or a proof key
                 let first = self.peek1(cmd, args)?;
                 let proof_id = self.get_string(&first)?;
-                LurkProof::verify_proof(&proof_id)?;
+                let artifact_path = pwd_path.join(&proof_id);
+                if artifact_path.is_file() {
+                    LurkProof::verify_artifact(&artifact_path)?;
+                } else {
+                    LurkProof::verify_proof(&proof_id)?;
+                }
+            }
+            "trace" => {
+                // This: !(:trace 20 (f x))
+                //
+                // Prints at most 20 of the reduction steps of `(f x)`. Without the number, every
+                // step is printed.
+                let (first, rest) = self.store.car_cdr(args)?;
+                if rest.is_nil() {
+                    self.trace(first, usize::MAX)?;
+                } else {
+                    let (_, expr) = self.peek2(cmd, args)?;
+                    let max_frames = match first.tag {
+                        ExprTag::Num => self.store.fetch_num(&first).copied(),
+                        _ => None,
+                    };
+                    let Some(Num::U64(max_frames)) = max_frames else {
+                        bail!(
+                            "Frame limit must be a number. Got {}",
+                            first.fmt_to_string(&self.store, &self.state.borrow())
+                        )
+                    };
+                    self.trace(expr, max_frames as usize)?;
+                }
             }
             "defpackage" => {
                 // TODO: handle args
@@ -713,9 +798,11 @@ impl Repl<F> {
         let input = read_to_string(file_path)?;
         println!("Loading {}", file_path);
 
+        // paths loaded from within the file are relative to its directory
+        let dir_path = file_path.parent().unwrap_or(file_path);
         let mut input = parser::Span::new(&input);
         loop {
-            match self.handle_form(input, file_path) {
+            match self.handle_form(input, dir_path) {
                 Ok(new_input) => input = new_input,
                 Err(e) => {
                     if let Some(parser::Error::NoInput) = e.downcast_ref::<parser::Error>() {
@@ -804,4 +891,43 @@ mod test {
         assert_eq!(pad(610, 10), 610);
         assert_eq!(pad(619, 20), 620);
     }
+
+    #[test]
+    fn test_load_keeps_definitions() {
+        use crate::cli::repl::{Backend, Repl};
+        use crate::lurk_sym_ptr;
+        use crate::parser::Span;
+        use crate::store::Store;
+        use camino::Utf8Path;
+        use std::fs;
+        use tempfile::Builder;
+
+        let dir = Builder::new().prefix("tmp").tempdir().unwrap();
+        let dir_path = Utf8Path::from_path(dir.path()).unwrap();
+        fs::write(
+            dir_path.join("square.lurk"),
+            "!(:def square (lambda (x) (* x x)))",
+        )
+        .unwrap();
+        fs::write(
+            dir_path.join("main.lurk"),
+            "!(:load \"square.lurk\")\n!(:def sixteen (square 4))",
+        )
+        .unwrap();
+
+        let store = Store::default();
+        let nil = lurk_sym_ptr!(&store, nil);
+        let mut repl = Repl::new(store, nil, 1, 100, Backend::Nova);
+        repl.load_file(&dir_path.join("main.lurk")).unwrap();
+
+        // later inputs see the bindings made by the loaded files
+        for (src, expected) in [("(square 5)", 25u64), ("sixteen", 16)] {
+            let (_, expr, _) = repl
+                .store
+                .read_maybe_meta_with_state(repl.state.clone(), Span::new(src))
+                .unwrap();
+            let (output, ..) = repl.eval_expr(expr).unwrap();
+            assert_eq!(repl.store.num(expected), output.expr);
+        }
+    }
 }
@@ -88,6 +88,16 @@ impl Config {
         }
     }
 
+    /// Without the `parallel` feature everything is sequential; with it, the witnesses of
+    /// recursive steps are computed in parallel
+    fn build_default() -> Self {
+        if cfg!(feature = "parallel") {
+            Self::parallel_steps_only()
+        } else {
+            Self::fully_sequential()
+        }
+    }
+
     fn max_parallel_simple() -> Self {
         Self {
             parallelism: ParallelConfig {
@@ -146,5 +156,5 @@ impl TryFrom<&str> for CannedConfig {
 }
 
 fn init_config() -> Config {
-    canned_config_from_env().map_or_else(Config::fully_sequential, |x| x.into())
+    canned_config_from_env().map_or_else(Config::build_default, |x| x.into())
 }
@@ -15,11 +15,16 @@ pub enum Continuation<F: LurkField> {
         unevaled_arg: Ptr<F>,
         saved_env: Ptr<F>,
         continuation: ContPtr<F>,
+        /// Whether this call was expanded from a multi-arg call, so that `continuation` applies
+        /// its result to the remaining arguments
+        expanded: bool,
     },
     Call2 {
         saved_env: Ptr<F>,
         function: Ptr<F>,
         continuation: ContPtr<F>,
+        /// Copied from the `Call` this one continues
+        expanded: bool,
     },
     Tail {
         saved_env: Ptr<F>,
@@ -68,7 +73,91 @@ pub enum Continuation<F: LurkField> {
     Terminal,
 }
 
+/// The operator a `Unop`, `Binop` or `Binop2` continuation applies
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ContOperator {
+    Unop(Op1),
+    Binop(Op2),
+}
+
+/// A continuation's tag and everything it holds, in one shape for all continuations, so that
+/// tools like debuggers can show what a continuation will do next without matching on each kind.
+/// See `Store::describe_cont`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContDescription<F: LurkField> {
+    pub tag: ContTag,
+    pub operator: Option<ContOperator>,
+    /// The saved expressions and environments, named as in `Continuation`
+    pub ptrs: Vec<(&'static str, Ptr<F>)>,
+    /// The continuation this one continues with, if any
+    pub continuation: Option<ContPtr<F>>,
+}
+
 impl<F: LurkField> Continuation<F> {
+    pub fn describe(&self) -> ContDescription<F> {
+        let (operator, ptrs) = match *self {
+            Self::Outermost | Self::Error | Self::Dummy | Self::Terminal | Self::Emit { .. } => {
+                (None, vec![])
+            }
+            Self::Call0 { saved_env, .. }
+            | Self::Tail { saved_env, .. }
+            | Self::Lookup { saved_env, .. } => (None, vec![("saved_env", saved_env)]),
+            Self::Call {
+                unevaled_arg,
+                saved_env,
+                ..
+            } => (
+                None,
+                vec![("unevaled_arg", unevaled_arg), ("saved_env", saved_env)],
+            ),
+            Self::Call2 {
+                saved_env,
+                function,
+                ..
+            } => (None, vec![("saved_env", saved_env), ("function", function)]),
+            Self::Unop { operator, .. } => (Some(ContOperator::Unop(operator)), vec![]),
+            Self::Binop {
+                operator,
+                saved_env,
+                unevaled_args,
+                ..
+            } => (
+                Some(ContOperator::Binop(operator)),
+                vec![("saved_env", saved_env), ("unevaled_args", unevaled_args)],
+            ),
+            Self::Binop2 {
+                operator,
+                evaled_arg,
+                ..
+            } => (
+                Some(ContOperator::Binop(operator)),
+                vec![("evaled_arg", evaled_arg)],
+            ),
+            Self::If { unevaled_args, .. } => (None, vec![("unevaled_args", unevaled_args)]),
+            Self::Let {
+                var,
+                body,
+                saved_env,
+                ..
+            }
+            | Self::LetRec {
+                var,
+                body,
+                saved_env,
+                ..
+            } => (
+                None,
+                vec![("var", var), ("body", body), ("saved_env", saved_env)],
+            ),
+        };
+        ContDescription {
+            tag: self.cont_tag(),
+            operator,
+            ptrs,
+            continuation: self.continuation(),
+        }
+    }
+
     pub(crate) fn intern_aux(&self, store: &mut crate::store::Store<F>) -> ContPtr<F> {
         match self {
             Self::Outermost | Self::Dummy | Self::Error | Self::Terminal => {
@@ -78,6 +167,7 @@ impl<F: LurkField> Continuation<F> {
             }
             _ => {
                 let (p, inserted) = self.insert_in_store(store);
+                store.count_intern(inserted);
                 let ptr = ContPtr::index(self.cont_tag(), p);
                 if inserted {
                     store.dehydrated_cont.push(ptr)
@@ -97,16 +187,20 @@ impl<F: LurkField> Continuation<F> {
                 unevaled_arg,
                 saved_env,
                 continuation,
-            } => store
-                .call_store
-                .insert_full((*unevaled_arg, *saved_env, *continuation)),
+                expanded,
+            } => {
+                store
+                    .call_store
+                    .insert_full((*unevaled_arg, *saved_env, *continuation, *expanded))
+            }
             Self::Call2 {
                 function,
                 saved_env,
                 continuation,
+                expanded,
             } => store
                 .call2_store
-                .insert_full((*function, *saved_env, *continuation)),
+                .insert_full((*function, *saved_env, *continuation, *expanded)),
             Self::Tail {
                 saved_env,
                 continuation,
@@ -161,6 +255,25 @@ impl<F: LurkField> Continuation<F> {
         }
     }
 
+    /// Returns the continuation this one continues with, if any
+    pub const fn continuation(&self) -> Option<ContPtr<F>> {
+        match self {
+            Self::Outermost | Self::Error | Self::Dummy | Self::Terminal => None,
+            Self::Call0 { continuation, .. }
+            | Self::Call { continuation, .. }
+            | Self::Call2 { continuation, .. }
+            | Self::Tail { continuation, .. }
+            | Self::Lookup { continuation, .. }
+            | Self::Unop { continuation, .. }
+            | Self::Binop { continuation, .. }
+            | Self::Binop2 { continuation, .. }
+            | Self::If { continuation, .. }
+            | Self::Let { continuation, .. }
+            | Self::LetRec { continuation, .. }
+            | Self::Emit { continuation } => Some(*continuation),
+        }
+    }
+
     pub const fn cont_tag(&self) -> ContTag {
         match self {
             Self::Outermost => ContTag::Outermost,
@@ -175,11 +288,13 @@ impl<F: LurkField> Continuation<F> {
                 unevaled_arg: _,
                 saved_env: _,
                 continuation: _,
+                expanded: _,
             } => ContTag::Call,
             Self::Call2 {
                 function: _,
                 saved_env: _,
                 continuation: _,
+                expanded: _,
             } => ContTag::Call2,
             Self::Tail {
                 saved_env: _,
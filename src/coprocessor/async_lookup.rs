@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use crate::coprocessor::{AsyncCoprocessor, CoCircuit, Coprocessor};
+use crate::field::LurkField;
+use crate::lurk_sym_ptr;
+use crate::num::Num;
+use crate::ptr::Ptr;
+use crate::store::Store;
+use crate::tag::ExprTag;
+
+/// An in-memory key-value map behind an async interface, standing in for a remote service
+#[derive(Clone, Debug, Default)]
+pub struct AsyncMap {
+    entries: Arc<HashMap<u64, u64>>,
+}
+
+impl AsyncMap {
+    pub fn new(entries: HashMap<u64, u64>) -> Self {
+        Self {
+            entries: Arc::new(entries),
+        }
+    }
+
+    pub async fn get(&self, key: u64) -> Option<u64> {
+        std::future::ready(self.entries.get(&key).copied()).await
+    }
+}
+
+/// An example `AsyncCoprocessor`: `(lookup key)` evaluates to the value of `key` in an `AsyncMap`,
+/// or `nil` if it's missing. Values are fetched by `evaluate_async` and cached, and `evaluate` only
+/// reads the cache, so evaluating again for proving gives the same result.
+#[derive(Clone, Debug)]
+pub struct AsyncLookupCoprocessor<F: LurkField> {
+    map: AsyncMap,
+    resolved: Arc<Mutex<HashMap<u64, Option<u64>>>>,
+    _p: PhantomData<F>,
+}
+
+impl<F: LurkField> AsyncLookupCoprocessor<F> {
+    pub fn new(map: AsyncMap) -> Self {
+        Self {
+            map,
+            resolved: Default::default(),
+            _p: Default::default(),
+        }
+    }
+
+    fn key(s: &Store<F>, ptr: &Ptr<F>) -> Option<u64> {
+        if ptr.tag != ExprTag::Num {
+            return None;
+        }
+        match s.fetch_num(ptr)? {
+            Num::U64(key) => Some(*key),
+            Num::Scalar(_) => None,
+        }
+    }
+}
+
+impl<F: LurkField> CoCircuit<F> for AsyncLookupCoprocessor<F> {}
+
+impl<F: LurkField> Coprocessor<F> for AsyncLookupCoprocessor<F> {
+    fn eval_arity(&self) -> usize {
+        1
+    }
+
+    fn simple_evaluate(&self, s: &mut Store<F>, args: &[Ptr<F>]) -> Ptr<F> {
+        let resolved = Self::key(s, &args[0])
+            .and_then(|key| self.resolved.lock().unwrap().get(&key).copied().flatten());
+        match resolved {
+            Some(value) => s.num(value),
+            None => lurk_sym_ptr!(s, nil),
+        }
+    }
+}
+
+impl<F: LurkField> AsyncCoprocessor<F> for AsyncLookupCoprocessor<F> {
+    fn evaluate_async<'a>(
+        &'a self,
+        s: &'a mut Store<F>,
+        args: &'a [Ptr<F>],
+    ) -> Pin<Box<dyn Future<Output = ()> + 'a>> {
+        Box::pin(async move {
+            let Some(key) = Self::key(s, &args[0]) else {
+                return;
+            };
+            if self.resolved.lock().unwrap().contains_key(&key) {
+                return;
+            }
+            let value = self.map.get(key).await;
+            self.resolved.lock().unwrap().insert(key, value);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::{empty_sym_env, lang::Lang, Evaluator};
+    use crate::state::user_sym;
+    use blstrs::Scalar as Fr;
+
+    #[test]
+    fn async_lookup() {
+        let s = &mut Store::<Fr>::default();
+        let map = AsyncMap::new(HashMap::from([(1, 10), (2, 20)]));
+        let coprocessor = AsyncLookupCoprocessor::new(map);
+        let lang = Lang::<Fr, AsyncLookupCoprocessor<Fr>>::new_with_bindings(
+            s,
+            vec![(user_sym("lookup"), coprocessor)],
+        );
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        let expr = s.read("(+ (lookup 1) (lookup 2))").unwrap();
+        let env = empty_sym_env(s);
+        let (output, ..) = runtime
+            .block_on(Evaluator::new(expr, env, s, 100, &lang).eval_async())
+            .unwrap();
+        assert_eq!(s.num(30), output.expr);
+
+        // evaluating synchronously afterwards, as when proving, reads the resolved values
+        let (sync_output, ..) = Evaluator::new(expr, env, s, 100, &lang).eval().unwrap();
+        assert_eq!(output, sync_output);
+
+        let missing = s.read("(lookup 3)").unwrap();
+        let (output, ..) = runtime
+            .block_on(Evaluator::new(missing, env, s, 100, &lang).eval_async())
+            .unwrap();
+        assert_eq!(lurk_sym_ptr!(s, nil), output.expr);
+    }
+}
@@ -1,6 +1,11 @@
 use std::fmt::Debug;
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::pin::Pin;
 
 use bellpepper_core::{ConstraintSystem, SynthesisError};
+use smallvec::{smallvec, SmallVec};
 
 use crate::circuit::gadgets::data::GlobalAllocations;
 use crate::circuit::gadgets::pointer::{AllocatedContPtr, AllocatedPtr};
@@ -9,8 +14,26 @@ use crate::field::LurkField;
 use crate::ptr::{ContPtr, Ptr};
 use crate::store::Store;
 
+#[cfg(feature = "async")]
+pub mod async_lookup;
+pub mod random;
+pub mod schnorr;
+pub mod sha256;
 pub mod trie;
 
+/// The values returned by a single coprocessor call. Most coprocessors return exactly one, which
+/// is stored inline, as is a second.
+pub type Values<F> = SmallVec<[Ptr<F>; 2]>;
+
+/// Packages the values returned by a coprocessor call into the single expression the call
+/// evaluates to: one value is returned as-is, and any other number of values as a proper list.
+pub fn package_values<F: LurkField>(s: &mut Store<F>, values: &[Ptr<F>]) -> Ptr<F> {
+    match values {
+        [value] => *value,
+        _ => s.list(values),
+    }
+}
+
 /// `Coprocessor` is a trait that represents a generalized interface for coprocessors.
 /// Coprocessors augment the Lurk circuit and evaluation with additional built-in functionality.
 /// This trait generalizes over functionality needed in the evaluator, the sibling `CoCircuit` trait,
@@ -25,6 +48,9 @@ pub mod trie;
 /// - An enum such as [`crate::eval::lang::Coproc`], which "closes" the hierarchy of possible coprocessor
 ///   implementations we want to instantiate at a particular point in the code.
 pub trait Coprocessor<F: LurkField>: Clone + Debug + Sync + Send + CoCircuit<F> {
+    /// The number of arguments `evaluate` expects. The evaluator checks each call against it, and
+    /// calls with any other number evaluate to an error. For coprocessors with a circuit, this
+    /// must agree with `CoCircuit::arity`, which `Lang` checks when the coprocessor is bound.
     fn eval_arity(&self) -> usize;
 
     fn evaluate(&self, s: &mut Store<F>, args: Ptr<F>, env: Ptr<F>, cont: ContPtr<F>) -> IO<F> {
@@ -44,7 +70,8 @@ pub trait Coprocessor<F: LurkField>: Clone + Debug + Sync + Send + CoCircuit<F>
             };
         };
 
-        let result = self.simple_evaluate(s, &argv);
+        let values = self.simple_evaluate_multi(s, &argv);
+        let result = package_values(s, &values);
 
         IO {
             expr: result,
@@ -56,12 +83,38 @@ pub trait Coprocessor<F: LurkField>: Clone + Debug + Sync + Send + CoCircuit<F>
     /// As with all evaluation, the value returned from `simple_evaluate` must be fully evaluated.
     fn simple_evaluate(&self, s: &mut Store<F>, args: &[Ptr<F>]) -> Ptr<F>;
 
+    /// Like `simple_evaluate`, but may return several values, as `divmod` returns a quotient and a
+    /// remainder. `evaluate` packages them with `package_values`, so the continuation still
+    /// receives a single expression: a call returning several values evaluates to the list of
+    /// them, which Lurk code destructures with `car` and `cdr`, and a circuit must produce that
+    /// same list. Coprocessors overriding this can implement `simple_evaluate` as
+    /// `package_values(s, &self.simple_evaluate_multi(s, args))`.
+    fn simple_evaluate_multi(&self, s: &mut Store<F>, args: &[Ptr<F>]) -> Values<F> {
+        smallvec![self.simple_evaluate(s, args)]
+    }
+
     /// Returns true if this Coprocessor actually implements a circuit.
     fn has_circuit(&self) -> bool {
         false
     }
 }
 
+/// A `Coprocessor` which needs to `await` external data, like a Merkle proof from a remote service,
+/// before it can evaluate. `Evaluator::eval_async` awaits `evaluate_async` before each call to the
+/// coprocessor, and then evaluates the call with the synchronous `evaluate` as usual.
+///
+/// Proving re-evaluates synchronously, so to keep the witness reproducible, `evaluate` must depend
+/// only on the arguments and on data already resolved by `evaluate_async`, which typically caches
+/// what it fetches.
+#[cfg(feature = "async")]
+pub trait AsyncCoprocessor<F: LurkField>: Coprocessor<F> {
+    fn evaluate_async<'a>(
+        &'a self,
+        s: &'a mut Store<F>,
+        args: &'a [Ptr<F>],
+    ) -> Pin<Box<dyn Future<Output = ()> + 'a>>;
+}
+
 /// `CoCircuit` is a trait that represents a generalized interface for coprocessors.
 /// Coprocessors augment the Lurk circuit and evaluation with additional built-in functionality.
 /// This trait generalizes over functionality needed in the circuit, the sibling `Coprocessor` trait,
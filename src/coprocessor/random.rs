@@ -0,0 +1,219 @@
+//! A coprocessor deriving pseudo-random numbers from a seed. It keeps no state of its own: each
+//! call hashes its seed, so programs thread the result through as the next seed. Coprocessor
+//! arguments aren't evaluated, so the next call is built and then evaluated, as in
+//! `(let ((a (random 1))) (eval (cons 'random (cons a nil))))`. Since the output depends only on
+//! the argument, evaluation and proving always agree, which is the property any coprocessor with
+//! a circuit needs.
+
+use std::marker::PhantomData;
+
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use serde::{Deserialize, Serialize};
+
+use crate::circuit::gadgets::data::{hash_poseidon, GlobalAllocations};
+use crate::circuit::gadgets::pointer::{AllocatedContPtr, AllocatedPtr};
+use crate::coprocessor::{CoCircuit, Coprocessor};
+use crate::eval::IO;
+use crate::field::LurkField;
+use crate::num::Num;
+use crate::ptr::{ContPtr, Ptr};
+use crate::store::Store;
+
+/// Maps a seed of any type to a `Num`, the Poseidon hash of the seed's tag and digest. The seed
+/// is usually a `Num` returned by an earlier call.
+#[derive(Clone, Debug, Serialize, Default, Deserialize)]
+pub struct RandomCoprocessor<F: LurkField> {
+    _p: PhantomData<F>,
+}
+
+impl<F: LurkField> RandomCoprocessor<F> {
+    pub fn new() -> Self {
+        Self {
+            _p: Default::default(),
+        }
+    }
+}
+
+impl<F: LurkField> Coprocessor<F> for RandomCoprocessor<F> {
+    fn eval_arity(&self) -> usize {
+        1
+    }
+
+    /// Errors on a seed that can't be hashed, such as a pointer into another store
+    fn evaluate(&self, s: &mut Store<F>, args: Ptr<F>, env: Ptr<F>, cont: ContPtr<F>) -> IO<F> {
+        match s.fetch_list(&args).as_deref() {
+            Some([seed]) if s.hash_expr(seed).is_some() => IO {
+                expr: self.simple_evaluate(s, &[*seed]),
+                env,
+                cont,
+            },
+            Some([seed]) => IO {
+                expr: *seed,
+                env,
+                cont: s.intern_cont_error(),
+            },
+            _ => IO {
+                expr: args,
+                env,
+                cont: s.intern_cont_error(),
+            },
+        }
+    }
+
+    /// Panics unless the seed can be hashed, which `evaluate` checks.
+    fn simple_evaluate(&self, s: &mut Store<F>, args: &[Ptr<F>]) -> Ptr<F> {
+        let seed = s.hash_expr(&args[0]).expect("the seed can be hashed");
+        let random = s
+            .poseidon_cache
+            .hash3(&[seed.tag_field(), *seed.value(), F::ZERO]);
+        s.intern_num(Num::Scalar(random))
+    }
+
+    fn has_circuit(&self) -> bool {
+        true
+    }
+}
+
+impl<F: LurkField> CoCircuit<F> for RandomCoprocessor<F> {
+    fn arity(&self) -> usize {
+        1
+    }
+
+    fn synthesize<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        g: &GlobalAllocations<F>,
+        store: &Store<F>,
+        input_exprs: &[AllocatedPtr<F>],
+        input_env: &AllocatedPtr<F>,
+        input_cont: &AllocatedContPtr<F>,
+    ) -> Result<(AllocatedPtr<F>, AllocatedPtr<F>, AllocatedContPtr<F>), SynthesisError> {
+        let seed = &input_exprs[0];
+        let preimage = vec![
+            seed.tag().clone(),
+            seed.hash().clone(),
+            g.default_num.clone(),
+        ];
+        let random = hash_poseidon(
+            cs.namespace(|| "random"),
+            preimage,
+            store.poseidon_constants().c3(),
+        )?;
+
+        Ok((
+            AllocatedPtr::from_parts(g.num_tag.clone(), random),
+            input_env.clone(),
+            input_cont.clone(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use lurk_macros::Coproc;
+    use pasta_curves::pallas::Scalar as Fr;
+
+    use super::*;
+    use crate as lurk;
+    use crate::eval::{empty_sym_env, eval_str, lang::Lang, Evaluator};
+    use crate::proof::{dummy::DummyProver, Prover};
+    use crate::state::user_sym;
+    use crate::tag::ExprTag;
+
+    #[derive(Clone, Debug, Coproc)]
+    enum RandomCoproc<F: LurkField> {
+        Random(RandomCoprocessor<F>),
+    }
+
+    fn random_lang(s: &mut Store<Fr>) -> Lang<Fr, RandomCoproc<Fr>> {
+        let mut lang = Lang::new();
+        lang.add_coprocessor(
+            user_sym("random"),
+            RandomCoproc::Random(RandomCoprocessor::new()),
+            s,
+        );
+        lang
+    }
+
+    fn eval(s: &mut Store<Fr>, lang: &Lang<Fr, RandomCoproc<Fr>>, src: &str) -> Ptr<Fr> {
+        let expr = s.read(src).unwrap();
+        let env = empty_sym_env(s);
+        let (io, ..) = Evaluator::new(expr, env, s, 100, lang)
+            .eval_checked()
+            .unwrap();
+        io.expr
+    }
+
+    #[test]
+    fn same_seed_same_output() {
+        let s = &mut Store::<Fr>::default();
+        let lang = random_lang(s);
+        let first = eval(s, &lang, "(random 42)");
+        assert_eq!(ExprTag::Num, first.tag);
+        assert_eq!(first, eval(s, &lang, "(random 42)"));
+        assert_eq!(
+            first,
+            eval(s, &lang, "(eval (cons 'random (cons (+ 40 2) nil)))")
+        );
+        // the argument isn't evaluated, so this seed is the list `(+ 40 2)`
+        assert_ne!(first, eval(s, &lang, "(random (+ 40 2))"));
+
+        // a fresh store derives the same value
+        let other = &mut Store::<Fr>::default();
+        let other_lang = random_lang(other);
+        let again = eval(other, &other_lang, "(random 42)");
+        assert_eq!(s.hash_expr(&first), other.hash_expr(&again));
+    }
+
+    #[test]
+    fn different_seeds_differ() {
+        let s = &mut Store::<Fr>::default();
+        let lang = random_lang(s);
+        let outputs = [
+            "(random 0)",
+            "(random 1)",
+            "(random 1u64)",
+            "(random \"1\")",
+            "(random (random 1))",
+            "(random (random (random 1)))",
+        ]
+        .map(|src| eval(s, &lang, src));
+        for (i, a) in outputs.iter().enumerate() {
+            for b in &outputs[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+        // without the coprocessor, `random` is unbound
+        assert!(eval_str::<Fr, RandomCoproc<Fr>>(s, "(random 1)").is_err());
+    }
+
+    #[test]
+    fn unhashable_seed_is_an_error() {
+        let s = &mut Store::<Fr>::default();
+        let env = empty_sym_env(s);
+        let cont = s.intern_cont_outermost();
+        // an index past the end of the store's conses
+        let unknown = Ptr::index(ExprTag::Cons, 1 << 20);
+        let args = s.list(&[unknown]);
+        let io = RandomCoprocessor::new().evaluate(s, args, env, cont);
+        assert_eq!(s.get_cont_error(), io.cont);
+        assert_eq!(unknown, io.expr);
+    }
+
+    #[test]
+    fn random_proves_and_verifies() {
+        let s = &mut Store::<Fr>::default();
+        let lang = random_lang(s);
+        let src = "(let ((a (random 7))) (eval (cons 'random (cons a nil))))";
+        let expected = eval(s, &lang, src);
+        assert_ne!(expected, eval(s, &lang, "(random 7)"));
+
+        let prover = DummyProver::new(2, lang);
+        let expr = s.read(src).unwrap();
+        let env = empty_sym_env(s);
+        let (proof, z0, zi, num_steps) = prover.evaluate_and_prove(expr, env, s, 1000).unwrap();
+        assert!(proof.verify(num_steps, &z0, &zi));
+        let expected = s.hash_expr(&expected).unwrap();
+        assert_eq!(&[expected.tag_field(), *expected.value()], &zi[..2]);
+    }
+}
@@ -0,0 +1,476 @@
+//! A coprocessor verifying Schnorr signatures over a curve `y² = x³ + 5` whose coordinates are
+//! elements of the circuit's field, so that the curve arithmetic is native to the circuit. Over
+//! the Pallas scalar field this is Vesta, whose scalars are Pallas base field elements.
+//!
+//! A signature of the message `m` by the key `P = xG` is a point `R = kG` and a scalar
+//! `s = k + ex`, where the challenge `e` is the Poseidon hash of `R`, `P` and `m`. It verifies if
+//! `sG = R + eP`, with `s` and `e` multiplied in as the integers they represent.
+
+use bellpepper_core::boolean::Boolean;
+use bellpepper_core::num::AllocatedNum;
+use bellpepper_core::{ConstraintSystem, SynthesisError};
+use serde::{Deserialize, Serialize};
+
+use crate::circuit::gadgets::constraints::{
+    add, alloc_equal, alloc_is_zero, div, mul, or, pick, sub,
+};
+use crate::circuit::gadgets::data::{allocate_constant, hash_poseidon, GlobalAllocations};
+use crate::circuit::gadgets::pointer::{AllocatedContPtr, AllocatedPtr};
+use crate::coprocessor::{CoCircuit, Coprocessor};
+use crate::field::{FWrap, LurkField};
+use crate::ptr::Ptr;
+use crate::store::Store;
+use crate::tag::ExprTag;
+
+/// A point of the curve, or of its affine plane when given coordinates that aren't on the curve
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Point<F: LurkField> {
+    Identity,
+    Affine(F, F),
+}
+
+impl<F: LurkField> Point<F> {
+    fn is_on_curve(x: F, y: F) -> bool {
+        y.square() == x.square() * x + F::from_u64(5)
+    }
+
+    /// The sum by the affine formulas. The circuit follows the same cases, and substitutes one
+    /// for a zero denominator, so this does too.
+    fn add(self, other: Self) -> Self {
+        let (Point::Affine(x1, y1), Point::Affine(x2, y2)) = (self, other) else {
+            return if self == Point::Identity { other } else { self };
+        };
+        let non_zero = |f: F| if f.is_zero().into() { F::ONE } else { f };
+        let lambda = if x1 != x2 {
+            (y2 - y1) * non_zero(x2 - x1).invert().unwrap()
+        } else if y1 == y2 {
+            x1.square() * F::from_u64(3) * non_zero(y1.double()).invert().unwrap()
+        } else {
+            return Point::Identity;
+        };
+        let x3 = lambda.square() - x1 - x2;
+        Point::Affine(x3, lambda * (x1 - x3) - y1)
+    }
+
+    /// Multiplies by the integer `scalar` represents, by doubling and adding from its most
+    /// significant bit
+    fn mul(self, scalar: F) -> Self {
+        let mut acc = Point::Identity;
+        for bit in scalar.to_le_bits().iter().rev() {
+            acc = acc.add(acc);
+            if *bit {
+                acc = acc.add(self);
+            }
+        }
+        acc
+    }
+}
+
+/// Takes six `Num`s, the coordinates of the public key, the message, the coordinates of `R` and
+/// `s`, and returns `t` if they are a valid signature and `nil` otherwise.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SchnorrCoprocessor<F: LurkField> {
+    generator: (FWrap<F>, FWrap<F>),
+}
+
+impl<F: LurkField> SchnorrCoprocessor<F> {
+    /// Verifies signatures for keys which are multiples of the point `generator`
+    pub fn new(generator: (F, F)) -> Self {
+        Self {
+            generator: (FWrap(generator.0), FWrap(generator.1)),
+        }
+    }
+
+    fn verify(&self, s: &Store<F>, key: (F, F), message: F, r: (F, F), sig: F) -> bool {
+        if !Point::is_on_curve(key.0, key.1) || !Point::is_on_curve(r.0, r.1) {
+            return false;
+        }
+        let e = s
+            .poseidon_cache
+            .hash6(&[r.0, r.1, key.0, key.1, message, F::ZERO]);
+        let generator = Point::Affine(self.generator.0 .0, self.generator.1 .0);
+        let key = Point::Affine(key.0, key.1);
+        generator.mul(sig) == Point::Affine(r.0, r.1).add(key.mul(e))
+    }
+}
+
+impl<F: LurkField> Coprocessor<F> for SchnorrCoprocessor<F> {
+    fn eval_arity(&self) -> usize {
+        6
+    }
+
+    fn simple_evaluate(&self, s: &mut Store<F>, args: &[Ptr<F>]) -> Ptr<F> {
+        let nums = args
+            .iter()
+            .map(|arg| match arg.tag {
+                ExprTag::Num => s.fetch_num(arg).map(|n| n.into_scalar()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        let verified = match nums.as_deref() {
+            Some(&[key_x, key_y, message, r_x, r_y, sig]) => {
+                self.verify(s, (key_x, key_y), message, (r_x, r_y), sig)
+            }
+            _ => false,
+        };
+        s.as_lurk_boolean(verified)
+    }
+
+    fn has_circuit(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+struct AllocatedPoint<F: LurkField> {
+    x: AllocatedNum<F>,
+    y: AllocatedNum<F>,
+    is_identity: Boolean,
+}
+
+impl<F: LurkField> AllocatedPoint<F> {
+    fn identity(g: &GlobalAllocations<F>) -> Self {
+        Self {
+            x: g.default_num.clone(),
+            y: g.default_num.clone(),
+            is_identity: Boolean::Constant(true),
+        }
+    }
+
+    fn affine(x: &AllocatedNum<F>, y: &AllocatedNum<F>) -> Self {
+        Self {
+            x: x.clone(),
+            y: y.clone(),
+            is_identity: Boolean::Constant(false),
+        }
+    }
+
+    fn is_on_curve<CS: ConstraintSystem<F>>(&self, mut cs: CS) -> Result<Boolean, SynthesisError> {
+        let y2 = mul(cs.namespace(|| "y^2"), &self.y, &self.y)?;
+        let x2 = mul(cs.namespace(|| "x^2"), &self.x, &self.x)?;
+        let x3 = mul(cs.namespace(|| "x^3"), &x2, &self.x)?;
+        let b = allocate_constant(&mut cs.namespace(|| "b"), F::from_u64(5))?;
+        let rhs = add(cs.namespace(|| "x^3 + b"), &x3, &b)?;
+        alloc_equal(cs.namespace(|| "y^2 = x^3 + b"), &y2, &rhs)
+    }
+
+    /// `a` if `condition`, and `b` otherwise
+    fn pick<CS: ConstraintSystem<F>>(
+        mut cs: CS,
+        condition: &Boolean,
+        a: &Self,
+        b: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let x = pick(cs.namespace(|| "x"), condition, &a.x, &b.x)?;
+        let y = pick(cs.namespace(|| "y"), condition, &a.y, &b.y)?;
+        let a_identity = Boolean::and(
+            cs.namespace(|| "condition and a is identity"),
+            condition,
+            &a.is_identity,
+        )?;
+        let b_identity = Boolean::and(
+            cs.namespace(|| "not condition and b is identity"),
+            &condition.not(),
+            &b.is_identity,
+        )?;
+        let is_identity = or(cs.namespace(|| "is identity"), &a_identity, &b_identity)?;
+        Ok(Self { x, y, is_identity })
+    }
+
+    /// The sum, following the cases of `Point::add`
+    fn add<CS: ConstraintSystem<F>>(
+        &self,
+        mut cs: CS,
+        g: &GlobalAllocations<F>,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let dx = sub(cs.namespace(|| "dx"), &other.x, &self.x)?;
+        let dy = sub(cs.namespace(|| "dy"), &other.y, &self.y)?;
+        let dx_is_zero = alloc_is_zero(cs.namespace(|| "dx is zero"), &dx)?;
+        let dy_is_zero = alloc_is_zero(cs.namespace(|| "dy is zero"), &dy)?;
+        let same = Boolean::and(cs.namespace(|| "same"), &dx_is_zero, &dy_is_zero)?;
+        let opposite = Boolean::and(cs.namespace(|| "opposite"), &dx_is_zero, &dy_is_zero.not())?;
+
+        let add_denominator = pick(
+            cs.namespace(|| "add denominator"),
+            &dx_is_zero,
+            &g.true_num,
+            &dx,
+        )?;
+        let add_lambda = div(cs.namespace(|| "add lambda"), &dy, &add_denominator)?;
+
+        let x2 = mul(cs.namespace(|| "x^2"), &self.x, &self.x)?;
+        let two_x2 = add(cs.namespace(|| "2x^2"), &x2, &x2)?;
+        let three_x2 = add(cs.namespace(|| "3x^2"), &two_x2, &x2)?;
+        let two_y = add(cs.namespace(|| "2y"), &self.y, &self.y)?;
+        let two_y_is_zero = alloc_is_zero(cs.namespace(|| "2y is zero"), &two_y)?;
+        let double_denominator = pick(
+            cs.namespace(|| "double denominator"),
+            &two_y_is_zero,
+            &g.true_num,
+            &two_y,
+        )?;
+        let double_lambda = div(
+            cs.namespace(|| "double lambda"),
+            &three_x2,
+            &double_denominator,
+        )?;
+
+        let lambda = pick(
+            cs.namespace(|| "lambda"),
+            &same,
+            &double_lambda,
+            &add_lambda,
+        )?;
+        let lambda2 = mul(cs.namespace(|| "lambda^2"), &lambda, &lambda)?;
+        let x3 = sub(cs.namespace(|| "lambda^2 - x1"), &lambda2, &self.x)?;
+        let x3 = sub(cs.namespace(|| "x3"), &x3, &other.x)?;
+        let run = sub(cs.namespace(|| "x1 - x3"), &self.x, &x3)?;
+        let rise = mul(cs.namespace(|| "lambda (x1 - x3)"), &lambda, &run)?;
+        let y3 = sub(cs.namespace(|| "y3"), &rise, &self.y)?;
+
+        let sum = Self {
+            x: x3,
+            y: y3,
+            is_identity: opposite,
+        };
+        let sum = Self::pick(
+            cs.namespace(|| "other is identity"),
+            &other.is_identity,
+            self,
+            &sum,
+        )?;
+        Self::pick(
+            cs.namespace(|| "self is identity"),
+            &self.is_identity,
+            other,
+            &sum,
+        )
+    }
+
+    /// Multiplies by the integer `bits` represent, least significant first, following `Point::mul`
+    fn mul<CS: ConstraintSystem<F>>(
+        &self,
+        mut cs: CS,
+        g: &GlobalAllocations<F>,
+        bits: &[Boolean],
+    ) -> Result<Self, SynthesisError> {
+        let mut acc = Self::identity(g);
+        for (i, bit) in bits.iter().enumerate().rev() {
+            acc = acc.add(cs.namespace(|| format!("double {i}")), g, &acc)?;
+            let sum = acc.add(cs.namespace(|| format!("add {i}")), g, self)?;
+            acc = Self::pick(cs.namespace(|| format!("pick {i}")), bit, &sum, &acc)?;
+        }
+        Ok(acc)
+    }
+
+    fn alloc_equal<CS: ConstraintSystem<F>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Boolean, SynthesisError> {
+        let x_equal = alloc_equal(cs.namespace(|| "x equal"), &self.x, &other.x)?;
+        let y_equal = alloc_equal(cs.namespace(|| "y equal"), &self.y, &other.y)?;
+        let coordinates_equal =
+            Boolean::and(cs.namespace(|| "coordinates equal"), &x_equal, &y_equal)?;
+        let neither_identity = Boolean::and(
+            cs.namespace(|| "neither is identity"),
+            &self.is_identity.not(),
+            &other.is_identity.not(),
+        )?;
+        let affine_equal = Boolean::and(
+            cs.namespace(|| "affine points equal"),
+            &neither_identity,
+            &coordinates_equal,
+        )?;
+        let both_identity = Boolean::and(
+            cs.namespace(|| "both are identity"),
+            &self.is_identity,
+            &other.is_identity,
+        )?;
+        or(cs.namespace(|| "equal"), &both_identity, &affine_equal)
+    }
+}
+
+impl<F: LurkField> CoCircuit<F> for SchnorrCoprocessor<F> {
+    fn arity(&self) -> usize {
+        6
+    }
+
+    fn synthesize<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        g: &GlobalAllocations<F>,
+        store: &Store<F>,
+        input_exprs: &[AllocatedPtr<F>],
+        input_env: &AllocatedPtr<F>,
+        input_cont: &AllocatedContPtr<F>,
+    ) -> Result<(AllocatedPtr<F>, AllocatedPtr<F>, AllocatedContPtr<F>), SynthesisError> {
+        let mut all_nums = Boolean::Constant(true);
+        for (i, input) in input_exprs.iter().enumerate() {
+            let is_num = alloc_equal(
+                cs.namespace(|| format!("input {i} is a num")),
+                input.tag(),
+                &g.num_tag,
+            )?;
+            all_nums = Boolean::and(
+                cs.namespace(|| format!("inputs up to {i} are nums")),
+                &all_nums,
+                &is_num,
+            )?;
+        }
+        let [key_x, key_y, message, r_x, r_y, sig] =
+            [0, 1, 2, 3, 4, 5].map(|i| input_exprs[i].hash());
+
+        let key = AllocatedPoint::affine(key_x, key_y);
+        let r = AllocatedPoint::affine(r_x, r_y);
+        let key_on_curve = key.is_on_curve(cs.namespace(|| "key is on curve"))?;
+        let r_on_curve = r.is_on_curve(cs.namespace(|| "r is on curve"))?;
+
+        let e = hash_poseidon(
+            cs.namespace(|| "challenge"),
+            vec![
+                r_x.clone(),
+                r_y.clone(),
+                key_x.clone(),
+                key_y.clone(),
+                message.clone(),
+                g.default_num.clone(),
+            ],
+            store.poseidon_constants().c6(),
+        )?;
+        let e_bits = e.to_bits_le_strict(&mut cs.namespace(|| "challenge bits"))?;
+        let sig_bits = sig.to_bits_le_strict(&mut cs.namespace(|| "signature bits"))?;
+
+        let generator = AllocatedPoint::affine(
+            &allocate_constant(&mut cs.namespace(|| "generator x"), self.generator.0 .0)?,
+            &allocate_constant(&mut cs.namespace(|| "generator y"), self.generator.1 .0)?,
+        );
+        let lhs = generator.mul(cs.namespace(|| "sG"), g, &sig_bits)?;
+        let e_key = key.mul(cs.namespace(|| "eP"), g, &e_bits)?;
+        let rhs = r.add(cs.namespace(|| "R + eP"), g, &e_key)?;
+        let equation_holds = lhs.alloc_equal(cs.namespace(|| "sG = R + eP"), &rhs)?;
+
+        let points_on_curve = Boolean::and(
+            cs.namespace(|| "points are on curve"),
+            &key_on_curve,
+            &r_on_curve,
+        )?;
+        let well_formed = Boolean::and(
+            cs.namespace(|| "inputs are well formed"),
+            &all_nums,
+            &points_on_curve,
+        )?;
+        let verified = Boolean::and(cs.namespace(|| "verified"), &well_formed, &equation_holds)?;
+        let result = AllocatedPtr::as_lurk_boolean(cs.namespace(|| "result"), store, &verified)?;
+
+        Ok((result, input_env.clone(), input_cont.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ff::{Field, FromUniformBytes, PrimeField};
+    use lurk_macros::Coproc;
+    use pasta_curves::arithmetic::CurveAffine;
+    use pasta_curves::group::{Curve, Group};
+    use pasta_curves::{pallas::Scalar as Fr, vesta};
+
+    use super::*;
+    use crate as lurk;
+    use crate::eval::{empty_sym_env, lang::Lang, Evaluator};
+    use crate::lurk_sym_ptr;
+    use crate::num::Num;
+    use crate::proof::{dummy::DummyProver, Prover};
+    use crate::state::user_sym;
+
+    #[derive(Clone, Debug, Coproc)]
+    enum SchnorrCoproc<F: LurkField> {
+        Schnorr(SchnorrCoprocessor<F>),
+    }
+
+    fn coordinates(point: vesta::Point) -> (Fr, Fr) {
+        let coordinates = point.to_affine().coordinates().unwrap();
+        (*coordinates.x(), *coordinates.y())
+    }
+
+    /// `(key, message, r, s)` for a signature by the secret `x`
+    fn sign(
+        s: &Store<Fr>,
+        x: vesta::Scalar,
+        k: vesta::Scalar,
+        message: Fr,
+    ) -> ((Fr, Fr), Fr, (Fr, Fr), Fr) {
+        let key = coordinates(vesta::Point::generator() * x);
+        let r = coordinates(vesta::Point::generator() * k);
+        let e = s
+            .poseidon_cache
+            .hash6(&[r.0, r.1, key.0, key.1, message, Fr::ZERO]);
+        let mut wide = [0u8; 64];
+        wide[..32].copy_from_slice(e.to_repr().as_ref());
+        let sig = k + vesta::Scalar::from_uniform_bytes(&wide) * x;
+        // the Vesta scalar field is the smaller of the two
+        let sig = Fr::from_repr(sig.to_repr()).unwrap();
+        (key, message, r, sig)
+    }
+
+    fn verify_expr(s: &mut Store<Fr>, args: [Fr; 6]) -> Ptr<Fr> {
+        let mut expr = vec![s.user_sym("schnorr-verify")];
+        expr.extend(args.map(|arg| s.num(Num::Scalar(arg))));
+        s.list(&expr)
+    }
+
+    #[test]
+    fn schnorr_verifies() {
+        let s = &mut Store::<Fr>::default();
+        let generator = coordinates(vesta::Point::generator());
+        let mut lang = Lang::<Fr, SchnorrCoproc<Fr>>::new();
+        lang.add_coprocessor(
+            user_sym("schnorr-verify"),
+            SchnorrCoproc::Schnorr(SchnorrCoprocessor::new(generator)),
+            s,
+        );
+        let prover = DummyProver::new(1, lang.clone());
+        let env = empty_sym_env(s);
+        let t = lurk_sym_ptr!(s, t);
+        let nil = lurk_sym_ptr!(s, nil);
+
+        let x = vesta::Scalar::from(0x5ec2e7);
+        let (key, message, r, sig) = sign(s, x, vesta::Scalar::from(0x1234_5678), Fr::from(42));
+        let (other_key, ..) = sign(s, x + vesta::Scalar::ONE, vesta::Scalar::ONE, message);
+        for (args, expected) in [
+            ([key.0, key.1, message, r.0, r.1, sig], t),
+            ([key.0, key.1, message, r.0, r.1, sig + Fr::ONE], nil),
+            ([key.0, key.1, message + Fr::ONE, r.0, r.1, sig], nil),
+            ([other_key.0, other_key.1, message, r.0, r.1, sig], nil),
+            ([key.0, key.1, message, r.1, r.0, sig], nil),
+            ([key.0, -key.1, message, r.0, r.1, sig], nil),
+        ] {
+            let expr = verify_expr(s, args);
+            let (io, ..) = Evaluator::new(expr, env, s, 10, &lang).eval().unwrap();
+            assert_eq!(expected, io.expr);
+
+            let (proof, z0, zi, num_steps) = prover.evaluate_and_prove(expr, env, s, 10).unwrap();
+            assert!(proof.verify(num_steps, &z0, &zi));
+        }
+
+        // arguments which aren't all numbers don't verify
+        let expr = s.read("(schnorr-verify 1 2 3 4 5 \"6\")").unwrap();
+        let (io, ..) = Evaluator::new(expr, env, s, 10, &lang).eval().unwrap();
+        assert_eq!(nil, io.expr);
+    }
+
+    #[test]
+    fn point_arithmetic_matches_pasta() {
+        let g = vesta::Point::generator();
+        let (gx, gy) = coordinates(g);
+        let generator = Point::Affine(gx, gy);
+        for n in [1u64, 2, 3, 7, 0xffff_ffff_ffff] {
+            let (x, y) = coordinates(g * vesta::Scalar::from(n));
+            assert_eq!(Point::Affine(x, y), generator.mul(Fr::from(n)));
+        }
+        assert_eq!(Point::Identity, generator.mul(Fr::ZERO));
+        let (x, y) = coordinates(-g);
+        assert_eq!(Point::Identity, generator.add(Point::Affine(x, y)));
+    }
+}
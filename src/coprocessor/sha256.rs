@@ -0,0 +1,409 @@
+//! A coprocessor computing the SHA-256 digest of a Lurk string. Since a circuit has a fixed
+//! shape, each `Sha256Coprocessor` hashes strings of one length, given when it's made, and binds
+//! as many names as there are lengths a program needs. Each character is one byte, so the
+//! string's characters must all be below 256. Any other argument, including a string of
+//! another length, evaluates to an error, and the circuit proves that error by taking the
+//! argument apart as far as it needs to.
+
+use std::marker::PhantomData;
+
+use bellpepper::gadgets::sha256::sha256;
+use bellpepper_core::boolean::{AllocatedBit, Boolean};
+use bellpepper_core::num::AllocatedNum;
+use bellpepper_core::{ConstraintSystem, LinearCombination, SynthesisError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::circuit::gadgets::constraints::{and, or_v_unchecked_for_optimization};
+use crate::circuit::gadgets::data::GlobalAllocations;
+use crate::circuit::gadgets::pointer::{AllocatedContPtr, AllocatedPtr};
+use crate::coprocessor::{CoCircuit, Coprocessor};
+use crate::eval::IO;
+use crate::expr::Expression;
+use crate::field::LurkField;
+use crate::ptr::{ContPtr, Ptr};
+use crate::store::Store;
+use crate::tag::{ExprTag, Tag};
+
+/// Maps a string of `n` bytes to the list of the 32 bytes of its SHA-256 digest, as `u64`s. Any
+/// other argument evaluates to an error.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Sha256Coprocessor<F: LurkField> {
+    n: usize,
+    _p: PhantomData<F>,
+}
+
+impl<F: LurkField> Sha256Coprocessor<F> {
+    /// Hashes strings of exactly `n` bytes
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            _p: Default::default(),
+        }
+    }
+
+    /// The bytes of `ptr`, if it's a string of `n` characters each below 256
+    fn bytes(&self, s: &Store<F>, ptr: &Ptr<F>) -> Option<Vec<u8>> {
+        if ptr.tag != ExprTag::Str {
+            return None;
+        }
+        let bytes = s
+            .fetch_string(ptr)?
+            .chars()
+            .map(|c| u8::try_from(c).ok())
+            .collect::<Option<Vec<_>>>()?;
+        (bytes.len() == self.n).then_some(bytes)
+    }
+}
+
+impl<F: LurkField> Coprocessor<F> for Sha256Coprocessor<F> {
+    fn eval_arity(&self) -> usize {
+        1
+    }
+
+    fn evaluate(&self, s: &mut Store<F>, args: Ptr<F>, env: Ptr<F>, cont: ContPtr<F>) -> IO<F> {
+        match s.fetch_list(&args).as_deref() {
+            Some([input]) if self.bytes(s, input).is_some() => IO {
+                expr: self.simple_evaluate(s, &[*input]),
+                env,
+                cont,
+            },
+            Some([input]) => IO {
+                expr: *input,
+                env,
+                cont: s.intern_cont_error(),
+            },
+            _ => IO {
+                expr: args,
+                env,
+                cont: s.intern_cont_error(),
+            },
+        }
+    }
+
+    /// Panics unless `args` is a single string of `n` bytes, which `evaluate` checks.
+    fn simple_evaluate(&self, s: &mut Store<F>, args: &[Ptr<F>]) -> Ptr<F> {
+        let bytes = self
+            .bytes(s, &args[0])
+            .expect("the argument is a string of the expected length");
+        let digest = Sha256::digest(bytes)
+            .iter()
+            .map(|byte| s.uint64(u64::from(*byte)))
+            .collect::<Vec<_>>();
+        s.list(&digest)
+    }
+
+    fn has_circuit(&self) -> bool {
+        true
+    }
+}
+
+/// Allocates a number equal to `bits`, most significant first, read as an integer
+fn pack_be<F: LurkField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    bits: &[Boolean],
+) -> Result<AllocatedNum<F>, SynthesisError> {
+    let num = AllocatedNum::alloc(cs.namespace(|| "num"), || {
+        bits.iter()
+            .try_fold(0u64, |acc, bit| {
+                let bit = bit.get_value().ok_or(SynthesisError::AssignmentMissing)?;
+                Ok((acc << 1) | u64::from(bit))
+            })
+            .map(F::from_u64)
+    })?;
+    let lc = bits
+        .iter()
+        .rev()
+        .enumerate()
+        .fold(LinearCombination::zero(), |lc, (i, bit)| {
+            lc + &bit.lc(CS::one(), F::from_u64(1 << i))
+        });
+    cs.enforce(
+        || "num is its bits",
+        |_| lc,
+        |lc| lc + CS::one(),
+        |lc| lc + num.get_variable(),
+    );
+    Ok(num)
+}
+
+impl<F: LurkField> CoCircuit<F> for Sha256Coprocessor<F> {
+    fn arity(&self) -> usize {
+        1
+    }
+
+    fn synthesize<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        g: &GlobalAllocations<F>,
+        store: &Store<F>,
+        input_exprs: &[AllocatedPtr<F>],
+        input_env: &AllocatedPtr<F>,
+        input_cont: &AllocatedContPtr<F>,
+    ) -> Result<(AllocatedPtr<F>, AllocatedPtr<F>, AllocatedContPtr<F>), SynthesisError> {
+        let input = &input_exprs[0];
+        let steps = self.steps(store, input);
+        self.synthesize_steps(cs, g, store, input, input_env, input_cont, steps)
+    }
+}
+
+impl<F: LurkField> Sha256Coprocessor<F> {
+    /// The witness for taking the first `n` characters off `input`, one at a time: each
+    /// character, and the hash of the string left after it. Steps past the end of the string, or
+    /// of an `input` that isn't a string, are zeros, which the circuit doesn't constrain. An
+    /// opaque string can't be taken apart, so a call on one evaluates to an error but can't be
+    /// proven.
+    fn steps(&self, store: &Store<F>, input: &AllocatedPtr<F>) -> Vec<Option<(u32, F)>> {
+        let (Some(tag), Some(hash)) = (input.tag().get_value(), input.hash().get_value()) else {
+            return vec![None; self.n];
+        };
+        let mut string = ExprTag::from_field(&tag)
+            .filter(|tag| *tag == ExprTag::Str)
+            .and_then(|tag| store.get_maybe_opaque(tag, hash));
+        (0..self.n)
+            .map(|_| {
+                let step = string.and_then(|ptr| match store.fetch(&ptr)? {
+                    Expression::Str(car, cdr) => {
+                        let rest = store.hash_expr(&cdr)?;
+                        Some((u32::from(store.fetch_char(&car)?), *rest.value(), cdr))
+                    }
+                    _ => None,
+                });
+                string = step.map(|(.., cdr)| cdr);
+                Some(step.map_or((0, F::ZERO), |(char, rest, _)| (char, rest)))
+            })
+            .collect()
+    }
+
+    /// Takes `n` characters off `input` as witnessed by `steps`, each step constrained to take
+    /// apart the string left by the previous one, so the characters are those of `input` and it's
+    /// hashed exactly when it's a string of `n` bytes.
+    #[allow(clippy::too_many_arguments)]
+    fn synthesize_steps<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        g: &GlobalAllocations<F>,
+        store: &Store<F>,
+        input: &AllocatedPtr<F>,
+        input_env: &AllocatedPtr<F>,
+        input_cont: &AllocatedContPtr<F>,
+        steps: Vec<Option<(u32, F)>>,
+    ) -> Result<(AllocatedPtr<F>, AllocatedPtr<F>, AllocatedContPtr<F>), SynthesisError> {
+        // whether the string taken apart so far is a string of bytes
+        let mut is_bytes = input.is_str(&mut cs.namespace(|| "input is a string"))?;
+        let mut string = input.clone();
+        let mut bits = Vec::with_capacity(8 * self.n);
+        for (i, step) in steps.into_iter().enumerate() {
+            let cs = &mut cs.namespace(|| format!("char {i}"));
+            let is_empty =
+                string.alloc_equal(&mut cs.namespace(|| "string is empty"), &g.empty_str_ptr)?;
+            let takes_char = and(
+                &mut cs.namespace(|| "takes a char"),
+                &is_bytes,
+                &is_empty.not(),
+            )?;
+
+            let char_bits = (0..32)
+                .map(|j| {
+                    let bit = step.map(|(char, _)| (char >> (31 - j)) & 1 == 1);
+                    AllocatedBit::alloc(cs.namespace(|| format!("bit {j}")), bit).map(Boolean::from)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let char = pack_be(cs.namespace(|| "char"), &char_bits)?;
+            let char = AllocatedPtr::from_parts(g.char_tag.clone(), char);
+            let rest = AllocatedNum::alloc(cs.namespace(|| "rest"), || {
+                step.map(|(_, rest)| rest)
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })?;
+            let rest = AllocatedPtr::from_parts(g.str_tag.clone(), rest);
+            let strcons = AllocatedPtr::construct_strcons(
+                cs.namespace(|| "strcons"),
+                g,
+                store,
+                &char,
+                &rest,
+            )?;
+            strcons.implies_ptr_equal(
+                &mut cs.namespace(|| "string is the char and the rest"),
+                &takes_char,
+                &string,
+            )?;
+
+            // a byte has none of its 24 high bits set
+            let is_wide = or_v_unchecked_for_optimization(
+                cs.namespace(|| "char is wide"),
+                &char_bits[..24].iter().collect::<Vec<_>>(),
+            )?;
+            is_bytes = and(
+                &mut cs.namespace(|| "is bytes"),
+                &takes_char,
+                &is_wide.not(),
+            )?;
+            bits.extend_from_slice(&char_bits[24..]);
+            string = rest;
+        }
+        let is_empty =
+            string.alloc_equal(&mut cs.namespace(|| "rest is empty"), &g.empty_str_ptr)?;
+        let is_string = and(
+            &mut cs.namespace(|| "input is the string"),
+            &is_bytes,
+            &is_empty,
+        )?;
+
+        let digest_bits = sha256(cs.namespace(|| "sha256"), &bits)?;
+        let digest = digest_bits
+            .chunks(8)
+            .enumerate()
+            .map(|(i, byte_bits)| {
+                let byte = pack_be(cs.namespace(|| format!("digest byte {i}")), byte_bits)?;
+                Ok(AllocatedPtr::from_parts(g.u64_tag.clone(), byte))
+            })
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+        let digest = AllocatedPtr::construct_list(
+            cs.namespace(|| "digest"),
+            g,
+            store,
+            &digest.iter().collect::<Vec<_>>(),
+        )?;
+
+        let result = AllocatedPtr::pick(cs.namespace(|| "result"), &is_string, &digest, input)?;
+        let cont = AllocatedContPtr::pick(
+            cs.namespace(|| "cont"),
+            &is_string,
+            input_cont,
+            &g.error_ptr_cont,
+        )?;
+        Ok((result, input_env.clone(), cont))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bellpepper_core::test_cs::TestConstraintSystem;
+    use lurk_macros::Coproc;
+    use pasta_curves::pallas::Scalar as Fr;
+
+    use super::*;
+    use crate as lurk;
+    use crate::eval::{empty_sym_env, lang::Lang, Evaluator};
+    use crate::proof::{dummy::DummyProver, Prover};
+    use crate::state::user_sym;
+
+    #[derive(Clone, Debug, Coproc)]
+    enum Sha256Coproc<F: LurkField> {
+        Sha256(Sha256Coprocessor<F>),
+    }
+
+    fn sha256_lang(s: &mut Store<Fr>, lengths: &[usize]) -> Lang<Fr, Sha256Coproc<Fr>> {
+        let mut lang = Lang::new();
+        for n in lengths {
+            lang.add_coprocessor(
+                user_sym(&format!("sha256-{n}")),
+                Sha256Coproc::Sha256(Sha256Coprocessor::new(*n)),
+                s,
+            );
+        }
+        lang
+    }
+
+    fn expected_digest(s: &mut Store<Fr>, input: &str) -> Ptr<Fr> {
+        let digest = Sha256::digest(input.as_bytes())
+            .iter()
+            .map(|byte| s.uint64(u64::from(*byte)))
+            .collect::<Vec<_>>();
+        s.list(&digest)
+    }
+
+    #[test]
+    fn sha256_matches_sha2() {
+        let multi_block = "lurk ".repeat(30);
+        let inputs = ["", "abc", multi_block.as_str()];
+        let s = &mut Store::<Fr>::default();
+        let lang = sha256_lang(s, &inputs.map(str::len));
+        let prover = DummyProver::new(1, lang.clone());
+        let env = empty_sym_env(s);
+
+        for input in inputs {
+            let expr = s
+                .read(&format!("(sha256-{} \"{input}\")", input.len()))
+                .unwrap();
+            let (io, ..) = Evaluator::new(expr, env, s, 10, &lang).eval().unwrap();
+            assert_eq!(expected_digest(s, input), io.expr, "{input}");
+            assert_eq!(s.intern_cont_terminal(), io.cont);
+
+            let (proof, z0, zi, num_steps) = prover.evaluate_and_prove(expr, env, s, 10).unwrap();
+            assert!(proof.verify(num_steps, &z0, &zi));
+        }
+    }
+
+    #[test]
+    fn sha256_rejects_other_arguments() {
+        let s = &mut Store::<Fr>::default();
+        let lang = sha256_lang(s, &[3]);
+        let prover = DummyProver::new(1, lang.clone());
+        let env = empty_sym_env(s);
+
+        for src in [
+            "(sha256-3 \"abcd\")",
+            "(sha256-3 \"ab\")",
+            "(sha256-3 \"aλc\")",
+            "(sha256-3 123)",
+            "(sha256-3 \"abc\" \"abc\")",
+        ] {
+            let expr = s.read(src).unwrap();
+            let (io, ..) = Evaluator::new(expr, env, s, 10, &lang).eval().unwrap();
+            assert_eq!(s.intern_cont_error(), io.cont, "{src}");
+
+            let (proof, z0, zi, num_steps) = prover.evaluate_and_prove(expr, env, s, 10).unwrap();
+            assert!(proof.verify(num_steps, &z0, &zi));
+        }
+    }
+
+    /// Synthesizes a call on `input`, witnessed by `steps`, or honestly without them. Returns
+    /// whether the constraints are satisfied, and whether the call returns the error
+    /// continuation.
+    fn synthesize_call(
+        s: &mut Store<Fr>,
+        sha: &Sha256Coprocessor<Fr>,
+        input: Ptr<Fr>,
+        steps: Option<Vec<Option<(u32, Fr)>>>,
+    ) -> (bool, bool) {
+        s.hydrate_scalar_cache();
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let g = GlobalAllocations::new(&mut cs, s).unwrap();
+        let input =
+            AllocatedPtr::alloc_ptr(&mut cs.namespace(|| "input"), s, || Ok(&input)).unwrap();
+        let steps = steps.unwrap_or_else(|| sha.steps(s, &input));
+        let (_, _, cont) = sha
+            .synthesize_steps(&mut cs, &g, s, &input, &g.nil_ptr, &g.terminal_ptr, steps)
+            .unwrap();
+        (
+            cs.is_satisfied(),
+            cont.tag().get_value() == g.error_ptr_cont.tag().get_value(),
+        )
+    }
+
+    #[test]
+    fn sha256_rejects_forged_witness() {
+        let s = &mut Store::<Fr>::default();
+        let sha = Sha256Coprocessor::new(3);
+        let abc = s.read("\"abc\"").unwrap();
+
+        assert_eq!((true, false), synthesize_call(s, &sha, abc, None));
+        for src in ["\"ab\"", "\"abcd\"", "\"aλc\"", "123"] {
+            let input = s.read(src).unwrap();
+            assert_eq!((true, true), synthesize_call(s, &sha, input, None), "{src}");
+        }
+
+        // Taking apart another string, or nothing at all, would make `abc` look like it isn't a
+        // string of 3 bytes, which must not satisfy the constraints.
+        let abd = s.read("\"abd\"").unwrap();
+        s.hydrate_scalar_cache();
+        let abd =
+            AllocatedPtr::alloc_ptr(&mut TestConstraintSystem::new(), s, || Ok(&abd)).unwrap();
+        let forgeries = [sha.steps(s, &abd), vec![Some((0, Fr::from(0))); 3]];
+        for steps in forgeries {
+            assert!(!synthesize_call(s, &sha, abc, Some(steps)).0);
+        }
+    }
+}
@@ -1,7 +1,13 @@
 use crate::eval::IO;
 use crate::field::LurkField;
 use crate::hash_witness::ConsName;
-use crate::store;
+use crate::parser;
+use crate::ptr::Ptr;
+use crate::state::State;
+use crate::store::{self, Store};
+use crate::symbol::Symbol;
+use crate::tag::{ExprTag, Op2};
+use crate::writer::Write;
 
 use bellpepper_core::SynthesisError;
 use nova::errors::NovaError;
@@ -15,6 +21,14 @@ pub enum ProofError {
     Synthesis(#[from] SynthesisError),
     #[error("Reduction error: {0}")]
     Reduction(#[from] ReductionError),
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+    #[error("Proof was created with different public parameters")]
+    ParamsMismatch,
+    #[error("Only recursive proofs can be extended")]
+    NotRecursive,
+    #[error("There are no frames to prove")]
+    NoFrames,
 }
 
 impl From<store::Error> for ProofError {
@@ -33,8 +47,92 @@ pub enum ReductionError {
     Store(#[from] store::Error),
 }
 
+/// Why `Lang::try_add_coprocessor` refused a coprocessor
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum LangError {
+    #[error("A coprocessor is already bound to {0}")]
+    NameCollision(Symbol),
+    #[error("Coprocessor {name} has a circuit taking {expected} arguments, but its evaluation takes {got}")]
+    CoprocessorArity {
+        name: Symbol,
+        expected: usize,
+        got: usize,
+    },
+}
+
+/// Why a reduction step ended in the error continuation, as reported by `IO::reduce_checked` and
+/// `Evaluator::eval_checked`. The step still reduces to the error continuation, since that's what
+/// the circuit proves.
+#[derive(Error, Debug, Clone)]
+pub enum EvalError<F: LurkField> {
+    #[error("Unbound variable")]
+    Unbound { sym: Ptr<F> },
+    #[error("Tried to apply a {ptr_tag}, which is not a function")]
+    NotAFunction { ptr_tag: ExprTag },
+    /// Since functions are curried, applying a zero-argument function to arguments reports the
+    /// first argument only.
+    #[error("Expected {expected} arguments, but got {got}")]
+    Arity { expected: usize, got: usize },
+    /// A coprocessor was called with other than its `Coprocessor::eval_arity` arguments
+    #[error("Coprocessor expected {expected} arguments, but got {got}")]
+    CoprocessorArity { expected: usize, got: usize },
+    #[error("Coprocessor failed")]
+    CoprocessorFailed { name: Ptr<F> },
+    /// The divisor of a `/` or `%` was zero, raised by the step applying its `Binop2` continuation.
+    /// These are the quotient and remainder operators on `U64`, there are no separate `quot` and `rem`
+    #[error("Division by zero in {operator}")]
+    DivisionByZero { operator: Op2 },
+    /// A `car` or `cdr` of anything but a cons, string or nil, or in strict mode, of anything but a
+    /// cons, see `Evaluator::set_strict_car_cdr`
+    #[error("Tried to take apart a {ptr_tag}, which is not a cons")]
+    NotACons { ptr_tag: ExprTag },
+    #[error("Error evaluating expression")]
+    Other { expr: Ptr<F> },
+    #[error("Reduction error: {0}")]
+    Reduction(#[from] ReductionError),
+}
+
+impl<F: LurkField> Write<F> for EvalError<F> {
+    fn fmt<W: std::io::Write>(
+        &self,
+        store: &Store<F>,
+        state: &State,
+        w: &mut W,
+    ) -> std::io::Result<()> {
+        match self {
+            Self::Unbound { sym } => {
+                write!(w, "Unbound variable: ")?;
+                sym.fmt(store, state, w)
+            }
+            Self::CoprocessorFailed { name } => {
+                write!(w, "Coprocessor failed: ")?;
+                name.fmt(store, state, w)
+            }
+            Self::Other { expr } => {
+                write!(w, "Error evaluating ")?;
+                expr.fmt(store, state, w)
+            }
+            Self::NotAFunction { .. }
+            | Self::NotACons { .. }
+            | Self::Arity { .. }
+            | Self::CoprocessorArity { .. }
+            | Self::DivisionByZero { .. }
+            | Self::Reduction(_) => {
+                write!(w, "{self}")
+            }
+        }
+    }
+}
+
 #[derive(Error, Debug, Clone)]
 pub enum LurkError<F: LurkField> {
     #[error("Explicit Lurk error; IO: {0}")]
     IO(IO<F>),
+    #[error("Parse error: {0}")]
+    Parse(#[from] parser::Error),
+    #[error("Evaluation error: {0}")]
+    Eval(#[from] EvalError<F>),
+    /// Evaluation took all of its `limit` steps without reaching a terminal or error continuation
+    #[error("Evaluation didn't finish in {limit} steps; IO: {io}")]
+    Incomplete { io: IO<F>, limit: usize },
 }
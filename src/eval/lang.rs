@@ -6,6 +6,7 @@ use lurk_macros::Coproc;
 use serde::{Deserialize, Serialize};
 
 use crate::coprocessor::{CoCircuit, Coprocessor};
+use crate::error::LangError;
 use crate::field::LurkField;
 use crate::ptr::Ptr;
 use crate::store::Store;
@@ -70,6 +71,10 @@ pub enum Coproc<F: LurkField> {
 /// It allows late-binding of the exact set of coprocessors by using a type parameter `C` that
 /// is expected to have the [`crate::coprocessor::Coprocessor`] trait bound in concrete instantiations.
 ///
+/// It is also the registry of those coprocessors: each is bound to a symbol, and the evaluator
+/// dispatches a call whose head is a bound symbol to its coprocessor. Binding validates the
+/// coprocessor's arity, and `try_add_coprocessor` also refuses to rebind a name.
+///
 /// # Type Parameters
 /// - `F`: A field type that implements the [`crate::field::LurkField`] trait.
 /// - `C`: A type that implements the [`crate::coprocessor::Coprocessor`] trait. This allows late-binding of the
@@ -89,6 +94,11 @@ impl<F: LurkField, C: Coprocessor<F>> Lang<F, C> {
         }
     }
 
+    /// A `Lang` with each of `bindings` added by `add_binding`.
+    ///
+    /// # Panics
+    ///
+    /// Like `add_binding`.
     pub fn new_with_bindings<B: Into<Binding<F, C>>>(s: &mut Store<F>, bindings: Vec<B>) -> Self {
         let mut new = Self {
             coprocessors: Default::default(),
@@ -113,26 +123,71 @@ impl<F: LurkField, C: Coprocessor<F>> Lang<F, C> {
         key
     }
 
+    /// Binds `name` to `cproc`, replacing any coprocessor it was bound to.
+    ///
+    /// # Panics
+    ///
+    /// If the coprocessor's circuit and evaluation disagree on its arity, which
+    /// `try_add_coprocessor` returns as an error instead.
     pub fn add_coprocessor<T: Into<C>, S: Into<Symbol>>(
         &mut self,
         name: S,
         cproc: T,
         store: &mut Store<F>,
     ) {
+        if let Err(e) = self.insert_coprocessor(name.into(), cproc.into(), store) {
+            panic!("{e}");
+        }
+    }
+
+    /// Like `add_coprocessor`, but refuses to rebind a name already bound to a coprocessor, and
+    /// returns an error rather than panicking for a coprocessor whose circuit and evaluation
+    /// disagree on its arity.
+    pub fn try_add_coprocessor<T: Into<C>, S: Into<Symbol>>(
+        &mut self,
+        name: S,
+        cproc: T,
+        store: &mut Store<F>,
+    ) -> Result<(), LangError> {
         let name = name.into();
+        if self.coprocessors.contains_key(&name) {
+            return Err(LangError::NameCollision(name));
+        }
+        self.insert_coprocessor(name, cproc.into(), store)
+    }
+
+    /// Binds `name` to `cproc`, unless its circuit has a different arity than `evaluate`, which
+    /// would make evaluations calling the coprocessor unprovable. Every way of binding a
+    /// coprocessor comes through here, so that's only checked once, when it's bound.
+    fn insert_coprocessor(
+        &mut self,
+        name: Symbol,
+        cproc: C,
+        store: &mut Store<F>,
+    ) -> Result<(), LangError> {
+        if cproc.has_circuit() && cproc.arity() != cproc.eval_arity() {
+            return Err(LangError::CoprocessorArity {
+                name,
+                expected: cproc.arity(),
+                got: cproc.eval_arity(),
+            });
+        }
         // TODO: Check if intern_symbol should take a reference
         let ptr = store.intern_symbol(&name);
         let z_ptr = store.hash_expr(&ptr).unwrap();
 
-        self.coprocessors.insert(name, (cproc.into(), z_ptr));
+        self.coprocessors.insert(name, (cproc, z_ptr));
+        Ok(())
     }
 
+    /// Binds the coprocessor of `binding` like `add_coprocessor`.
+    ///
+    /// # Panics
+    ///
+    /// Like `add_coprocessor`, if the coprocessor's circuit and evaluation disagree on its arity.
     pub fn add_binding<B: Into<Binding<F, C>>>(&mut self, binding: B, store: &mut Store<F>) {
         let Binding { name, coproc, _p } = binding.into();
-        let ptr = store.intern_symbol(&name);
-        let z_ptr = store.hash_expr(&ptr).unwrap();
-
-        self.coprocessors.insert(name, (coproc, z_ptr));
+        self.add_coprocessor(name, coproc, store);
     }
 
     pub fn coprocessors(&self) -> &HashMap<Symbol, (C, ZExprPtr<F>)> {
@@ -1,15 +1,15 @@
 use crate::coprocessor::Coprocessor;
-use crate::error::ReductionError;
+use crate::error::{EvalError, LurkError, ReductionError};
 use crate::expr::Expression;
-use crate::field::LurkField;
-use crate::hash_witness::{ConsWitness, ContWitness};
+use crate::field::{LanguageField, LurkField};
+use crate::hash_witness::{ConsName, ConsWitness, ContWitness, HashName, Stub};
 use crate::ptr::{ContPtr, Ptr};
 use crate::state::{initial_lurk_state, State};
 use crate::store::Store;
-use crate::tag::ContTag;
+use crate::tag::{ContTag, ExprTag};
 use crate::writer::Write;
 use crate::{lurk_sym_ptr, store};
-use lang::Lang;
+use lang::{Coproc, Lang};
 
 use log::info;
 #[cfg(not(target_arch = "wasm32"))]
@@ -20,6 +20,7 @@ use serde::{Deserialize, Serialize};
 use std::cmp::PartialEq;
 use std::iter::{Iterator, Take};
 use std::marker::PhantomData;
+use std::time::Instant;
 
 pub mod lang;
 
@@ -28,7 +29,10 @@ mod reduction;
 #[cfg(test)]
 pub(crate) mod tests;
 
-#[derive(Clone, Debug, PartialEq, Copy, Eq)]
+/// An `IO` serializes as its pointers, so like them it must be paired
+/// with a snapshot of its store, see `Store::dump`.
+#[derive(Clone, Debug, PartialEq, Copy, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct IO<F: LurkField> {
     pub expr: Ptr<F>,
     pub env: Ptr<F>,
@@ -58,6 +62,27 @@ impl<F: LurkField> std::fmt::Display for IO<F> {
     }
 }
 
+/// The result of `Evaluator::eval_with_limit` and `Evaluator::eval_deadline`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvalOutcome<F: LurkField> {
+    /// Evaluation reached a terminal or error continuation.
+    Complete(IO<F>),
+    /// Evaluation ran out of steps. `last_frame` is the state, continuation included, that
+    /// `Evaluator::resume` restarts from.
+    Incomplete {
+        last_frame: IO<F>,
+        steps_taken: usize,
+    },
+    /// Evaluation ran past its deadline, see `Incomplete`
+    TimedOut {
+        last_frame: IO<F>,
+        steps_taken: usize,
+    },
+}
+
+/// How many steps `Evaluator::eval_deadline` takes between readings of the clock by default
+pub const DEFAULT_CLOCK_INTERVAL: usize = 1000;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Frame<T: Copy, W: Copy, C> {
     pub input: T,
@@ -156,6 +181,18 @@ impl<F: LurkField, W: Copy, C: Coprocessor<F>> Frame<IO<F>, W, C> {
             && <IO<F> as Evaluable<F, Witness<F>, C>>::is_complete(&self.output)
     }
 
+    /// Renders this frame's step, with the tag of the continuation it reduces under, as
+    /// `i [tag]: input => output`.
+    pub fn pretty(&self, store: &Store<F>) -> String {
+        format!(
+            "{} [{}]: {} => {}",
+            self.i,
+            self.input.cont.tag,
+            self.input.fmt_to_string(store, initial_lurk_state()),
+            self.output.fmt_to_string(store, initial_lurk_state())
+        )
+    }
+
     pub fn log(&self, store: &Store<F>) {
         // This frame's output is the input for the next frame.
         // Report that index. Otherwise we can't report the initial input.
@@ -231,6 +268,16 @@ impl<F: LurkField, C: Coprocessor<F>> Evaluable<F, Witness<F>, C> for IO<F> {
 }
 
 impl<F: LurkField> IO<F> {
+    /// Takes one reduction step, like `Evaluable::reduce`, also returning the `EvalError` raised by
+    /// the step when it ends in the error continuation.
+    pub fn reduce_checked<C: Coprocessor<F>>(
+        &self,
+        store: &mut Store<F>,
+        lang: &Lang<F, C>,
+    ) -> Result<(Self, Witness<F>, Option<EvalError<F>>), ReductionError> {
+        reduction::reduce_checked(self.expr, self.env, self.cont, store, lang)
+    }
+
     // Returns any expression that was emitted in this IO (if an output) or previous (if an input).
     // The intention is that this method will be used to extract and handle all output as needed.
     pub fn maybe_emitted_expression(&self, store: &Store<F>) -> Option<Ptr<F>> {
@@ -407,6 +454,19 @@ pub struct Witness<F: LurkField> {
     pub(crate) conts: ContWitness<F>,
 }
 
+impl<F: LurkField> Witness<F> {
+    /// The tag of the value this step's `car` or `cdr` took apart, if it applied one. A `car` or
+    /// `cdr` of something that can't be taken apart leaves its slot unfilled, and the step then
+    /// outputs that value along with the error continuation.
+    fn car_cdr_operand_tag(&self, output: &IO<F>) -> Option<ExprTag> {
+        match &self.conses.slots[ConsName::UnopConsLike.index()] {
+            (ConsName::UnopConsLike, Stub::Value(cons)) => Some(cons.cons.tag),
+            (ConsName::UnopConsLike, _) => Some(output.expr.tag),
+            _ => None,
+        }
+    }
+}
+
 impl<'a, F: LurkField, C: Coprocessor<F>> Evaluator<'a, F, C>
 where
     IO<F>: Copy,
@@ -425,37 +485,182 @@ where
             store,
             limit,
             lang,
+            emitted: vec![],
+            state: None,
+            strict_car_cdr: false,
+            clock_interval: DEFAULT_CLOCK_INTERVAL,
         }
     }
 
+    /// The values passed to `emit` during the last evaluation, in the order they were emitted.
+    /// Each comes from a step whose output continuation is `Emit`, so it's part of that step's
+    /// frame and is witnessed when the frame is proved.
+    pub fn emitted(&self) -> &[Ptr<F>] {
+        &self.emitted
+    }
+
     pub fn eval(&mut self) -> Result<(IO<F>, usize, Vec<Ptr<F>>), ReductionError> {
         let mut io = self.initial();
         Evaluable::<F, Witness<F>, C>::log(&io, self.store, 0);
         let mut iterations = 0;
-        let mut emitted_vec = vec![];
+        self.emitted.clear();
         for _ in 0..self.limit {
             if Evaluable::<F, Witness<F>, C>::is_complete(&io) {
                 break;
             }
             (io, _) = io.reduce(self.store, self.lang)?;
             if let Some(emitted) = io.maybe_emitted_expression(self.store) {
-                emitted_vec.push(emitted);
+                self.emitted.push(emitted);
             }
             iterations += 1;
             Evaluable::<F, Witness<F>, C>::log(&io, self.store, iterations);
         }
-        Ok((io, iterations, emitted_vec))
+        Ok((io, iterations, self.emitted.clone()))
+    }
+
+    /// Like `eval`, but an evaluation ending in the error continuation returns the `EvalError`
+    /// raised by the step that reduced to it. With `set_strict_car_cdr`, a `car` or `cdr` of
+    /// anything but a cons also fails, with `EvalError::NotACons`.
+    pub fn eval_checked(&mut self) -> Result<(IO<F>, usize, Vec<Ptr<F>>), EvalError<F>> {
+        let mut io = self.initial();
+        let mut iterations = 0;
+        self.emitted.clear();
+        for _ in 0..self.limit {
+            if Evaluable::<F, Witness<F>, C>::is_complete(&io) {
+                break;
+            }
+            let (next, witness, cause) = io.reduce_checked(self.store, self.lang)?;
+            if self.strict_car_cdr {
+                match witness.car_cdr_operand_tag(&next) {
+                    Some(ExprTag::Cons) | None => (),
+                    Some(ptr_tag) => return Err(EvalError::NotACons { ptr_tag }),
+                }
+            }
+            if let Some(cause) = cause {
+                return Err(cause);
+            }
+            if let Some(emitted) = next.maybe_emitted_expression(self.store) {
+                self.emitted.push(emitted);
+            }
+            io = next;
+            iterations += 1;
+        }
+        Ok((io, iterations, self.emitted.clone()))
     }
 
+    /// Evaluates each of `programs` with `eval_checked`, each in the empty environment and with
+    /// its own `limit` steps, rather than this evaluator's expression. The programs share the
+    /// store, so structure interned for one is reused by the next, but bindings made by one are
+    /// never seen by another.
+    pub fn eval_many(&mut self, programs: &[Ptr<F>]) -> Vec<Result<Ptr<F>, EvalError<F>>> {
+        let env = empty_sym_env(self.store);
+        programs
+            .iter()
+            .map(|program| {
+                let mut evaluator =
+                    Evaluator::new(*program, env, self.store, self.limit, self.lang);
+                evaluator.set_strict_car_cdr(self.strict_car_cdr);
+                evaluator.eval_checked().map(|(io, ..)| io.expr)
+            })
+            .collect()
+    }
+
+    /// Evaluates for at most `limit` steps, returning the partial state if evaluation doesn't
+    /// complete in time.
+    pub fn eval_with_limit(&mut self) -> Result<EvalOutcome<F>, ReductionError> {
+        let io = self.initial();
+        self.resume(io)
+    }
+
+    /// Like `eval_with_limit`, but evaluates from `io`, typically the `last_frame` of an
+    /// incomplete outcome, instead of from the initial state. Here `limit` bounds the additional
+    /// steps taken. Since reduction is deterministic, a resumed evaluation ends in the same state
+    /// as an uninterrupted one. To resume in another process, save the `IO` along with a
+    /// `Store::dump` of its store.
+    pub fn resume(&mut self, mut io: IO<F>) -> Result<EvalOutcome<F>, ReductionError> {
+        let mut steps_taken = 0;
+        while steps_taken < self.limit && !Evaluable::<F, Witness<F>, C>::is_complete(&io) {
+            (io, _) = io.reduce(self.store, self.lang)?;
+            steps_taken += 1;
+        }
+        if Evaluable::<F, Witness<F>, C>::is_complete(&io) {
+            Ok(EvalOutcome::Complete(io))
+        } else {
+            Ok(EvalOutcome::Incomplete {
+                last_frame: io,
+                steps_taken,
+            })
+        }
+    }
+
+    /// Like `eval_with_limit`, but also stops once `deadline` has passed, with a `TimedOut`
+    /// outcome that can be resumed like an incomplete one. The clock is only read every
+    /// `set_clock_interval` steps, so evaluation may run that many steps past the deadline.
+    pub fn eval_deadline(&mut self, deadline: Instant) -> Result<EvalOutcome<F>, ReductionError> {
+        let mut io = self.initial();
+        let mut steps_taken = 0;
+        while !Evaluable::<F, Witness<F>, C>::is_complete(&io) {
+            if steps_taken % self.clock_interval == 0 && Instant::now() >= deadline {
+                return Ok(EvalOutcome::TimedOut {
+                    last_frame: io,
+                    steps_taken,
+                });
+            }
+            if steps_taken == self.limit {
+                return Ok(EvalOutcome::Incomplete {
+                    last_frame: io,
+                    steps_taken,
+                });
+            }
+            (io, _) = io.reduce(self.store, self.lang)?;
+            steps_taken += 1;
+        }
+        Ok(EvalOutcome::Complete(io))
+    }
+
+    /// Makes `eval_deadline` read the clock every `steps` steps, at least one, instead of every
+    /// `DEFAULT_CLOCK_INTERVAL`. Fewer readings cost less, but overshoot the deadline further.
+    pub fn set_clock_interval(&mut self, steps: usize) {
+        self.clock_interval = steps.max(1);
+    }
+
+    /// The state evaluation starts from: the one set by `set_io_state` if any, and otherwise
+    /// `expr` in `env` with the outermost continuation
     #[inline]
     pub fn initial(&mut self) -> IO<F> {
-        IO {
-            expr: self.expr,
-            env: self.env,
-            cont: self.store.intern_cont_outermost(),
+        match self.state {
+            Some(io) => io,
+            None => IO {
+                expr: self.expr,
+                env: self.env,
+                cont: self.store.intern_cont_outermost(),
+            },
         }
     }
 
+    /// The state the next evaluation starts from, see `set_io_state`
+    pub fn io_state(&mut self) -> IO<F> {
+        self.initial()
+    }
+
+    /// Makes every later evaluation, and the frames of `get_frames` and `trace`, start from `io`,
+    /// typically the input of some earlier frame. Since reduction is deterministic, the frames
+    /// from there on are the same as those the earlier evaluation produced, which is what lets a
+    /// debugger step back by replaying from a saved state. The state's pointers must be in this
+    /// evaluator's store.
+    pub fn set_io_state(&mut self, io: IO<F>) {
+        self.state = Some(io);
+    }
+
+    /// Makes `eval_checked` reject a `car` or `cdr` of anything but a cons. By default, as in
+    /// the circuit, `car` and `cdr` of `nil` are `nil`, those of a string take it apart into its
+    /// first character and the rest, and those of any other atom end in the error continuation,
+    /// which `eval_checked` reports as `EvalError::NotACons`. Only the checking changes: `eval` and
+    /// proofs still follow the default.
+    pub fn set_strict_car_cdr(&mut self, strict: bool) {
+        self.strict_car_cdr = strict;
+    }
+
     pub fn iter(&mut self) -> Result<Take<FrameIt<'_, Witness<F>, F, C>>, ReductionError> {
         let initial_input = self.initial();
 
@@ -486,6 +691,16 @@ where
         Ok(frames)
     }
 
+    /// Like `get_frames`, but without the identity frame that follows a completed evaluation, so
+    /// there's exactly one frame per reduction step. See `Frame::pretty` for rendering them.
+    pub fn trace(&mut self) -> Result<Vec<Frame<IO<F>, Witness<F>, C>>, ReductionError> {
+        let mut frames = self.get_frames()?;
+        if frames.last().map_or(false, |frame| frame.is_complete()) {
+            frames.pop();
+        }
+        Ok(frames)
+    }
+
     pub fn generate_frames<Fp: Fn(usize) -> bool>(
         expr: Ptr<F>,
         env: Ptr<F>,
@@ -517,11 +732,75 @@ where
     }
 }
 
+#[cfg(feature = "async")]
+impl<'a, F: LurkField, C: crate::coprocessor::AsyncCoprocessor<F>> Evaluator<'a, F, C>
+where
+    IO<F>: Copy,
+{
+    /// Like `eval`, but lets coprocessors fetch external data first, see `AsyncCoprocessor`.
+    pub async fn eval_async(&mut self) -> Result<(IO<F>, usize, Vec<Ptr<F>>), ReductionError> {
+        let mut io = self.initial();
+        Evaluable::<F, Witness<F>, C>::log(&io, self.store, 0);
+        let mut iterations = 0;
+        let mut emitted_vec = vec![];
+        for _ in 0..self.limit {
+            if Evaluable::<F, Witness<F>, C>::is_complete(&io) {
+                break;
+            }
+            self.resolve_coprocessor_call(&io).await;
+            (io, _) = io.reduce(self.store, self.lang)?;
+            if let Some(emitted) = io.maybe_emitted_expression(self.store) {
+                emitted_vec.push(emitted);
+            }
+            iterations += 1;
+            Evaluable::<F, Witness<F>, C>::log(&io, self.store, iterations);
+        }
+        Ok((io, iterations, emitted_vec))
+    }
+
+    /// If `io` is about to call a coprocessor with the right number of arguments, await its
+    /// `evaluate_async`
+    async fn resolve_coprocessor_call(&mut self, io: &IO<F>) {
+        if !io.expr.is_cons() {
+            return;
+        }
+        let Some((head, args)) = self.store.fetch_cons(&io.expr).copied() else {
+            return;
+        };
+        let lang = self.lang;
+        let Some((coprocessor, _)) = lang.lookup(self.store, head) else {
+            return;
+        };
+        match self.store.fetch_list(&args) {
+            Some(argv) if argv.len() == coprocessor.eval_arity() => {
+                coprocessor.evaluate_async(self.store, &argv).await
+            }
+            _ => (),
+        }
+    }
+}
+
 #[inline]
 pub fn empty_sym_env<F: LurkField>(store: &Store<F>) -> Ptr<F> {
     lurk_sym_ptr!(store, nil)
 }
 
+/// Takes one reduction step from `expr`, `env` and `cont`, returning the next `IO`, without any
+/// of the proof-system machinery. It's the very step `Evaluator` takes, and the one that computes
+/// the circuit's witness, so agreeing with the circuit shows the constraints match this step, not
+/// that the step itself is right; its results are checked against independent expectations for
+/// that.
+pub fn reduce_reference<F: LurkField, C: Coprocessor<F>>(
+    expr: Ptr<F>,
+    env: Ptr<F>,
+    cont: ContPtr<F>,
+    store: &mut Store<F>,
+    lang: &Lang<F, C>,
+) -> Result<IO<F>, ReductionError> {
+    let (expr, env, cont, _) = reduction::reduce(expr, env, cont, store, lang)?;
+    Ok(IO { expr, env, cont })
+}
+
 // Convenience functions, mostly for use in tests.
 
 pub fn eval_to_ptr<F: LurkField, C: Coprocessor<F>>(
@@ -537,10 +816,88 @@ pub fn eval_to_ptr<F: LurkField, C: Coprocessor<F>>(
         .expr)
 }
 
+/// Reads `src`, then evaluates it in the empty environment, returning the result. Evaluation
+/// stops after a million steps, like `eval_to_ptr`, and it's an error not to have finished by then.
+pub fn eval_str<F: LurkField, C: Coprocessor<F>>(
+    s: &mut Store<F>,
+    src: &str,
+) -> Result<Ptr<F>, LurkError<F>> {
+    eval_str_with_limit::<F, C>(s, src, 1000000)
+}
+
+fn eval_str_with_limit<F: LurkField, C: Coprocessor<F>>(
+    s: &mut Store<F>,
+    src: &str,
+    limit: usize,
+) -> Result<Ptr<F>, LurkError<F>> {
+    let expr = s.read(src)?;
+    let lang = Lang::<F, C>::new();
+    let (io, ..) = Evaluator::new(expr, empty_sym_env(s), s, limit, &lang).eval_checked()?;
+    match Status::from(io.cont) {
+        Status::Terminal => Ok(io.expr),
+        Status::Error => Err(LurkError::IO(io)),
+        Status::Incomplete => Err(LurkError::Incomplete { io, limit }),
+    }
+}
+
+/// The field of the Nova curve cycle to evaluate a program over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldChoice {
+    Pallas,
+    Vesta,
+}
+
+impl From<FieldChoice> for LanguageField {
+    fn from(choice: FieldChoice) -> Self {
+        match choice {
+            FieldChoice::Pallas => LanguageField::Pallas,
+            FieldChoice::Vesta => LanguageField::Vesta,
+        }
+    }
+}
+
+/// The field-independent parts of a result computed by `run_on_field`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldOutput {
+    pub field: LanguageField,
+    pub tag: ExprTag,
+    /// The result, as printed by the writer
+    pub printed: String,
+    /// The hex digits of the result's hash, which depend on the field
+    pub digest: String,
+}
+
+/// Evaluates `src` with `eval_str` over the field picked by `choice`, so the field can be chosen at
+/// runtime.
+pub fn run_on_field(choice: FieldChoice, src: &str) -> anyhow::Result<FieldOutput> {
+    match choice {
+        FieldChoice::Pallas => run_on::<pasta_curves::pallas::Scalar>(src),
+        FieldChoice::Vesta => run_on::<pasta_curves::vesta::Scalar>(src),
+    }
+}
+
+fn run_on<F: LurkField>(src: &str) -> anyhow::Result<FieldOutput> {
+    let s = &mut Store::<F>::default();
+    let output = eval_str::<F, Coproc<F>>(s, src)?;
+    let digest = s
+        .hash_expr(&output)
+        .ok_or_else(|| anyhow::anyhow!("couldn't hash the result"))?;
+    Ok(FieldOutput {
+        field: F::FIELD,
+        tag: output.tag,
+        printed: output.fmt_to_string(s, initial_lurk_state()),
+        digest: digest.value().hex_digits(),
+    })
+}
+
 pub struct Evaluator<'a, F: LurkField, C: Coprocessor<F>> {
     expr: Ptr<F>,
     env: Ptr<F>,
     store: &'a mut Store<F>,
     limit: usize,
     lang: &'a Lang<F, C>,
+    emitted: Vec<Ptr<F>>,
+    state: Option<IO<F>>,
+    strict_car_cdr: bool,
+    clock_interval: usize,
 }
@@ -1,7 +1,7 @@
 use super::{empty_sym_env, Witness};
 use crate::cont::Continuation;
 use crate::coprocessor::Coprocessor;
-use crate::error::ReductionError;
+use crate::error::{EvalError, ReductionError};
 use crate::eval::{lang::Lang, IO};
 use crate::expr::{Expression, Thunk};
 use crate::field::LurkField;
@@ -21,11 +21,29 @@ pub(crate) fn reduce<F: LurkField, C: Coprocessor<F>>(
     store: &mut Store<F>,
     lang: &Lang<F, C>,
 ) -> Result<(Ptr<F>, Ptr<F>, ContPtr<F>, Witness<F>), ReductionError> {
+    let (IO { expr, env, cont }, witness, _) = reduce_checked(expr, env, cont, store, lang)?;
+
+    Ok((expr, env, cont, witness))
+}
+
+/// Like `reduce`, but also returns the `EvalError` explaining the step when it reduces to the
+/// error continuation.
+pub(crate) fn reduce_checked<F: LurkField, C: Coprocessor<F>>(
+    expr: Ptr<F>,
+    env: Ptr<F>,
+    cont: ContPtr<F>,
+    store: &mut Store<F>,
+    lang: &Lang<F, C>,
+) -> Result<(IO<F>, Witness<F>, Option<EvalError<F>>), ReductionError> {
     let c = *store.expect_constants();
     let (ctrl, witness) = reduce_with_witness(expr, env, cont, store, &c, lang)?;
-    let (new_expr, new_env, new_cont) = ctrl.into_results(store);
+    let cause = match &ctrl {
+        Control::Error(_, _, cause) => Some(cause.clone()),
+        _ => None,
+    };
+    let (expr, env, cont) = ctrl.into_results(store);
 
-    Ok((new_expr, new_env, new_cont, witness))
+    Ok((IO { expr, env, cont }, witness, cause))
 }
 
 #[derive(Debug, Clone)]
@@ -33,7 +51,8 @@ enum Control<F: LurkField> {
     Return(Ptr<F>, Ptr<F>, ContPtr<F>),
     MakeThunk(Ptr<F>, Ptr<F>, ContPtr<F>),
     ApplyContinuation(Ptr<F>, Ptr<F>, ContPtr<F>),
-    Error(Ptr<F>, Ptr<F>),
+    /// The error continuation, with the `EvalError` explaining it
+    Error(Ptr<F>, Ptr<F>, EvalError<F>),
 }
 
 impl<F: LurkField> Control<F> {
@@ -42,7 +61,7 @@ impl<F: LurkField> Control<F> {
             Self::Return(expr, env, cont)
             | Self::MakeThunk(expr, env, cont)
             | Self::ApplyContinuation(expr, env, cont) => (expr, env, cont),
-            Self::Error(expr, env) => (expr, env, store.intern_cont_error()),
+            Self::Error(expr, env, _) => (expr, env, store.intern_cont_error()),
         }
     }
     #[allow(dead_code)]
@@ -115,7 +134,7 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                         // CIRCUIT: sym_otherwise
                         if env.is_nil() {
                             // CIRCUIT: needed_env_missing
-                            Control::Error(expr, env)
+                            Control::Error(expr, env, EvalError::Unbound { sym: expr })
                         } else {
                             // CIRCUIT: main
                             let (binding, smaller_env) =
@@ -124,7 +143,7 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                                 // If binding is NIL, it's empty. There is no match. Return an error due to unbound variable.
 
                                 // CIRCUIT: needed_binding_missing
-                                Control::Error(expr, env)
+                                Control::Error(expr, env, EvalError::Unbound { sym: expr })
                             } else {
                                 // Binding is not NIL, so it is either a normal binding or a recursive environment.
 
@@ -259,7 +278,7 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                                             }
                                         }
                                     }
-                                    _ => Control::Error(expr, env), // CIRCUIT: with_other_binding
+                                    _ => Control::Error(expr, env, EvalError::Other { expr }), // CIRCUIT: with_other_binding
                                 }
                             }
                         }
@@ -278,7 +297,10 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                             let pair = cons_witness.car_cdr_named($cons_name, store, $cons);
 
                             if matches!(pair, Err(ReductionError::CarCdrType(_))) {
-                                return Ok((Control::Error(expr, env), None));
+                                return Ok((
+                                    Control::Error(expr, env, EvalError::Other { expr }),
+                                    None,
+                                ));
                             } else {
                                 pair
                             }
@@ -316,6 +338,11 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                         (c.greater.ptr(), Op2::Greater),
                         (c.less_equal.ptr(), Op2::LessEqual),
                         (c.greater_equal.ptr(), Op2::GreaterEqual),
+                        (c.bit_and.ptr(), Op2::BitAnd),
+                        (c.bit_or.ptr(), Op2::BitOr),
+                        (c.bit_xor.ptr(), Op2::BitXor),
+                        (c.shift_left.ptr(), Op2::ShiftLeft),
+                        (c.shift_right.ptr(), Op2::ShiftRight),
                     ];
 
                     if head == lambda {
@@ -328,12 +355,27 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                             cons_witness.car_cdr_named(ConsName::ExprCadr, store, &args)?
                         };
                         if arg.tag != ExprTag::Sym {
-                            Control::Error(expr, env)
+                            Control::Error(expr, env, EvalError::Other { expr })
                         } else {
                             let (_, cdr_args) =
                                 cons_witness.car_cdr_named(ConsName::ExprCadr, store, &args)?;
                             let inner_body = if cdr_args.is_nil() {
                                 body
+                            } else if cdr_args.tag == ExprTag::Sym {
+                                // (LAMBDA (A . REST) STUFF)
+                                // becomes a variadic function of A with body (LAMBDA (REST) . STUFF),
+                                // which is the form itself rather than a list of it. See
+                                // `ContTag::Call2` for how it is applied.
+                                let nil = lurk_sym_ptr!(store, nil);
+                                let formals = cons_witness.cons_named(
+                                    ConsName::InnerLambda,
+                                    store,
+                                    cdr_args,
+                                    nil,
+                                );
+                                let inner =
+                                    cons_witness.cons_named(ConsName::Lambda, store, formals, body);
+                                cons_witness.cons_named(ConsName::InnerBody, store, lambda, inner)
                             } else {
                                 // (LAMBDA (A B) STUFF)
                                 // becomes (LAMBDA (A) (LAMBDA (B) STUFF))
@@ -356,7 +398,7 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                         let (quoted, end) = car_cdr_named!(ConsName::ExprCdr, &rest)?;
 
                         if !end.is_nil() {
-                            Control::Error(expr, env)
+                            Control::Error(expr, env, EvalError::Other { expr })
                         } else {
                             Control::ApplyContinuation(quoted, env, cont)
                         }
@@ -366,7 +408,7 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                             cons_witness.car_cdr_named(ConsName::ExprCddr, store, &body)?;
                         // Only a single body form allowed for now.
                         if !rest_body.is_nil() || body.is_nil() {
-                            Control::Error(expr, env)
+                            Control::Error(expr, env, EvalError::Other { expr })
                         } else if bindings.is_nil() {
                             Control::Return(body1, env, cont)
                         } else {
@@ -378,7 +420,7 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                                 &binding1,
                             )?;
                             if var.tag != ExprTag::Sym {
-                                Control::Error(expr, env)
+                                Control::Error(expr, env, EvalError::Other { expr })
                             } else {
                                 let (val, end) = cons_witness.car_cdr_named(
                                     ConsName::ExprCaaadr,
@@ -387,9 +429,11 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                                 )?;
 
                                 if !end.is_nil() {
-                                    Control::Error(expr, env)
+                                    Control::Error(expr, env, EvalError::Other { expr })
                                 } else {
                                     let head_ptr = c.let_.ptr();
+                                    // The remaining bindings become a nested form evaluated in the
+                                    // extended env, so each binding sees the ones before it.
                                     let expanded = if rest_bindings.is_nil() {
                                         body1
                                     } else {
@@ -433,7 +477,7 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                     } else if let Some((_, op)) = binops_map.iter().find(|(ptr, _)| head == *ptr) {
                         let (arg1, more) = car_cdr_named!(ConsName::ExprCdr, &rest)?;
                         if rest.is_nil() || more.is_nil() {
-                            Control::Error(expr, env)
+                            Control::Error(expr, env, EvalError::Other { expr })
                         } else {
                             Control::Return(
                                 arg1,
@@ -475,7 +519,7 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                     } else if let Some((_, op)) = unops_map.iter().find(|(ptr, _)| head == *ptr) {
                         let (arg1, end) = car_cdr_named!(ConsName::ExprCdr, &rest)?;
                         if rest.is_nil() || !end.is_nil() {
-                            Control::Error(expr, env)
+                            Control::Error(expr, env, EvalError::Other { expr })
                         } else {
                             Control::Return(
                                 arg1,
@@ -492,12 +536,15 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                         }
                     } else if head == c.eval.ptr() {
                         if rest.is_nil() {
-                            return Ok((Control::Error(expr, env), None));
+                            return Ok((
+                                Control::Error(expr, env, EvalError::Other { expr }),
+                                None,
+                            ));
                         }
                         let (arg1, more) = car_cdr_named!(ConsName::ExprCdr, &rest)?;
 
                         if rest.is_nil() {
-                            Control::Error(expr, env)
+                            Control::Error(expr, env, EvalError::Other { expr })
                         } else {
                             Control::Return(
                                 arg1,
@@ -525,7 +572,7 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                         let (condition, more) = car_cdr_named!(ConsName::ExprCdr, &rest)?;
 
                         if more.is_nil() {
-                            Control::Error(condition, env)
+                            Control::Error(condition, env, EvalError::Other { expr: condition })
                         } else {
                             Control::Return(
                                 condition,
@@ -542,7 +589,7 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                         }
                     } else if head == c.current_env.ptr() {
                         if !rest.is_nil() {
-                            Control::Error(expr, env)
+                            Control::Error(expr, env, EvalError::Other { expr })
                         } else {
                             Control::ApplyContinuation(env, env, cont)
                         }
@@ -555,19 +602,53 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                         if let Some((coprocessor, _z_ptr)) = lang.lookup(store, head) {
                             let (_arg, _more_args) = car_cdr_named!(ConsName::ExprCdr, &args)?;
 
-                            let IO { expr, env, cont } =
-                                coprocessor.evaluate(store, args, env, cont);
+                            // Check the call against `eval_arity` here, so a call with the wrong
+                            // number of arguments is reported as such rather than as a failure
+                            // of the coprocessor. Like `Coprocessor::evaluate`, it returns `args`.
+                            let cause = match store.fetch_list(&args) {
+                                Some(argv) if argv.len() == coprocessor.eval_arity() => None,
+                                Some(argv) => Some(EvalError::CoprocessorArity {
+                                    expected: coprocessor.eval_arity(),
+                                    got: argv.len(),
+                                }),
+                                None => Some(EvalError::Other { expr }),
+                            };
+                            if let Some(cause) = cause {
+                                return Ok((Control::Error(args, env, cause), closure_to_extend));
+                            }
 
+                            let IO {
+                                expr: result,
+                                env,
+                                cont,
+                            } = coprocessor.evaluate(store, args, env, cont);
+
+                            if cont.tag == ContTag::Error {
+                                return Ok((
+                                    Control::Error(
+                                        result,
+                                        env,
+                                        EvalError::CoprocessorFailed { name: head },
+                                    ),
+                                    closure_to_extend,
+                                ));
+                            }
                             return Ok((
-                                Control::ApplyContinuation(expr, env, cont),
+                                Control::ApplyContinuation(result, env, cont),
                                 closure_to_extend,
                             ));
                         };
 
                         // `fun_form` must be a function or potentially evaluate to one.
-                        if !fun_form.is_potentially(ExprTag::Fun) {
+                        if !fun_form.is_callable() {
                             dbg!("not potentially fun");
-                            Control::Error(expr, env)
+                            Control::Error(
+                                expr,
+                                env,
+                                EvalError::NotAFunction {
+                                    ptr_tag: fun_form.tag,
+                                },
+                            )
                         } else if args.is_nil() {
                             Control::Return(
                                 fun_form,
@@ -583,10 +664,11 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                             )
                         } else {
                             let (arg, more_args) = car_cdr_named!(ConsName::ExprCdr, &args)?;
-                            match more_args.tag {
-                                // (fn arg)
+                            let is_expanded = more_args == dummy_arg;
+                            if more_args.is_nil() || is_expanded {
+                                // (fn arg), or (fn arg . _) expanded from a multi-arg call below
                                 // Interpreting as call.
-                                ExprTag::Nil => Control::Return(
+                                Control::Return(
                                     fun_form,
                                     env,
                                     cont_witness.intern_named_cont(
@@ -596,33 +678,34 @@ fn reduce_with_witness_inner<F: LurkField, C: Coprocessor<F>>(
                                             unevaled_arg: arg,
                                             saved_env: env,
                                             continuation: cont,
+                                            expanded: is_expanded,
                                         },
                                     ),
-                                ),
-                                _ => {
-                                    // Interpreting as multi-arg call.
-                                    // (fn arg . more_args) => ((fn arg) . more_args)
-                                    let nil = lurk_sym_ptr!(store, nil);
-                                    let expanded_inner0 = cons_witness.cons_named(
-                                        ConsName::ExpandedInner0,
-                                        store,
-                                        arg,
-                                        nil,
-                                    );
-                                    let expanded_inner = cons_witness.cons_named(
-                                        ConsName::ExpandedInner,
-                                        store,
-                                        fun_form,
-                                        expanded_inner0,
-                                    );
-                                    let expanded = cons_witness.cons_named(
-                                        ConsName::FunExpanded,
-                                        store,
-                                        expanded_inner,
-                                        more_args,
-                                    );
-                                    Control::Return(expanded, env, cont)
-                                }
+                                )
+                            } else {
+                                // Interpreting as multi-arg call.
+                                // (fn arg . more_args) => ((fn arg . _) . more_args)
+                                // The dummy marks the inner call as expanded, so that a
+                                // variadic FN knows the remaining arguments are its own.
+                                let expanded_inner0 = cons_witness.cons_named(
+                                    ConsName::ExpandedInner0,
+                                    store,
+                                    arg,
+                                    dummy_arg,
+                                );
+                                let expanded_inner = cons_witness.cons_named(
+                                    ConsName::ExpandedInner,
+                                    store,
+                                    fun_form,
+                                    expanded_inner0,
+                                );
+                                let expanded = cons_witness.cons_named(
+                                    ConsName::FunExpanded,
+                                    store,
+                                    expanded_inner,
+                                    more_args,
+                                );
+                                Control::Return(expanded, env, cont)
                             }
                         }
                     }
@@ -714,12 +797,12 @@ fn apply_continuation<F: LurkField>(
                     Expression::Fun(arg, body, closed_env) => {
                         if arg == c.dummy.ptr() {
                             if body.is_nil() {
-                                Control::Error(result, env)
+                                Control::Error(result, env, EvalError::Other { expr: result })
                             } else {
                                 let (body_form, end) =
                                     cons_witness.car_cdr_named(ConsName::FunBody, store, &body)?;
                                 if !end.is_nil() {
-                                    Control::Error(result, env)
+                                    Control::Error(result, env, EvalError::Other { expr: result })
                                 } else {
                                     let cont = make_tail_continuation(
                                         saved_env,
@@ -739,7 +822,13 @@ fn apply_continuation<F: LurkField>(
                     }
                     _ => unreachable!(),
                 }, // Bad function
-                _ => Control::Error(result, env),
+                _ => Control::Error(
+                    result,
+                    env,
+                    EvalError::NotAFunction {
+                        ptr_tag: result.tag,
+                    },
+                ),
             },
             _ => unreachable!(),
         },
@@ -752,6 +841,7 @@ fn apply_continuation<F: LurkField>(
                     unevaled_arg,
                     saved_env,
                     continuation,
+                    expanded,
                 } => {
                     let function = result;
                     let next_expr = unevaled_arg;
@@ -763,6 +853,7 @@ fn apply_continuation<F: LurkField>(
                             function,
                             saved_env,
                             continuation,
+                            expanded,
                         },
                     );
                     Control::Return(next_expr, env, newer_cont)
@@ -771,7 +862,13 @@ fn apply_continuation<F: LurkField>(
             },
             _ => {
                 // Bad function
-                Control::Error(result, env)
+                Control::Error(
+                    result,
+                    env,
+                    EvalError::NotAFunction {
+                        ptr_tag: result.tag,
+                    },
+                )
             }
         },
         ContTag::Call2 => match cont_witness
@@ -782,6 +879,7 @@ fn apply_continuation<F: LurkField>(
                 function,
                 saved_env,
                 continuation,
+                expanded,
             } => match function.tag {
                 ExprTag::Fun => match store
                     .fetch(&function)
@@ -789,16 +887,26 @@ fn apply_continuation<F: LurkField>(
                 {
                     Expression::Fun(arg, body, closed_env) => {
                         if arg == c.dummy.ptr() {
-                            return Ok(Control::Error(result, env));
+                            return Ok(Control::Error(
+                                result,
+                                env,
+                                EvalError::Arity {
+                                    expected: 0,
+                                    got: 1,
+                                },
+                            ));
                         }
                         if body.is_nil() {
-                            Control::Error(result, env)
+                            Control::Error(result, env, EvalError::Other { expr: function })
                         } else {
                             let (body_form, end) =
                                 cons_witness.car_cdr_named(ConsName::FunBody, store, &body)?;
+                            // A variadic function's body is the form (LAMBDA (REST) . STUFF) rather
+                            // than a list of one form.
+                            let is_variadic = body_form == c.lambda.ptr() && !end.is_nil();
 
-                            if !end.is_nil() {
-                                Control::Error(result, env)
+                            if !end.is_nil() && !is_variadic {
+                                Control::Error(result, env, EvalError::Other { expr: function })
                             } else {
                                 let newer_env = cons_witness.extend_named(
                                     ConsName::ClosedEnv,
@@ -807,13 +915,46 @@ fn apply_continuation<F: LurkField>(
                                     result,
                                     store,
                                 );
-                                let cont = make_tail_continuation(
-                                    saved_env,
-                                    continuation,
-                                    store,
-                                    cont_witness,
-                                );
-                                Control::Return(body_form, newer_env, cont)
+                                if is_variadic {
+                                    // The body evaluates to a function of the remaining arguments
+                                    // as a list. If this call was expanded from a multi-arg call,
+                                    // its continuation is a Call of the next one, and the rest
+                                    // gatherer is applied to that function instead, returning a
+                                    // variadic function which collects the remaining arguments.
+                                    // Otherwise, the function is applied to NIL. Arguments applied
+                                    // separately, as in (((LAMBDA (A . REST) F) 1) 2), belong to
+                                    // the result.
+                                    let newer_cont =
+                                        if expanded && continuation.tag == ContTag::Call {
+                                            Continuation::Call2 {
+                                                function: c.rest_gatherer.ptr(),
+                                                saved_env,
+                                                continuation,
+                                                expanded: false,
+                                            }
+                                        } else {
+                                            Continuation::Call {
+                                                unevaled_arg: lurk_sym_ptr!(store, nil),
+                                                saved_env,
+                                                continuation,
+                                                expanded: false,
+                                            }
+                                        };
+                                    let cont = cont_witness.intern_named_cont(
+                                        ContName::NewerCont2,
+                                        store,
+                                        newer_cont,
+                                    );
+                                    Control::Return(body, newer_env, cont)
+                                } else {
+                                    let cont = make_tail_continuation(
+                                        saved_env,
+                                        continuation,
+                                        store,
+                                        cont_witness,
+                                    );
+                                    Control::Return(body_form, newer_env, cont)
+                                }
                             }
                         }
                     }
@@ -821,7 +962,13 @@ fn apply_continuation<F: LurkField>(
                 },
                 _ => {
                     // Call2 continuation contains a non-function
-                    return Ok(Control::Error(result, env));
+                    return Ok(Control::Error(
+                        result,
+                        env,
+                        EvalError::NotAFunction {
+                            ptr_tag: function.tag,
+                        },
+                    ));
                 }
             },
             _ => unreachable!(),
@@ -875,14 +1022,30 @@ fn apply_continuation<F: LurkField>(
                         match cons_witness.car_cdr_mut_named(ConsName::UnopConsLike, store, &result)
                         {
                             Ok((car, _)) => car,
-                            Err(_) => return Ok(Control::Error(result, env)),
+                            Err(_) => {
+                                return Ok(Control::Error(
+                                    result,
+                                    env,
+                                    EvalError::NotACons {
+                                        ptr_tag: result.tag,
+                                    },
+                                ))
+                            }
                         }
                     }
                     Op1::Cdr => {
                         match cons_witness.car_cdr_mut_named(ConsName::UnopConsLike, store, &result)
                         {
                             Ok((_, cdr)) => cdr,
-                            Err(_) => return Ok(Control::Error(result, env)),
+                            Err(_) => {
+                                return Ok(Control::Error(
+                                    result,
+                                    env,
+                                    EvalError::NotACons {
+                                        ptr_tag: result.tag,
+                                    },
+                                ))
+                            }
                         }
                     }
                     Op1::Atom => match result.tag {
@@ -903,11 +1066,23 @@ fn apply_continuation<F: LurkField>(
                     }
                     Op1::Open => match result.tag {
                         ExprTag::Num | ExprTag::Comm => store.open_mut(result)?.1,
-                        _ => return Ok(Control::Error(result, env)),
+                        _ => {
+                            return Ok(Control::Error(
+                                result,
+                                env,
+                                EvalError::Other { expr: result },
+                            ))
+                        }
                     },
                     Op1::Secret => match result.tag {
                         ExprTag::Num | ExprTag::Comm => store.secret_mut(result)?,
-                        _ => return Ok(Control::Error(result, env)),
+                        _ => {
+                            return Ok(Control::Error(
+                                result,
+                                env,
+                                EvalError::Other { expr: result },
+                            ))
+                        }
                     },
                     Op1::Commit => store.commit(result),
                     Op1::Num => match result.tag {
@@ -917,7 +1092,13 @@ fn apply_continuation<F: LurkField>(
                                 .ok_or_else(|| store::Error("expr hash missing".into()))?;
                             store.intern_num(crate::Num::Scalar::<F>(*z_ptr.value()))
                         }
-                        _ => return Ok(Control::Error(result, env)),
+                        _ => {
+                            return Ok(Control::Error(
+                                result,
+                                env,
+                                EvalError::Other { expr: result },
+                            ))
+                        }
                     },
                     Op1::U64 => match result.tag {
                         ExprTag::Num => {
@@ -928,7 +1109,13 @@ fn apply_continuation<F: LurkField>(
                             store.intern_u64(z_ptr.value().to_u64_unchecked())
                         }
                         ExprTag::U64 => result,
-                        _ => return Ok(Control::Error(result, env)),
+                        _ => {
+                            return Ok(Control::Error(
+                                result,
+                                env,
+                                EvalError::Other { expr: result },
+                            ))
+                        }
                     },
                     Op1::Comm => match result.tag {
                         ExprTag::Num | ExprTag::Comm => {
@@ -937,7 +1124,13 @@ fn apply_continuation<F: LurkField>(
                                 .ok_or_else(|| store::Error("expr hash missing".into()))?;
                             store.intern_maybe_opaque_comm(*z_ptr.value())
                         }
-                        _ => return Ok(Control::Error(result, env)),
+                        _ => {
+                            return Ok(Control::Error(
+                                result,
+                                env,
+                                EvalError::Other { expr: result },
+                            ))
+                        }
                     },
                     Op1::Char => match result.tag {
                         ExprTag::Num | ExprTag::Char => {
@@ -946,7 +1139,13 @@ fn apply_continuation<F: LurkField>(
                                 .ok_or_else(|| store::Error("expr hash missing".into()))?;
                             Ptr::index(ExprTag::Char, z_ptr.value().to_u32_unchecked() as usize)
                         }
-                        _ => return Ok(Control::Error(result, env)),
+                        _ => {
+                            return Ok(Control::Error(
+                                result,
+                                env,
+                                EvalError::Other { expr: result },
+                            ))
+                        }
                     },
                     Op1::Eval => {
                         return Ok(Control::Return(result, empty_sym_env(store), continuation));
@@ -978,7 +1177,11 @@ fn apply_continuation<F: LurkField>(
                         Control::Return(begin_again, saved_env, continuation)
                     }
                 } else if !rest.is_nil() {
-                    return Ok(Control::Error(result, env));
+                    return Ok(Control::Error(
+                        result,
+                        env,
+                        EvalError::Other { expr: result },
+                    ));
                 } else {
                     Control::Return(
                         arg2,
@@ -1033,7 +1236,11 @@ fn apply_continuation<F: LurkField>(
                             let mut tmp = a;
                             let b_is_zero: bool = b.is_zero();
                             if b_is_zero {
-                                Err(Control::Error(result, env))
+                                Err(Control::Error(
+                                    result,
+                                    env,
+                                    EvalError::DivisionByZero { operator },
+                                ))
                             } else {
                                 tmp /= b;
                                 Ok(store.intern_num(tmp))
@@ -1041,7 +1248,11 @@ fn apply_continuation<F: LurkField>(
                         }
                         Op2::Modulo => {
                             // Modulo requires both args be UInt.
-                            Err(Control::Error(result, env))
+                            Err(Control::Error(
+                                result,
+                                env,
+                                EvalError::Other { expr: result },
+                            ))
                         }
                         Op2::Equal | Op2::NumEqual => Ok(store.as_lurk_boolean(a == b)),
                         Op2::Less => Ok(store.as_lurk_boolean(a < b)),
@@ -1080,6 +1291,16 @@ fn apply_continuation<F: LurkField>(
                         (Expression::Num(a), _) if operator == Op2::Hide => {
                             store.hide(a.into_scalar(), arg2)
                         }
+                        (Expression::UInt(a), Expression::UInt(b)) if operator.is_bitwise() => {
+                            match operator {
+                                Op2::BitAnd => store.intern_u64((a & b).into()),
+                                Op2::BitOr => store.intern_u64((a | b).into()),
+                                Op2::BitXor => store.intern_u64((a ^ b).into()),
+                                Op2::ShiftLeft => store.intern_u64((a << b).into()),
+                                Op2::ShiftRight => store.intern_u64((a >> b).into()),
+                                _ => unreachable!(),
+                            }
+                        }
                         (Expression::UInt(a), Expression::UInt(b)) if operator.is_numeric() => {
                             match operator {
                                 Op2::Sum => store.intern_u64((a + b).into()),
@@ -1087,10 +1308,10 @@ fn apply_continuation<F: LurkField>(
                                 Op2::Product => store.intern_u64((a * b).into()),
                                 Op2::Quotient => {
                                     if b.is_zero() {
-                                        return Ok(Control::Return(
+                                        return Ok(Control::Error(
                                             result,
                                             env,
-                                            store.intern_cont_error(),
+                                            EvalError::DivisionByZero { operator },
                                         ));
                                     } else {
                                         store.intern_u64((a / b).into())
@@ -1098,10 +1319,10 @@ fn apply_continuation<F: LurkField>(
                                 }
                                 Op2::Modulo => {
                                     if b.is_zero() {
-                                        return Ok(Control::Return(
+                                        return Ok(Control::Error(
                                             result,
                                             env,
-                                            store.intern_cont_error(),
+                                            EvalError::DivisionByZero { operator },
                                         ));
                                     } else {
                                         store.intern_u64((a % b).into())
@@ -1134,7 +1355,11 @@ fn apply_continuation<F: LurkField>(
                             cons_witness.strcons_named(ConsName::TheCons, store, evaled_arg, arg2)
                         }
                         _ => {
-                            return Ok(Control::Return(result, env, store.intern_cont_error()));
+                            return Ok(Control::Error(
+                                result,
+                                env,
+                                EvalError::Other { expr: result },
+                            ));
                         }
                     },
                 };
@@ -1185,7 +1410,7 @@ fn apply_continuation<F: LurkField>(
                 let (arg2, end) =
                     cons_witness.car_cdr_named(ConsName::UnevaledArgsCdr, store, &more)?;
                 if !end.is_nil() {
-                    Control::Return(arg1, env, store.intern_cont_error())
+                    Control::Error(arg1, env, EvalError::Other { expr: arg1 })
                 } else {
                     Control::Return(
                         if condition.is_nil() { arg2 } else { arg1 },
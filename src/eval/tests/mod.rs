@@ -8,7 +8,7 @@ use crate::eval::reduction::{extend, lookup, reduce};
 use crate::num::Num;
 use crate::package::Package;
 use crate::state::{user_sym, State};
-use crate::tag::{ExprTag, Op, Op1, Op2};
+use crate::tag::{ContTag, ExprTag, Op, Op1, Op2};
 
 use lurk_macros::{let_store, lurk, Coproc};
 use pasta_curves::pallas::Scalar as Fr;
@@ -1510,6 +1510,97 @@ fn begin_current_env1() {
     }
 }
 
+#[test]
+fn begin_evaluates_every_expression() {
+    let s = &mut Store::<Fr>::default();
+    let expr = s
+        .read("(begin (emit 1) (emit (+ 1 1)) (let ((x (emit 3))) 4) (emit 5) 6)")
+        .unwrap();
+    let env = empty_sym_env(s);
+    let lang = Lang::<Fr, Coproc<Fr>>::new();
+    let (io, _, emitted) = Evaluator::new(expr, env, s, 1000, &lang).eval().unwrap();
+
+    // only the last value is returned, but each expression before it is evaluated in order
+    assert_eq!(s.num(6), io.expr);
+    assert_eq!(s.intern_cont_terminal(), io.cont);
+    let expected: Vec<_> = [1u64, 2, 3, 5].into_iter().map(|n| s.num(n)).collect();
+    assert_eq!(expected, emitted);
+
+    let s = &mut Store::<Fr>::default();
+    let expected = s.num(3);
+    assert_eq!(
+        expected,
+        eval_str::<Fr, Coproc<Fr>>(s, "(begin (begin 1 2) 3)").unwrap()
+    );
+    assert!(eval_str::<Fr, Coproc<Fr>>(s, "(begin 1 (car 2) 3)").is_err());
+}
+
+#[test]
+fn evaluator_records_emitted() {
+    let s = &mut Store::<Fr>::default();
+    let expr = s.read("(begin (emit 1) (emit 2) 3)").unwrap();
+    let env = empty_sym_env(s);
+    let lang = Lang::<Fr, Coproc<Fr>>::new();
+    let (io, emitted) = {
+        let mut evaluator = Evaluator::new(expr, env, s, 1000, &lang);
+        assert!(evaluator.emitted().is_empty());
+        let (io, _, emitted) = evaluator.eval().unwrap();
+        assert_eq!(emitted, evaluator.emitted());
+
+        // evaluating again gives the same values, rather than adding to them
+        let (again, _, _) = evaluator.eval_checked().unwrap();
+        assert_eq!(io, again);
+        assert_eq!(emitted, evaluator.emitted());
+        (io, emitted)
+    };
+
+    assert_eq!(s.num(3), io.expr);
+    assert_eq!(vec![s.num(1), s.num(2)], emitted);
+}
+
+#[test]
+fn test_quasiquote() {
+    let s = &mut Store::<Fr>::default();
+    for (quasiquoted, explicit) in [
+        ("`(a b c)", "'(a b c)"),
+        ("`,(+ 1 2)", "3"),
+        (
+            "(let ((b 2)) `(a ,b c))",
+            "(cons 'a (cons 2 (cons 'c nil)))",
+        ),
+        (
+            "(let ((b 2)) `(a (,b) . ,b))",
+            "(cons 'a (cons (cons 2 nil) 2))",
+        ),
+        ("(let ((c '(3 4))) `(a ,@c 5))", "'(a 3 4 5)"),
+        ("(let ((c '(3 4))) `(a ,@c))", "'(a 3 4)"),
+        ("(let ((c nil)) `(a ,@c b))", "'(a b)"),
+        (
+            "(let ((x 1) (y '(2 3))) `((,x . ,y) ,@y ,@y))",
+            "'((1 2 3) 2 3 2 3)",
+        ),
+        ("(let ((x 1)) `(a '(b ,x)))", "'(a (quote (b 1)))"),
+        // splicing doesn't capture the bindings of the spliced expression
+        ("(let ((append 1) (a '(2))) `(,@a ,append))", "'(2 1)"),
+        // the inner quasiquotation is expanded first, leaving `,x` to the outer one
+        (
+            "(let ((x 1) (y 2)) (eval `(let ((y 3)) `(,y ,(+ y ,x)))))",
+            "'(3 4)",
+        ),
+        (
+            "(let ((x 1)) `(a `(b ,(c ,x))))",
+            "'(a (cons (quote b) (cons (c 1) nil)))",
+        ),
+    ] {
+        let expected = eval_str::<Fr, Coproc<Fr>>(s, explicit).unwrap();
+        let actual = eval_str::<Fr, Coproc<Fr>>(s, quasiquoted).unwrap();
+        assert!(s.ptr_eq(&expected, &actual).unwrap(), "{quasiquoted}");
+    }
+
+    // an unquote outside of a quasiquotation is an error
+    assert!(eval_str::<Fr, Coproc<Fr>>(s, "(let ((x 1)) ,x)").is_err());
+}
+
 #[test]
 fn hide_open() {
     let s = &mut Store::<Fr>::default();
@@ -2149,6 +2240,20 @@ fn test_u64_add() {
     test_aux::<Coproc<Fr>>(s, expr2, Some(res), None, Some(terminal), None, 6, None);
 }
 
+#[test]
+fn test_u64_add_wraps_unlike_num() {
+    let s = &mut Store::<Fr>::default();
+
+    let expr = "(+ 18446744073709551615u64 1u64)";
+    let expr2 = "(+ 18446744073709551615 1)";
+    let res = s.uint64(0);
+    let res2 = s.num(Num::Scalar(Fr::from(u64::MAX) + Fr::from(1)));
+    let terminal = s.get_cont_terminal();
+
+    test_aux::<Coproc<Fr>>(s, expr, Some(res), None, Some(terminal), None, 3, None);
+    test_aux::<Coproc<Fr>>(s, expr2, Some(res2), None, Some(terminal), None, 3, None);
+}
+
 #[test]
 fn test_u64_sub() {
     let s = &mut Store::<Fr>::default();
@@ -2221,6 +2326,72 @@ fn test_num_mod() {
     test_aux::<Coproc<Fr>>(s, expr3, None, None, Some(error), None, 3, None);
 }
 
+#[test]
+fn test_u64_bitwise() {
+    let s = &mut Store::<Fr>::default();
+    let terminal = s.get_cont_terminal();
+
+    let values = [0, 1, 0xf0f0, 0xdead_beef_0123_4567, u64::MAX];
+    for a in values {
+        for b in values {
+            for (op, expected) in [("bit-and", a & b), ("bit-or", a | b), ("bit-xor", a ^ b)] {
+                let expr = format!("({op} {a}u64 {b}u64)");
+                let res = s.uint64(expected);
+                test_aux::<Coproc<Fr>>(s, &expr, Some(res), None, Some(terminal), None, 3, None);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_u64_shifts() {
+    let s = &mut Store::<Fr>::default();
+    let terminal = s.get_cont_terminal();
+
+    let values = [0, 1, 0xdead_beef_0123_4567, u64::MAX];
+    let amounts = [0, 1, 7, 32, 63, 64, 65, 1 << 32, u64::MAX];
+    for a in values {
+        for b in amounts {
+            // Shifting by 64 or more bits shifts every bit out.
+            let (shl, shr) = if b < 64 { (a << b, a >> b) } else { (0, 0) };
+            for (op, expected) in [("shl", shl), ("shr", shr)] {
+                let expr = format!("({op} {a}u64 {b}u64)");
+                let res = s.uint64(expected);
+                test_aux::<Coproc<Fr>>(s, &expr, Some(res), None, Some(terminal), None, 3, None);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_num_bitwise() {
+    let s = &mut Store::<Fr>::default();
+    let error = s.get_cont_error();
+
+    for op in ["bit-and", "bit-or", "bit-xor", "shl", "shr"] {
+        for expr in [
+            format!("({op} 6 3)"),
+            format!("({op} 6 3u64)"),
+            format!("({op} 6u64 3)"),
+        ] {
+            test_aux::<Coproc<Fr>>(s, &expr, None, None, Some(error), None, 3, None);
+        }
+    }
+}
+
+#[test]
+fn test_and_or_are_user_symbols() {
+    let s = &mut Store::<Fr>::default();
+    let terminal = s.get_cont_terminal();
+
+    // The bitwise operators are named `bit-and`, `bit-or` and `bit-xor`, leaving these free.
+    for name in ["and", "or", "xor"] {
+        let expr = format!("(let (({name} (lambda (x) x))) ({name} 5))");
+        let res = s.num(5);
+        test_aux::<Coproc<Fr>>(s, &expr, Some(res), None, Some(terminal), None, 6, None);
+    }
+}
+
 #[test]
 fn test_u64_comp() {
     let s = &mut Store::<Fr>::default();
@@ -2262,6 +2433,40 @@ fn test_u64_comp() {
     test_aux::<Coproc<Fr>>(s, expr12, Some(nil), None, Some(terminal), None, 3, None);
 }
 
+#[test]
+fn test_comparisons_at_u64_max() {
+    let s = &mut Store::<Fr>::default();
+    let t = lurk_sym_ptr!(s, t);
+    let nil = lurk_sym_ptr!(s, nil);
+    let max = "18446744073709551615";
+    for (src, expected) in [
+        (format!("(< {max}u64 {max}u64)"), nil),
+        (format!("(<= {max}u64 {max}u64)"), t),
+        (format!("(> {max}u64 {max}u64)"), nil),
+        (format!("(>= {max}u64 {max}u64)"), t),
+        (format!("(< 18446744073709551614u64 {max}u64)"), t),
+        (format!("(> {max}u64 0u64)"), t),
+        (format!("(<= {max}u64 0u64)"), nil),
+        // u64 arithmetic wraps, so the successor of max is the least u64
+        (format!("(< (+ {max}u64 1u64) {max}u64)"), t),
+        // field elements past the u64 range are still ordered like integers
+        (format!("(< {max} (+ {max} 1))"), t),
+        (format!("(> (+ {max} 1) {max})"), t),
+        (format!("(>= {max} (+ {max} 1))"), nil),
+        (format!("(< 0 {max}u64)"), t),
+        (format!("(= {max} {max}u64)"), t),
+        // negative field elements are less than every u64
+        ("(< (- 0 1) 0)".to_string(), t),
+        (format!("(> (- 0 1) {max})"), nil),
+    ] {
+        assert_eq!(
+            expected,
+            eval_str::<Fr, Coproc<Fr>>(s, &src).unwrap(),
+            "{src}"
+        );
+    }
+}
+
 #[test]
 fn test_u64_conversion() {
     let s = &mut Store::<Fr>::default();
@@ -2618,12 +2823,544 @@ fn test_eval_non_symbol_binding_error() {
     test("#\\x");
 }
 
+#[test]
+fn test_trace() {
+    let s = &mut Store::<Fr>::default();
+    let lang = Lang::<Fr, Coproc<Fr>>::new();
+    let expr = s
+        .read("(letrec ((f (lambda (n) (if (= n 0) 0 (f (- n 1)))))) (f 2))")
+        .unwrap();
+    let env = empty_sym_env(s);
+
+    let (output, iterations, _) = Evaluator::new(expr, env, s, 100, &lang).eval().unwrap();
+    let frames = Evaluator::new(expr, env, s, 100, &lang).trace().unwrap();
+
+    assert_eq!(iterations, frames.len());
+    assert!(frames.windows(2).all(|w| w[0].precedes(&w[1])));
+    assert_eq!(expr, frames[0].input.expr);
+    assert_eq!(output, frames[frames.len() - 1].output);
+    // the binding of `f` is evaluated under the `letrec` continuation
+    assert_eq!(ContTag::LetRec, frames[1].input.cont.tag);
+    assert!(frames[1].pretty(s).starts_with("1 [letrec#]: "));
+}
+
+#[test]
+fn test_restore_io_state() {
+    let s = &mut Store::<Fr>::default();
+    let lang = Lang::<Fr, Coproc<Fr>>::new();
+    let expr = s
+        .read("(letrec ((f (lambda (n) (if (= n 0) 0 (f (- n 1)))))) (begin (emit (f 2)) 7))")
+        .unwrap();
+    let env = empty_sym_env(s);
+    let zero = s.num(0);
+
+    let mut evaluator = Evaluator::new(expr, env, s, 100, &lang);
+    let initial = evaluator.io_state();
+    let frames = evaluator.trace().unwrap();
+    let steps = |frames: &[Frame<IO<Fr>, Witness<Fr>, Coproc<Fr>>]| {
+        frames
+            .iter()
+            .map(|frame| (frame.input, frame.output, frame.witness))
+            .collect::<Vec<_>>()
+    };
+
+    // restoring the state at step 3 replays the remaining frames
+    let saved = frames[3].input;
+    evaluator.set_io_state(saved);
+    assert_eq!(saved, evaluator.io_state());
+    let replayed = evaluator.trace().unwrap();
+    assert_eq!(steps(&frames[3..]), steps(&replayed));
+    let (output, iterations, emitted) = evaluator.eval().unwrap();
+    assert_eq!(frames[frames.len() - 1].output, output);
+    assert_eq!(frames.len() - 3, iterations);
+    assert_eq!(vec![zero], emitted);
+
+    // and restoring the initial state replays them all
+    evaluator.set_io_state(initial);
+    assert_eq!(steps(&frames), steps(&evaluator.trace().unwrap()));
+}
+
+#[test]
+fn test_eval_with_limit() {
+    let s = &mut Store::<Fr>::default();
+    let lang = Lang::<Fr, Coproc<Fr>>::new();
+    let env = empty_sym_env(s);
+
+    let expr = s
+        .read("(letrec ((loop (lambda () (loop)))) (loop))")
+        .unwrap();
+    let outcome = Evaluator::new(expr, env, s, 50, &lang)
+        .eval_with_limit()
+        .unwrap();
+    let EvalOutcome::Incomplete {
+        last_frame,
+        steps_taken,
+    } = outcome
+    else {
+        panic!("expected an incomplete outcome, got {outcome:?}");
+    };
+    assert_eq!(50, steps_taken);
+    assert!(matches!(
+        Evaluator::new(expr, env, s, 50, &lang).resume(last_frame),
+        Ok(EvalOutcome::Incomplete {
+            steps_taken: 50,
+            ..
+        })
+    ));
+
+    // `(+ 1 2)` takes three steps
+    let expr = s.read("(+ 1 2)").unwrap();
+    let three = s.num(3);
+    let outcome = Evaluator::new(expr, env, s, 3, &lang)
+        .eval_with_limit()
+        .unwrap();
+    assert!(matches!(outcome, EvalOutcome::Complete(io) if io.expr == three));
+
+    let outcome = Evaluator::new(expr, env, s, 1, &lang)
+        .eval_with_limit()
+        .unwrap();
+    let EvalOutcome::Incomplete {
+        last_frame,
+        steps_taken: 1,
+    } = outcome
+    else {
+        panic!("expected an incomplete outcome, got {outcome:?}");
+    };
+    let outcome = Evaluator::new(expr, env, s, 10, &lang)
+        .resume(last_frame)
+        .unwrap();
+    assert!(matches!(outcome, EvalOutcome::Complete(io) if io.expr == three));
+}
+
+#[test]
+fn test_eval_many() {
+    let s = &mut Store::<Fr>::default();
+    let lang = Lang::<Fr, Coproc<Fr>>::new();
+    let programs = [
+        "(let ((x 40)) (+ x 2))",
+        "(+ x 2)",
+        "(letrec ((y 1)) (cons y y))",
+    ]
+    .map(|src| s.read(src).unwrap());
+    let (forty_two, one) = (s.num(42), s.num(1));
+    let pair = s.cons(one, one);
+    let x = s.user_sym("x");
+    let nil = lurk_sym_ptr!(s, nil);
+
+    let results = Evaluator::new(nil, nil, s, 100, &lang).eval_many(&programs);
+    assert_eq!(3, results.len());
+    assert!(matches!(results[0], Ok(ptr) if ptr == forty_two));
+    // the first program's binding of `x` doesn't leak into the second
+    assert!(matches!(results[1], Err(EvalError::Unbound { sym }) if sym == x));
+    assert!(matches!(results[2], Ok(ptr) if ptr == pair));
+
+    // running the programs again reuses what the first run interned
+    let conses = s.cons_store.len();
+    let again = Evaluator::new(nil, nil, s, 100, &lang).eval_many(&programs);
+    assert!(matches!(again[..], [Ok(a), Err(_), Ok(c)] if a == forty_two && c == pair));
+    assert_eq!(conses, s.cons_store.len());
+    assert!(Evaluator::new(nil, nil, s, 100, &lang)
+        .eval_many(&[])
+        .is_empty());
+}
+
+#[test]
+fn test_eval_deadline() {
+    let s = &mut Store::<Fr>::default();
+    let lang = Lang::<Fr, Coproc<Fr>>::new();
+    let env = empty_sym_env(s);
+
+    let expr = s
+        .read("(letrec ((loop (lambda (n) (loop (+ n 1))))) (loop 0))")
+        .unwrap();
+    let start = std::time::Instant::now();
+    let mut evaluator = Evaluator::new(expr, env, s, usize::MAX, &lang);
+    evaluator.set_clock_interval(100);
+    let outcome = evaluator
+        .eval_deadline(start + std::time::Duration::from_millis(50))
+        .unwrap();
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    let EvalOutcome::TimedOut {
+        last_frame,
+        steps_taken,
+    } = outcome
+    else {
+        panic!("expected a timed out outcome, got {outcome:?}");
+    };
+    assert!(steps_taken > 0);
+    assert_eq!(0, steps_taken % 100);
+    // a timed out evaluation resumes like an incomplete one
+    assert!(matches!(
+        Evaluator::new(expr, env, s, 10, &lang).resume(last_frame),
+        Ok(EvalOutcome::Incomplete {
+            steps_taken: 10,
+            ..
+        })
+    ));
+
+    // the step limit still applies, and an evaluation finishing in time completes
+    let far = std::time::Instant::now() + std::time::Duration::from_secs(60);
+    assert!(matches!(
+        Evaluator::new(expr, env, s, 20, &lang).eval_deadline(far),
+        Ok(EvalOutcome::Incomplete {
+            steps_taken: 20,
+            ..
+        })
+    ));
+    let expr = s.read("(+ 1 2)").unwrap();
+    let three = s.num(3);
+    let outcome = Evaluator::new(expr, env, s, 10, &lang)
+        .eval_deadline(far)
+        .unwrap();
+    assert!(matches!(outcome, EvalOutcome::Complete(io) if io.expr == three));
+
+    // a deadline that has already passed stops before the first step
+    let outcome = Evaluator::new(expr, env, s, 10, &lang)
+        .eval_deadline(start)
+        .unwrap();
+    assert!(matches!(
+        outcome,
+        EvalOutcome::TimedOut { steps_taken: 0, .. }
+    ));
+}
+
+#[test]
+fn test_resume_from_checkpoint() {
+    let s = &mut Store::<Fr>::default();
+    let lang = Lang::<Fr, Coproc<Fr>>::new();
+    let env = empty_sym_env(s);
+    let expr = s
+        .read("(letrec ((sum (lambda (n) (if (= n 0) 0 (+ n (sum (- n 1))))))) (sum 60))")
+        .unwrap();
+
+    let (output, iterations, _) = Evaluator::new(expr, env, s, 10000, &lang).eval().unwrap();
+    assert!(iterations > 1000);
+
+    let EvalOutcome::Incomplete {
+        last_frame: checkpoint,
+        steps_taken: 400,
+    } = Evaluator::new(expr, env, s, 400, &lang)
+        .eval_with_limit()
+        .unwrap()
+    else {
+        panic!("expected to stop at step 400");
+    };
+    let resumed = Evaluator::new(expr, env, s, iterations - 400, &lang)
+        .resume(checkpoint)
+        .unwrap();
+    assert_eq!(EvalOutcome::Complete(output), resumed);
+
+    // resuming from a snapshot, as after a restart
+    {
+        let json = serde_json::to_string(&checkpoint).unwrap();
+        let mut snapshot = vec![];
+        s.dump(&mut snapshot).unwrap();
+
+        let s2 = &mut Store::<Fr>::load(snapshot.as_slice()).unwrap();
+        let checkpoint: IO<Fr> = serde_json::from_str(&json).unwrap();
+        let EvalOutcome::Complete(resumed) = Evaluator::new(expr, env, s2, 10000, &lang)
+            .resume(checkpoint)
+            .unwrap()
+        else {
+            panic!("expected the resumed evaluation to complete");
+        };
+        assert!(s.ptr_eq_with(&output.expr, s2, &resumed.expr).unwrap());
+    }
+}
+
+#[test]
+fn test_eval_checked_errors() {
+    let s = &mut Store::<Fr>::default();
+    let lang = Lang::<Fr, Coproc<Fr>>::new();
+    let env = empty_sym_env(s);
+    let eval_checked = |s: &mut Store<Fr>, src| {
+        let expr = s.read(src).unwrap();
+        Evaluator::new(expr, env, s, 100, &lang)
+            .eval_checked()
+            .unwrap_err()
+    };
+
+    let y = s.read("y").unwrap();
+    let err = eval_checked(s, "(let ((x 1)) y)");
+    assert!(matches!(err, EvalError::Unbound { sym } if sym == y));
+    assert_eq!(
+        "Unbound variable: y",
+        err.fmt_to_string(s, initial_lurk_state())
+    );
+    assert!(matches!(
+        eval_checked(s, "(1 2)"),
+        EvalError::NotAFunction {
+            ptr_tag: ExprTag::Num
+        }
+    ));
+    assert!(matches!(
+        eval_checked(s, "(let ((f (lambda () 1))) (f 2))"),
+        EvalError::Arity {
+            expected: 0,
+            got: 1
+        }
+    ));
+    assert!(matches!(
+        eval_checked(s, "(car 1)"),
+        EvalError::NotACons {
+            ptr_tag: ExprTag::Num
+        }
+    ));
+    assert!(matches!(
+        eval_checked(s, "(quote 1 2)"),
+        EvalError::Other { .. }
+    ));
+    for (src, op) in [
+        ("(/ 100u64 0u64)", Op2::Quotient),
+        ("(% 100u64 (- 3u64 3u64))", Op2::Modulo),
+        ("(/ 1 0)", Op2::Quotient),
+        ("(let ((zero 0)) (/ 1 zero))", Op2::Quotient),
+    ] {
+        assert!(
+            matches!(eval_checked(s, src), EvalError::DivisionByZero { operator } if operator == op),
+            "{src}"
+        );
+    }
+
+    let expr = s.read("(+ 1 2)").unwrap();
+    let (output, ..) = Evaluator::new(expr, env, s, 100, &lang)
+        .eval_checked()
+        .unwrap();
+    assert_eq!(s.num(3), output.expr);
+}
+
+#[test]
+fn test_division_by_zero_step() {
+    let s = &mut Store::<Fr>::default();
+    let lang = Lang::<Fr, Coproc<Fr>>::new();
+    let env = empty_sym_env(s);
+
+    for (src, op) in [("(% 7u64 0u64)", Op2::Modulo), ("(/ 7 0)", Op2::Quotient)] {
+        let expr = s.read(src).unwrap();
+        let mut io = Evaluator::new(expr, env, s, 100, &lang).initial();
+        // the step applying the `Binop2` continuation to the zero divisor raises the error
+        let cause = loop {
+            let (next, _, cause) = io.reduce_checked(s, &lang).unwrap();
+            if let Some(cause) = cause {
+                assert_eq!(ContTag::Binop2, io.cont.tag, "{src}");
+                assert_eq!(ContTag::Error, next.cont.tag, "{src}");
+                break cause;
+            }
+            io = next;
+        };
+        assert!(
+            matches!(cause, EvalError::DivisionByZero { operator } if operator == op),
+            "{src}"
+        );
+    }
+}
+
+#[test]
+fn test_strict_car_cdr() {
+    let s = &mut Store::<Fr>::default();
+    let lang = Lang::<Fr, Coproc<Fr>>::new();
+    let env = empty_sym_env(s);
+    let eval_checked = |s: &mut Store<Fr>, src, strict| {
+        let expr = s.read(src).unwrap();
+        let mut evaluator = Evaluator::new(expr, env, s, 100, &lang);
+        evaluator.set_strict_car_cdr(strict);
+        evaluator.eval_checked().map(|(io, ..)| io.expr)
+    };
+
+    // by default, `(car 5)` is an error, while `nil` and strings can be taken apart
+    assert!(matches!(
+        eval_checked(s, "(car 5)", false),
+        Err(EvalError::NotACons {
+            ptr_tag: ExprTag::Num
+        })
+    ));
+    let nil = lurk_sym_ptr!(s, nil);
+    assert_eq!(nil, eval_checked(s, "(cdr nil)", false).unwrap());
+    assert_eq!(
+        s.intern_char('a'),
+        eval_checked(s, "(car \"ab\")", false).unwrap()
+    );
+
+    for (src, tag) in [
+        ("(car 5)", ExprTag::Num),
+        ("(cdr 5u64)", ExprTag::U64),
+        ("(let ((x 'x)) (car x))", ExprTag::Sym),
+        ("(cdr nil)", ExprTag::Nil),
+        ("(car (cdr '(1)))", ExprTag::Nil),
+        ("(car \"ab\")", ExprTag::Str),
+    ] {
+        assert!(
+            matches!(eval_checked(s, src, true), Err(EvalError::NotACons { ptr_tag }) if ptr_tag == tag),
+            "{src}"
+        );
+    }
+    assert_eq!(s.num(1), eval_checked(s, "(car '(1 . 2))", true).unwrap());
+    assert_eq!(s.num(2), eval_checked(s, "(cdr (cons 1 2))", true).unwrap());
+    assert!(matches!(
+        eval_checked(s, "(car y)", true),
+        Err(EvalError::Unbound { .. })
+    ));
+}
+
+#[test]
+fn test_tail_call_continuation_depth() {
+    let s = &mut Store::<Fr>::default();
+    let lang = Lang::<Fr, Coproc<Fr>>::new();
+    let env = empty_sym_env(s);
+
+    // returns the number of steps, the maximum continuation depth, and whether any step was in
+    // tail position
+    let measure = |s: &mut Store<Fr>, src: &str| {
+        let expr = s.read(src).unwrap();
+        let frames = Evaluator::new(expr, env, s, 10000, &lang).trace().unwrap();
+        let max_depth = frames
+            .iter()
+            .map(|frame| s.cont_depth(&frame.input.cont).unwrap())
+            .max()
+            .unwrap();
+        let any_tail = frames.iter().any(|frame| frame.input.cont.is_tail());
+        (frames.len(), max_depth, any_tail)
+    };
+    let countdown = |n| format!("(letrec ((f (lambda (n) (if (= n 0) 0 (f (- n 1)))))) (f {n}))");
+    let sum = |n| format!("(letrec ((f (lambda (n) (if (= n 0) 0 (+ n (f (- n 1))))))) (f {n}))");
+
+    let (tail_steps_5, tail_depth_5, any_tail) = measure(s, &countdown(5));
+    let (tail_steps_20, tail_depth_20, _) = measure(s, &countdown(20));
+    assert!(any_tail);
+    assert_eq!(tail_depth_5, tail_depth_20);
+
+    let (steps_5, depth_5, _) = measure(s, &sum(5));
+    let (steps_20, depth_20, _) = measure(s, &sum(20));
+    assert!(depth_20 > depth_5 + 15);
+    assert!(tail_steps_5 < steps_5);
+    assert!(tail_steps_20 < steps_20);
+}
+
+#[test]
+fn test_eval_str() {
+    let s = &mut Store::<Fr>::default();
+
+    let three = s.num(3);
+    assert_eq!(three, eval_str::<Fr, Coproc<Fr>>(s, "(+ 1 2)").unwrap());
+    assert!(matches!(
+        eval_str::<Fr, Coproc<Fr>>(s, "(+ 1"),
+        Err(LurkError::Parse(_))
+    ));
+    let x = s.read("x").unwrap();
+    assert!(matches!(
+        eval_str::<Fr, Coproc<Fr>>(s, "x"),
+        Err(LurkError::Eval(EvalError::Unbound { sym })) if sym == x
+    ));
+}
+
+#[test]
+fn test_run_on_field() {
+    let src = "(let ((xs (cons 1 (cons 2 nil)))) (cons (car xs) \"done\"))";
+    let pallas = run_on_field(FieldChoice::Pallas, src).unwrap();
+    let vesta = run_on_field(FieldChoice::Vesta, src).unwrap();
+
+    assert_eq!(LanguageField::Pallas, pallas.field);
+    assert_eq!(LanguageField::Vesta, vesta.field);
+    assert_eq!(ExprTag::Cons, pallas.tag);
+    assert_eq!(pallas.tag, vesta.tag);
+    assert_eq!(pallas.printed, vesta.printed);
+    assert_ne!(pallas.digest, vesta.digest);
+
+    for choice in [FieldChoice::Pallas, FieldChoice::Vesta] {
+        assert_eq!(ExprTag::Num, run_on_field(choice, "(+ 1 2)").unwrap().tag);
+        assert!(run_on_field(choice, "(+ 1").is_err());
+    }
+}
+
+#[test]
+fn test_eval_str_incomplete() {
+    let s = &mut Store::<Fr>::default();
+    let expected = s.num(3);
+    assert_eq!(
+        expected,
+        eval_str_with_limit::<Fr, Coproc<Fr>>(s, "(+ 1 2)", 100).unwrap()
+    );
+    // running out of steps isn't a value
+    assert!(matches!(
+        eval_str_with_limit::<Fr, Coproc<Fr>>(s, "(letrec ((f (lambda (x) (f x)))) (f 1))", 100),
+        Err(LurkError::Incomplete { limit: 100, .. })
+    ));
+}
+
+#[test]
+fn test_lambda_rest_args() {
+    let s = &mut Store::<Fr>::default();
+    for (src, expected) in [
+        ("((lambda (a . rest) rest) 1)", "nil"),
+        ("((lambda (a . rest) rest) 1 2)", "(2)"),
+        ("((lambda (a . rest) rest) 1 2 3 4)", "(2 3 4)"),
+        ("((lambda (a . rest) a) 1 2 3)", "1"),
+        ("((lambda (a b . rest) (cons b rest)) 1 2 3)", "(2 3)"),
+        (
+            "(let ((x 5)) ((lambda (a . rest) rest) 1 x (+ x 1)))",
+            "(5 6)",
+        ),
+        ("(let ((rest 9)) ((lambda (a . r) rest) 1 2))", "9"),
+        (
+            "(let ((f (lambda (a . rest) (cons a rest)))) (f (f 1 2) 3))",
+            "((1 2) 3)",
+        ),
+        // only the arguments of the same form are gathered, later ones go to the result
+        ("(((lambda (a . r) (lambda (x) x)) 1) 2)", "2"),
+        ("(((lambda (a . r) (lambda (x) r)) 1) 2)", "nil"),
+        (
+            "(((lambda (a . r) (lambda (x) (cons x r))) 1 2) 3)",
+            "(3 2)",
+        ),
+    ] {
+        let expected = s.read(expected).unwrap();
+        assert_eq!(
+            expected,
+            eval_str::<Fr, Coproc<Fr>>(s, src).unwrap(),
+            "{src}"
+        );
+    }
+
+    // applying no arguments leaves the function as it is
+    let unapplied = eval_str::<Fr, Coproc<Fr>>(s, "((lambda (a . rest) rest))").unwrap();
+    assert_eq!(ExprTag::Fun, unapplied.tag);
+
+    let (one, two) = (s.num(1), s.num(2));
+    let expected = s.cons(one, two);
+    assert_eq!(
+        expected,
+        eval_str::<Fr, Coproc<Fr>>(s, "((lambda (a b) (cons a b)) 1 2)").unwrap()
+    );
+}
+
+#[test]
+fn test_let_binds_sequentially() {
+    // `let` already behaves like Scheme's `let*`, so there is no separate sequential form and
+    // `let*` is just an unbound symbol.
+    let s = &mut Store::<Fr>::default();
+    let expected = s.num(2);
+    assert_eq!(
+        expected,
+        eval_str::<Fr, Coproc<Fr>>(s, "(let ((a 1) (b (+ a 1))) b)").unwrap()
+    );
+    let shadowed = s.num(3);
+    assert_eq!(
+        shadowed,
+        eval_str::<Fr, Coproc<Fr>>(s, "(let ((a 1) (a (+ a 1)) (a (+ a 1))) a)").unwrap()
+    );
+    assert!(eval_str::<Fr, Coproc<Fr>>(s, "(let* ((a 1) (b (+ a 1))) b)").is_err());
+    // a binding can't see the ones after it
+    assert!(eval_str::<Fr, Coproc<Fr>>(s, "(let ((b (+ a 1)) (a 1)) b)").is_err());
+}
+
 #[cfg(test)]
 pub(crate) mod coproc {
     use super::super::lang::Lang;
     use super::super::*;
     use super::*;
     use crate::coprocessor::test::DumbCoprocessor;
+    use crate::coprocessor::{package_values, CoCircuit, Values};
+    use crate::error::LangError;
     use crate::store::Store;
 
     #[derive(Clone, Debug, Coproc)]
@@ -2656,5 +3393,167 @@ pub(crate) mod coproc {
         test_aux(s, expr, Some(res), None, None, None, 1, Some(&lang));
         test_aux(s, expr2, Some(res), None, None, None, 3, Some(&lang));
         test_aux(s, expr3, None, None, Some(error), None, 1, Some(&lang));
+
+        let expr3 = s.read(expr3).unwrap();
+        let env = empty_sym_env(s);
+        let err = Evaluator::new(expr3, env, s, 10, &lang)
+            .eval_checked()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            EvalError::CoprocessorArity {
+                expected: 2,
+                got: 3
+            }
+        ));
+    }
+
+    /// A coprocessor whose circuit takes two arguments, but whose evaluation takes three.
+    #[derive(Clone, Debug)]
+    pub(crate) struct MisdeclaredCoprocessor<F: LurkField>(PhantomData<F>);
+
+    impl<F: LurkField> CoCircuit<F> for MisdeclaredCoprocessor<F> {
+        fn arity(&self) -> usize {
+            2
+        }
+    }
+
+    impl<F: LurkField> Coprocessor<F> for MisdeclaredCoprocessor<F> {
+        fn eval_arity(&self) -> usize {
+            3
+        }
+
+        fn simple_evaluate(&self, s: &mut Store<F>, _args: &[Ptr<F>]) -> Ptr<F> {
+            lurk_sym_ptr!(s, nil)
+        }
+
+        fn has_circuit(&self) -> bool {
+            true
+        }
+    }
+
+    #[derive(Clone, Debug, Coproc)]
+    pub(crate) enum MisdeclaredCoproc<F: LurkField> {
+        MC(MisdeclaredCoprocessor<F>),
+    }
+
+    #[test]
+    #[should_panic(expected = "takes 3")]
+    fn test_coprocessor_arity_mismatch() {
+        let s = &mut Store::<Fr>::new();
+
+        // a coprocessor whose circuit and evaluation disagree on its arity can't be bound
+        Lang::<Fr, MisdeclaredCoproc<Fr>>::new_with_bindings(
+            s,
+            vec![(
+                user_sym("cproc-misdeclared"),
+                MisdeclaredCoprocessor(PhantomData).into(),
+            )],
+        );
+    }
+
+    /// A coprocessor returning both the quotient and the remainder of dividing two numbers.
+    #[derive(Clone, Debug)]
+    pub(crate) struct DivModCoprocessor<F: LurkField>(PhantomData<F>);
+
+    impl<F: LurkField> CoCircuit<F> for DivModCoprocessor<F> {}
+
+    impl<F: LurkField> Coprocessor<F> for DivModCoprocessor<F> {
+        fn eval_arity(&self) -> usize {
+            2
+        }
+
+        fn simple_evaluate(&self, s: &mut Store<F>, args: &[Ptr<F>]) -> Ptr<F> {
+            let values = self.simple_evaluate_multi(s, args);
+            package_values(s, &values)
+        }
+
+        fn simple_evaluate_multi(&self, s: &mut Store<F>, args: &[Ptr<F>]) -> Values<F> {
+            let to_u64 = |ptr: &Ptr<F>| {
+                if ptr.tag != ExprTag::Num {
+                    return None;
+                }
+                s.fetch_num(ptr)?.into_scalar().to_u64()
+            };
+            match (to_u64(&args[0]), to_u64(&args[1])) {
+                (Some(a), Some(b)) if b != 0 => [s.num(a / b), s.num(a % b)].into_iter().collect(),
+                _ => Values::new(),
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, Coproc)]
+    pub(crate) enum DivModCoproc<F: LurkField> {
+        DM(DivModCoprocessor<F>),
+    }
+
+    #[test]
+    fn test_coprocessor_multi_value() {
+        let s = &mut Store::<Fr>::new();
+
+        let lang = Lang::<Fr, DivModCoproc<Fr>>::new_with_bindings(
+            s,
+            vec![(user_sym("divmod"), DivModCoprocessor(PhantomData).into())],
+        );
+
+        let expr = "(divmod 17 5)";
+        let expr2 = "(divmod 17 0)";
+
+        let res = s.read("(3 2)").unwrap();
+        let nil = lurk_sym_ptr!(s, nil);
+
+        test_aux(s, expr, Some(res), None, None, None, 1, Some(&lang));
+        // returning no values evaluates to the empty list
+        test_aux(s, expr2, Some(nil), None, None, None, 1, Some(&lang));
+
+        let expr3 = s
+            .read("(let ((qr (divmod 17 5))) (+ (* 10 (car qr)) (car (cdr qr))))")
+            .unwrap();
+        let env = empty_sym_env(s);
+        let (output, ..) = Evaluator::new(expr3, env, s, 100, &lang).eval().unwrap();
+        assert_eq!(s.num(32), output.expr);
+    }
+
+    #[derive(Clone, Debug, Coproc)]
+    pub(crate) enum DumbDivModCoproc<F: LurkField> {
+        DC(DumbCoprocessor<F>),
+        DM(DivModCoprocessor<F>),
+    }
+
+    #[test]
+    fn test_coprocessor_dispatch_by_name() {
+        let s = &mut Store::<Fr>::new();
+
+        let mut lang = Lang::<Fr, DumbDivModCoproc<Fr>>::new();
+        lang.try_add_coprocessor(user_sym("cproc-dumb"), DumbCoprocessor::new(), s)
+            .unwrap();
+        lang.try_add_coprocessor(user_sym("divmod"), DivModCoprocessor(PhantomData), s)
+            .unwrap();
+
+        let expr = "(cons (cproc-dumb 9 8) (divmod 17 5))";
+        let res = s.read("(89 3 2)").unwrap();
+        test_aux(s, expr, Some(res), None, None, None, 3, Some(&lang));
+
+        // a name can't be bound twice, and the first binding is kept
+        assert_eq!(
+            Err(LangError::NameCollision(user_sym("divmod"))),
+            lang.try_add_coprocessor(user_sym("divmod"), DumbCoprocessor::new(), s)
+        );
+        test_aux(s, expr, Some(res), None, None, None, 3, Some(&lang));
+
+        let mut misdeclared = Lang::<Fr, MisdeclaredCoproc<Fr>>::new();
+        assert_eq!(
+            Err(LangError::CoprocessorArity {
+                name: user_sym("cproc-misdeclared"),
+                expected: 2,
+                got: 3
+            }),
+            misdeclared.try_add_coprocessor(
+                user_sym("cproc-misdeclared"),
+                MisdeclaredCoprocessor(PhantomData),
+                s
+            )
+        );
+        assert!(!misdeclared.has_coprocessors());
     }
 }
@@ -2,7 +2,7 @@ use std::hash::Hash;
 
 use crate::field::LurkField;
 use crate::ptr::{ContPtr, Ptr};
-use crate::{Num, UInt};
+use crate::{Num, Symbol, UInt};
 
 // Expressions, Continuations, Op1, Op2 occupy the same namespace in
 // their encoding.
@@ -35,6 +35,32 @@ pub enum Expression<F: LurkField> {
     UInt(UInt),
 }
 
+/// An expression with its strings and symbols read out of the store, as returned by
+/// `Store::fetch_expr`, so it can be matched on in one step. Child expressions are still pointers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprView<F: LurkField> {
+    Nil,
+    Cons(Ptr<F>, Ptr<F>),
+    Comm {
+        secret: F,
+        payload: Ptr<F>,
+    },
+    Fun {
+        arg: Ptr<F>,
+        body: Ptr<F>,
+        env: Ptr<F>,
+    },
+    Num(Num<F>),
+    Str(String),
+    Thunk(Thunk<F>),
+    /// A symbol or keyword other than `nil`
+    Sym(Symbol),
+    Char(char),
+    UInt(UInt),
+    /// A pointer whose expression isn't known to the store, only its digest
+    Opaque(F),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Thunk<F: LurkField> {
     pub value: Ptr<F>,
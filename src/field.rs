@@ -79,6 +79,42 @@ pub trait LurkField: PrimeField + PrimeFieldBits {
         Self::from_repr(def).into()
     }
 
+    /// The number of bytes of `to_bytes_le`, just enough to hold `Self::NUM_BITS` bits
+    fn bytes_le_len() -> usize {
+        (Self::NUM_BITS as usize + 7) / 8
+    }
+
+    /// Converts the field element to its canonical little-endian bytes. Unlike `to_bytes`, this
+    /// is computed from the element's bits, so it doesn't depend on the layout of `Self::Repr`.
+    fn to_bytes_le(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; Self::bytes_le_len()];
+        let bits = self.to_le_bits();
+        for (i, bit) in bits.into_iter().take(Self::NUM_BITS as usize).enumerate() {
+            if bit {
+                bytes[i / 8] |= 1 << (i % 8);
+            }
+        }
+        bytes
+    }
+
+    /// Attempts to construct a field element from the output of `to_bytes_le`, failing if `bs`
+    /// has the wrong length or encodes a value at least the modulus
+    fn from_bytes_le(bs: &[u8]) -> Option<Self> {
+        if bs.len() != Self::bytes_le_len() {
+            return None;
+        }
+        let max = (Self::ZERO - Self::ONE).to_bytes_le();
+        if bs.iter().rev().cmp(max.iter().rev()) == std::cmp::Ordering::Greater {
+            return None;
+        }
+        let radix = Self::from(256u64);
+        Some(
+            bs.iter()
+                .rev()
+                .fold(Self::ZERO, |acc, b| acc * radix + Self::from(u64::from(*b))),
+        )
+    }
+
     /// Converts the field element to a hexadecimal string
     fn hex_digits(self) -> String {
         let bytes = self.to_bytes();
@@ -195,6 +231,28 @@ pub trait LurkField: PrimeField + PrimeFieldBits {
         self.double().is_odd().into()
     }
 
+    /// Whether `a` is less than `b`, comparing their canonical representatives as integers
+    /// rather than as signed values like `is_negative` does
+    fn lt_native(a: &Self, b: &Self) -> bool {
+        a.to_bytes_le()
+            .iter()
+            .rev()
+            .lt(b.to_bytes_le().iter().rev())
+    }
+
+    /// Adds `a` and `b`, also returning whether the integer sum of their canonical
+    /// representatives reached the modulus, so that the result wrapped around
+    fn checked_add_native(a: Self, b: Self) -> (Self, bool) {
+        let sum = a + b;
+        (sum, Self::lt_native(&sum, &a))
+    }
+
+    /// Subtracts `b` from `a`, also returning whether the result wrapped around, which is when
+    /// `b`'s canonical representative is greater than `a`'s
+    fn checked_sub_native(a: Self, b: Self) -> (Self, bool) {
+        (a - b, Self::lt_native(&a, &b))
+    }
+
     /// Constructs a field element from an ExprTag
     fn from_expr_tag(tag: ExprTag) -> Self {
         Self::from_u64(tag.into())
@@ -353,6 +411,86 @@ pub mod tests {
       }
     }
 
+    fn bytes_le_roundtrip<F: LurkField>(f1: FWrap<F>) {
+        let bytes = f1.0.to_bytes_le();
+        assert_eq!((F::NUM_BITS as usize + 7) / 8, bytes.len());
+        assert_eq!(Some(f1.0), F::from_bytes_le(&bytes));
+    }
+
+    /// Checks that the modulus and anything above it are rejected
+    fn bytes_le_rejects_modulus<F: LurkField>() {
+        let mut modulus = (F::ZERO - F::ONE).to_bytes_le();
+        for byte in modulus.iter_mut() {
+            let (sum, carry) = byte.overflowing_add(1);
+            *byte = sum;
+            if !carry {
+                break;
+            }
+        }
+        assert_eq!(None, F::from_bytes_le(&modulus));
+        assert_eq!(None, F::from_bytes_le(&vec![0xff; F::bytes_le_len()]));
+        assert_eq!(None, F::from_bytes_le(&[0]));
+        assert_eq!(
+            Some(F::ZERO - F::ONE),
+            F::from_bytes_le(&(F::ZERO - F::ONE).to_bytes_le())
+        );
+    }
+
+    /// Checks wraparound detection at the modulus boundary and away from it
+    fn checked_native_wraparound<F: LurkField>() {
+        let max = F::ZERO - F::ONE;
+        let (two, three) = (F::from(2), F::from(3));
+        assert_eq!((F::from(5), false), F::checked_add_native(two, three));
+        assert_eq!((max, false), F::checked_add_native(max, F::ZERO));
+        assert_eq!((F::ZERO, true), F::checked_add_native(max, F::ONE));
+        assert_eq!((max - F::ONE, true), F::checked_add_native(max, max));
+        assert_eq!(
+            (max, false),
+            F::checked_add_native(F::most_positive(), F::most_positive())
+        );
+        assert_eq!(
+            (F::ZERO, true),
+            F::checked_add_native(F::most_positive(), F::most_negative())
+        );
+
+        assert_eq!((F::ONE, false), F::checked_sub_native(three, two));
+        assert_eq!((F::ZERO, false), F::checked_sub_native(max, max));
+        assert_eq!((max, true), F::checked_sub_native(two, three));
+        assert_eq!((F::ONE, true), F::checked_sub_native(F::ZERO, max));
+    }
+
+    #[test]
+    fn checked_native_arithmetic() {
+        checked_native_wraparound::<Fr>();
+        checked_native_wraparound::<pallas::Scalar>();
+        checked_native_wraparound::<vesta::Scalar>();
+    }
+
+    #[test]
+    fn bytes_le_width_and_bounds() {
+        assert_eq!(32, Fr::bytes_le_len());
+        assert_eq!(32, pallas::Scalar::bytes_le_len());
+        assert_eq!(32, vesta::Scalar::bytes_le_len());
+        bytes_le_rejects_modulus::<Fr>();
+        bytes_le_rejects_modulus::<pallas::Scalar>();
+        bytes_le_rejects_modulus::<vesta::Scalar>();
+    }
+
+    proptest! {
+      #[test]
+      fn prop_bls_bytes_le_roundtrip(f1 in any::<FWrap<Fr>>()) {
+          bytes_le_roundtrip(f1)
+      }
+      #[test]
+      fn prop_pallas_bytes_le_roundtrip(f1 in any::<FWrap<pallas::Scalar>>()) {
+          bytes_le_roundtrip(f1)
+      }
+      #[test]
+      fn prop_vesta_bytes_le_roundtrip(f1 in any::<FWrap<vesta::Scalar>>()) {
+          bytes_le_roundtrip(f1)
+      }
+    }
+
     // Construct canonical bytes from a field element
     fn to_le_bytes_canonical<F: LurkField>(f: F) -> Vec<u8> {
         let mut vec = vec![];
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::fmt;
 use std::hash::Hash;
 use std::sync::Arc;
 
@@ -83,17 +84,33 @@ impl<F: LurkField> HashConstants<F> {
     }
 }
 
+/// A hash function to use in place of Poseidon, for comparing the costs of different hashes.
+/// Preimages have 3, 4, 6 or 8 elements. Circuits always hash with Poseidon, so data hashed with
+/// anything else can't be proven.
+pub trait HashFunction<F: LurkField>: fmt::Debug + Send + Sync {
+    fn hash(&self, preimage: &[F]) -> F;
+}
+
 #[derive(Clone, Default, Debug)]
 pub struct PoseidonCache<F: LurkField> {
     a3: Arc<CacheMap<CacheKey<F, 3>, F>>,
     a4: Arc<CacheMap<CacheKey<F, 4>, F>>,
     a6: Arc<CacheMap<CacheKey<F, 6>, F>>,
     a8: Arc<CacheMap<CacheKey<F, 8>, F>>,
+    hash_function: Option<Arc<dyn HashFunction<F>>>,
 
     pub constants: HashConstants<F>,
 }
 
 impl<F: LurkField> PoseidonCache<F> {
+    /// A cache that hashes with `hash_function` instead of Poseidon
+    pub fn with_hash_function(hash_function: Arc<dyn HashFunction<F>>) -> Self {
+        Self {
+            hash_function: Some(hash_function),
+            ..Default::default()
+        }
+    }
+
     pub fn compute_hash<const ARITY: usize>(&self, preimage: [F; ARITY]) -> F {
         macro_rules! hash {
             ($hash_name:ident, $n:expr) => {{
@@ -183,27 +200,35 @@ impl<F: LurkField, const N: usize> Hash for CacheKey<F, N> {
 
 impl<F: LurkField> PoseidonCache<F> {
     pub fn hash3(&self, preimage: &[F; 3]) -> F {
-        self.a3.get_copy_or_insert_with(CacheKey(*preimage), || {
-            Poseidon::new_with_preimage(preimage, self.constants.c3()).hash()
-        })
+        self.a3
+            .get_copy_or_insert_with(CacheKey(*preimage), || match &self.hash_function {
+                Some(hash_function) => hash_function.hash(preimage),
+                None => Poseidon::new_with_preimage(preimage, self.constants.c3()).hash(),
+            })
     }
 
     pub fn hash4(&self, preimage: &[F; 4]) -> F {
-        self.a4.get_copy_or_insert_with(CacheKey(*preimage), || {
-            Poseidon::new_with_preimage(preimage, self.constants.c4()).hash()
-        })
+        self.a4
+            .get_copy_or_insert_with(CacheKey(*preimage), || match &self.hash_function {
+                Some(hash_function) => hash_function.hash(preimage),
+                None => Poseidon::new_with_preimage(preimage, self.constants.c4()).hash(),
+            })
     }
 
     pub fn hash6(&self, preimage: &[F; 6]) -> F {
-        self.a6.get_copy_or_insert_with(CacheKey(*preimage), || {
-            Poseidon::new_with_preimage(preimage, self.constants.c6()).hash()
-        })
+        self.a6
+            .get_copy_or_insert_with(CacheKey(*preimage), || match &self.hash_function {
+                Some(hash_function) => hash_function.hash(preimage),
+                None => Poseidon::new_with_preimage(preimage, self.constants.c6()).hash(),
+            })
     }
 
     pub fn hash8(&self, preimage: &[F; 8]) -> F {
-        self.a8.get_copy_or_insert_with(CacheKey(*preimage), || {
-            Poseidon::new_with_preimage(preimage, self.constants.c8()).hash()
-        })
+        self.a8
+            .get_copy_or_insert_with(CacheKey(*preimage), || match &self.hash_function {
+                Some(hash_function) => hash_function.hash(preimage),
+                None => Poseidon::new_with_preimage(preimage, self.constants.c8()).hash(),
+            })
     }
 }
 
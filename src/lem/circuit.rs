@@ -35,7 +35,7 @@ use bellpepper_core::{
 use crate::circuit::gadgets::{
     constraints::{
         add, alloc_equal, alloc_equal_const, and, enforce_selector_with_premise, implies_equal,
-        mul, sub,
+        mul, sub, u64_bitwise,
     },
     data::{allocate_constant, hash_poseidon},
     pointer::AllocatedPtr,
@@ -555,6 +555,33 @@ impl Func {
                     Op::Div(_tgt, _a, _b) => {
                         // TODO
                     }
+                    Op::Bitwise(tgts, a, b) => {
+                        let a = bound_allocations.get(a)?;
+                        let b = bound_allocations.get(b)?;
+                        let cs = &mut cs.namespace(|| format!("bitwise {}", tgts[0]));
+                        let u64_tag = Tag::Expr(U64).to_field::<F>();
+                        // the results are only enforced for U64s on the concrete path
+                        let a_is_u64 =
+                            alloc_equal_const(&mut cs.namespace(|| "a_is_u64"), a.tag(), u64_tag)?;
+                        let b_is_u64 =
+                            alloc_equal_const(&mut cs.namespace(|| "b_is_u64"), b.tag(), u64_tag)?;
+                        let both_u64 = and(&mut cs.namespace(|| "both_u64"), &a_is_u64, &b_is_u64)?;
+                        let cond = and(&mut cs.namespace(|| "cond"), not_dummy, &both_u64)?;
+                        let res = u64_bitwise(
+                            &mut cs.namespace(|| "u64_bitwise"),
+                            &cond,
+                            a.hash(),
+                            b.hash(),
+                        )?;
+                        let tag = g.global_allocator.get_or_alloc_const(cs, u64_tag)?;
+                        for (tgt, num) in tgts
+                            .iter()
+                            .zip([res.and, res.or, res.xor, res.shl, res.shr])
+                        {
+                            let c = AllocatedPtr::from_parts(tag.clone(), num);
+                            bound_allocations.insert(tgt.clone(), c);
+                        }
+                    }
                     Op::Emit(_) => (),
                     Op::Hide(tgt, _sec, _pay) => {
                         // TODO
@@ -846,6 +873,12 @@ impl Func {
                     Op::Div(_, _, _) => {
                         // TODO
                     }
+                    Op::Bitwise(..) => {
+                        globals.insert(FWrap(Tag::Expr(U64).to_field()));
+                        // 3 constraints for each `alloc_equal_const`, 1 for each `and`
+                        // (the second is free for non-nested ops) and 450 for `u64_bitwise`
+                        num_constraints += if nested { 458 } else { 457 };
+                    }
                     Op::Emit(_) => (),
                     Op::Hash2(_, tag, _) => {
                         // tag for the image
@@ -126,7 +126,12 @@ fn reduce() -> Func {
             | Symbol("<")
             | Symbol(">")
             | Symbol("<=")
-            | Symbol(">=") => {
+            | Symbol(">=")
+            | Symbol("bit-and")
+            | Symbol("bit-or")
+            | Symbol("bit-xor")
+            | Symbol("shl")
+            | Symbol("shr") => {
                 return (t)
             }
         };
@@ -663,6 +668,9 @@ fn apply_cont() -> Func {
                     }
                     Cont::Binop2 => {
                         let (operator, evaled_arg, continuation) = unhash3(cont);
+                        // One gadget serves all the bitwise operators, whose results are
+                        // only returned if both arguments are U64s
+                        let (bit_and, bit_or, bit_xor, shl, shr) = bitwise(evaled_arg, result);
                         match operator.val {
                             Symbol("eval") => {
                                 return (evaled_arg, result, continuation, ret)
@@ -739,6 +747,71 @@ fn apply_cont() -> Func {
                                 // TODO
                                 return (result, env, err, errctrl)
                             }
+                            Symbol("bit-and") => {
+                                match evaled_arg.tag {
+                                    Expr::U64 => {
+                                        match result.tag {
+                                            Expr::U64 => {
+                                                return (bit_and, env, continuation, makethunk)
+                                            }
+                                        };
+                                        return (result, env, err, errctrl)
+                                    }
+                                };
+                                return (result, env, err, errctrl)
+                            }
+                            Symbol("bit-or") => {
+                                match evaled_arg.tag {
+                                    Expr::U64 => {
+                                        match result.tag {
+                                            Expr::U64 => {
+                                                return (bit_or, env, continuation, makethunk)
+                                            }
+                                        };
+                                        return (result, env, err, errctrl)
+                                    }
+                                };
+                                return (result, env, err, errctrl)
+                            }
+                            Symbol("bit-xor") => {
+                                match evaled_arg.tag {
+                                    Expr::U64 => {
+                                        match result.tag {
+                                            Expr::U64 => {
+                                                return (bit_xor, env, continuation, makethunk)
+                                            }
+                                        };
+                                        return (result, env, err, errctrl)
+                                    }
+                                };
+                                return (result, env, err, errctrl)
+                            }
+                            Symbol("shl") => {
+                                match evaled_arg.tag {
+                                    Expr::U64 => {
+                                        match result.tag {
+                                            Expr::U64 => {
+                                                return (shl, env, continuation, makethunk)
+                                            }
+                                        };
+                                        return (result, env, err, errctrl)
+                                    }
+                                };
+                                return (result, env, err, errctrl)
+                            }
+                            Symbol("shr") => {
+                                match evaled_arg.tag {
+                                    Expr::U64 => {
+                                        match result.tag {
+                                            Expr::U64 => {
+                                                return (shr, env, continuation, makethunk)
+                                            }
+                                        };
+                                        return (result, env, err, errctrl)
+                                    }
+                                };
+                                return (result, env, err, errctrl)
+                            }
                         };
                         return (result, env, err, errctrl)
                     }
@@ -809,8 +882,8 @@ mod tests {
     use blstrs::Scalar as Fr;
 
     const NUM_INPUTS: usize = 1;
-    const NUM_AUX: usize = 8092;
-    const NUM_CONSTRAINTS: usize = 10125;
+    const NUM_AUX: usize = 8625;
+    const NUM_CONSTRAINTS: usize = 10823;
     const NUM_SLOTS: SlotsCounter = SlotsCounter {
         hash2: 16,
         hash3: 4,
@@ -888,6 +961,8 @@ mod tests {
                 (sum (build 10)))",
         );
         let fold_res = read("55");
+        let bitwise = read("(bit-xor 6u64 3u64)");
+        let bitwise_res = read("5u64");
         vec![
             (sum, sum_res),
             (car, car_res),
@@ -895,6 +970,7 @@ mod tests {
             (lam0, lam0_res),
             (lam, lam_res),
             (fold, fold_res),
+            (bitwise, bitwise_res),
         ]
     }
 
@@ -141,6 +141,22 @@ impl Block {
                     };
                     bindings.insert(tgt.clone(), c);
                 }
+                Op::Bitwise(tgts, a, b) => {
+                    let a = bindings.get(a)?;
+                    let b = bindings.get(b)?;
+                    // The results are only meaningful for U64s, and are zeros otherwise
+                    let results = match (a, b) {
+                        (Ptr::Leaf(Tag::Expr(U64), f), Ptr::Leaf(Tag::Expr(U64), g)) => {
+                            let (a, b) = (f.to_u64_unchecked(), g.to_u64_unchecked());
+                            let (shl, shr) = if b < 64 { (a << b, a >> b) } else { (0, 0) };
+                            [a & b, a | b, a ^ b, shl, shr]
+                        }
+                        _ => [0; 5],
+                    };
+                    for (tgt, res) in tgts.iter().zip(results) {
+                        bindings.insert(tgt.clone(), Ptr::Leaf(Tag::Expr(U64), F::from_u64(res)));
+                    }
+                }
                 Op::Emit(a) => {
                     let a = bindings.get(a)?;
                     println!("{}", a.to_string(store))
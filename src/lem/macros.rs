@@ -86,6 +86,13 @@ macro_rules! op {
             $crate::var!($b),
         )
     };
+    ( let ($and:ident, $or:ident, $xor:ident, $shl:ident, $shr:ident) = bitwise($a:ident, $b:ident) ) => {
+        $crate::lem::Op::Bitwise(
+            $crate::vars!($and, $or, $xor, $shl, $shr),
+            $crate::var!($a),
+            $crate::var!($b),
+        )
+    };
     ( emit($v:ident) ) => {
         $crate::lem::Op::Emit($crate::var!($v))
     };
@@ -283,6 +290,16 @@ macro_rules! block {
             $($tail)*
         )
     };
+    (@seq {$($limbs:expr)*}, let ($and:ident, $or:ident, $xor:ident, $shl:ident, $shr:ident) = bitwise($a:ident, $b:ident) ; $($tail:tt)*) => {
+        $crate::block! (
+            @seq
+            {
+                $($limbs)*
+                $crate::op!(let ($and, $or, $xor, $shl, $shr) = bitwise($a, $b))
+            },
+            $($tail)*
+        )
+    };
     (@seq {$($limbs:expr)*}, emit($v:ident) ; $($tail:tt)*) => {
         $crate::block! (
             @seq
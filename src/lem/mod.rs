@@ -245,6 +245,10 @@ pub enum Op {
     Mul(Var, Var, Var),
     /// `Div(y, a, b)` binds `y` to the sum of `a` and `b`
     Div(Var, Var, Var),
+    /// `Bitwise([and, or, xor, shl, shr], a, b)` binds `and`, `or` and `xor` to
+    /// the bitwise operations on the U64s `a` and `b`, and `shl` and `shr` to
+    /// the shifts of `a` by `b` bits, which are zero when `b` is at least 64
+    Bitwise([Var; 5], Var, Var),
     /// `Emit(v)` simply prints out the value of `v` when interpreting the code
     Emit(Var),
     /// `Hash2(x, t, ys)` binds `x` to a `Ptr` with tag `t` and 2 children `ys`
@@ -348,6 +352,11 @@ impl Func {
                         is_bound(b, map)?;
                         is_unique(tgt, map);
                     }
+                    Op::Bitwise(tgts, a, b) => {
+                        is_bound(a, map)?;
+                        is_bound(b, map)?;
+                        tgts.iter().for_each(|var| is_unique(var, map));
+                    }
                     Op::Emit(a) => {
                         is_bound(a, map)?;
                     }
@@ -575,6 +584,12 @@ impl Block {
                     let tgt = insert_one(map, uniq, &tgt);
                     ops.push(Op::Div(tgt, a, b))
                 }
+                Op::Bitwise(tgts, a, b) => {
+                    let a = map.get_cloned(&a)?;
+                    let b = map.get_cloned(&b)?;
+                    let tgts = insert_many(map, uniq, &tgts);
+                    ops.push(Op::Bitwise(tgts.try_into().unwrap(), a, b))
+                }
                 Op::Emit(a) => {
                     let a = map.get_cloned(&a)?;
                     ops.push(Op::Emit(a))
@@ -255,6 +255,7 @@ impl<F: LurkField> Store<F> {
                 }
                 Ok(cdr)
             }
+            Syntax::ReaderMacro(_, _, x) => self.intern_syntax(*x),
         }
     }
 
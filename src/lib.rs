@@ -3,6 +3,8 @@
 extern crate alloc;
 
 pub mod cache_map;
+#[cfg(feature = "capi")]
+pub mod capi;
 pub mod circuit;
 pub mod cli;
 pub mod config;
@@ -29,6 +31,8 @@ pub mod syntax;
 mod syntax_macros;
 pub mod tag;
 pub mod uint;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 pub mod writer;
 pub mod z_data;
 pub use num::Num;
@@ -76,6 +76,9 @@ impl<F: LurkField> Hash for Num<F> {
     }
 }
 
+/// Field elements have no natural order, so `Num`s are compared as signed integers: elements
+/// above `F::most_positive()` are negative, and `most_negative()` is the least `Num`. This is the
+/// order Lurk's `<`, `<=`, `>` and `>=` use, unless both arguments are `u64`s.
 impl<F: LurkField> PartialOrd for Num<F> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         if self == other {
@@ -84,8 +84,11 @@ impl Package {
 
     pub fn fmt_to_string(&self, symbol: &SymbolRef) -> String {
         match self.names.get(symbol) {
-            None => symbol.fmt_to_string(),
-            Some(name) => Symbol::fmt_path_component_to_string(name),
+            // names that would read as something else (a number, say) print in full instead
+            Some(name) if !Symbol::sym(&[name]).prints_as_absolute() => {
+                Symbol::fmt_path_component_to_string(name)
+            }
+            _ => symbol.fmt_to_string(),
         }
     }
 }
@@ -1,10 +1,12 @@
 use std::cell::RefCell;
+use std::ops::Range;
 use std::rc::Rc;
 
 use crate::field::LurkField;
 use crate::ptr::Ptr;
 use crate::state::State;
 use crate::store::Store;
+use crate::syntax::Syntax;
 use nom::sequence::preceded;
 use nom::Parser;
 use thiserror;
@@ -30,22 +32,52 @@ pub static LURK_WHITESPACE: [char; 27] = [
 pub enum Error {
     #[error("Empty input error")]
     NoInput,
-    #[error("Syntax error: {0}")]
-    Syntax(String),
+    /// `span` is the range of byte offsets of the input the error is about, such as a whole
+    /// unterminated string or out-of-range number literal.
+    #[error("Syntax error: {message}")]
+    Syntax { message: String, span: Range<usize> },
+}
+
+impl Error {
+    fn syntax<F: LurkField>(input_end: usize, e: nom::Err<error::ParseError<Span<'_>, F>>) -> Self {
+        match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                let start = e.input.location_offset();
+                let end = match e.upto {
+                    Some(upto) => upto.location_offset(),
+                    // without a known end, point at the character the error was found at
+                    None => start + e.input.chars().next().map_or(0, char::len_utf8),
+                };
+                Error::Syntax {
+                    message: format!("{}", e),
+                    span: start..end,
+                }
+            }
+            nom::Err::Incomplete(_) => Error::Syntax {
+                message: format!("{}", e),
+                span: input_end..input_end,
+            },
+        }
+    }
+}
+
+/// Reads the first expression of `input` in the initial Lurk state, without interning it
+pub fn read_syntax<F: LurkField>(input: &str) -> Result<Syntax<F>, Error> {
+    let state = State::init_lurk_state().rccell();
+    match preceded(
+        syntax::parse_space,
+        syntax::parse_syntax(state, false, false),
+    )
+    .parse(Span::new(input))
+    {
+        Ok((_i, x)) => Ok(x),
+        Err(e) => Err(Error::syntax(input.len(), e)),
+    }
 }
 
 impl<F: LurkField> Store<F> {
     pub fn read(&mut self, input: &str) -> Result<Ptr<F>, Error> {
-        let state = State::init_lurk_state().rccell();
-        match preceded(
-            syntax::parse_space,
-            syntax::parse_syntax(state, false, false),
-        )
-        .parse(Span::new(input))
-        {
-            Ok((_i, x)) => Ok(self.intern_syntax(x)),
-            Err(e) => Err(Error::Syntax(format!("{}", e))),
-        }
+        Ok(self.intern_syntax(read_syntax(input)?))
     }
 
     pub fn read_with_state(
@@ -60,7 +92,7 @@ impl<F: LurkField> Store<F> {
         .parse(Span::new(input))
         {
             Ok((_i, x)) => Ok(self.intern_syntax(x)),
-            Err(e) => Err(Error::Syntax(format!("{}", e))),
+            Err(e) => Err(Error::syntax(input.len(), e)),
         }
     }
 
@@ -73,7 +105,111 @@ impl<F: LurkField> Store<F> {
         match preceded(parse_space, parse_maybe_meta(state, false)).parse(input) {
             Ok((i, Some((is_meta, x)))) => Ok((i, self.intern_syntax(x), is_meta)),
             Ok((_, None)) => Err(Error::NoInput),
-            Err(e) => Err(Error::Syntax(format!("{}", e))),
+            Err(e) => Err(Error::syntax(input.location_offset() + input.len(), e)),
+        }
+    }
+}
+
+/// An iterator over the top-level forms of a source, which reads each form as it's reached. See
+/// `Store::read_forms`.
+pub struct Forms<'a, F: LurkField> {
+    store: &'a mut Store<F>,
+    state: Rc<RefCell<State>>,
+    /// The unread input, or `None` once the input ends or an error is returned
+    rest: Option<Span<'a>>,
+    input_end: usize,
+}
+
+impl<'a, F: LurkField> Forms<'a, F> {
+    fn fail(
+        &mut self,
+        e: nom::Err<error::ParseError<Span<'a>, F>>,
+    ) -> Option<Result<Ptr<F>, Error>> {
+        self.rest = None;
+        Some(Err(Error::syntax(self.input_end, e)))
+    }
+}
+
+impl<'a, F: LurkField> Iterator for Forms<'a, F> {
+    type Item = Result<Ptr<F>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = match syntax::parse_space::<F>(self.rest?) {
+            Ok((rest, _)) => rest,
+            Err(e) => return self.fail(e),
+        };
+        if rest.is_empty() {
+            self.rest = None;
+            return None;
+        }
+        match syntax::parse_syntax(self.state.clone(), false, false).parse(rest) {
+            Ok((rest, x)) => {
+                self.rest = Some(rest);
+                Some(Ok(self.store.intern_syntax(x)))
+            }
+            Err(e) => self.fail(e),
+        }
+    }
+}
+
+impl<F: LurkField> Store<F> {
+    /// Returns an iterator over the top-level forms of `input`, reading each one only when it's
+    /// reached. After the first syntax error, the iterator ends.
+    pub fn read_forms<'a>(&'a mut self, input: &'a str) -> Forms<'a, F> {
+        Forms {
+            store: self,
+            state: State::init_lurk_state().rccell(),
+            rest: Some(Span::new(input)),
+            input_end: input.len(),
+        }
+    }
+}
+
+/// Reads expressions like `Store::read_with_state`, and also the literals of the reader macros
+/// registered with it, for embedding domain-specific data in source text.
+pub struct Reader<F: LurkField> {
+    state: Rc<RefCell<State>>,
+    macros: syntax::ReaderMacros<F>,
+}
+
+impl<F: LurkField> Default for Reader<F> {
+    fn default() -> Self {
+        Self::new(State::init_lurk_state().rccell())
+    }
+}
+
+impl<F: LurkField> Reader<F> {
+    /// A reader without reader macros, reading symbols in `state`
+    pub fn new(state: Rc<RefCell<State>>) -> Self {
+        Self {
+            state,
+            macros: Rc::default(),
+        }
+    }
+
+    /// Makes `prefix` immediately followed by a string literal, such as `#hex"ff"` for the prefix
+    /// `#hex`, read as the syntax `handler` returns for the string's contents, which is interned
+    /// like the rest of the expression. The prefix is recognized wherever an expression can
+    /// start, replacing any handler it had.
+    pub fn register_macro(&mut self, prefix: &str, handler: syntax::ReaderMacro<F>) {
+        let macros =
+            Rc::get_mut(&mut self.macros).expect("reader macros aren't shared between reads");
+        match macros.iter_mut().find(|(p, _)| p == prefix) {
+            Some((_, h)) => *h = handler,
+            None => macros.push((prefix.to_owned(), handler)),
+        }
+    }
+
+    /// Reads the first expression of `input` into `store`
+    pub fn read(&self, store: &mut Store<F>, input: &str) -> Result<Ptr<F>, Error> {
+        match preceded(
+            syntax::parse_space,
+            syntax::parse_syntax_with_macros(self.state.clone(), self.macros.clone(), false, false),
+        )
+        .parse(Span::new(input))
+        {
+            Ok((_i, x)) => Ok(store.intern_syntax(x)),
+            Err(e) => Err(Error::syntax(input.len(), e)),
         }
     }
 }
@@ -612,3 +748,131 @@ impl<F: LurkField> Store<F> {
 //        test(&mut s, "0xa/", "0xa");
 //    }
 //}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::position::Pos;
+    use crate::uint::UInt;
+    use blstrs::Scalar as Fr;
+
+    fn syntax_error_span(input: &str) -> Range<usize> {
+        match Store::<Fr>::default().read(input) {
+            Err(Error::Syntax { span, .. }) => span,
+            res => panic!("expected a syntax error, got {res:?}"),
+        }
+    }
+
+    #[test]
+    fn read_forms() {
+        let store = &mut Store::<Fr>::default();
+        let src = "(+ 1 2) ;; first\n\n  x\n;; between\n;; forms\n  \"s\" 'a ;; last";
+        let expected = ["(+ 1 2)", "x", "\"s\"", "'a"]
+            .map(|form| store.read(form).unwrap())
+            .to_vec();
+        let forms = store
+            .read_forms(src)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(expected, forms);
+
+        assert_eq!(0, store.read_forms("").count());
+        assert_eq!(0, store.read_forms("  ;; only a comment").count());
+
+        let one = store.num(1);
+        let mut forms = store.read_forms("1 (+ 1");
+        assert_eq!(one, forms.next().unwrap().unwrap());
+        assert!(matches!(forms.next(), Some(Err(Error::Syntax { .. }))));
+        assert!(forms.next().is_none());
+    }
+
+    #[test]
+    fn read_comments() {
+        let store = &mut Store::<Fr>::default();
+        let list = store.read("(1 2 3)").unwrap();
+        for src in [
+            "(1 #| a block comment |# 2 3)",
+            "(1 #| outer #| inner |# still in outer |# 2 3)",
+            "#| before |#(1 2#|between|#3)#| after |#",
+            "(1 ; a line comment\n 2 3)",
+            ";; a comment\n(1 2 3) ; a line comment immediately before EOF",
+        ] {
+            assert_eq!(list, store.read(src).unwrap(), "{src}");
+        }
+
+        for (src, span) in [
+            ("#| unterminated", 0..15),
+            ("(1 #| outer #| inner |# 2 3)", 3..28),
+        ] {
+            match store.read(src) {
+                Err(Error::Syntax { span: found, .. }) => assert_eq!(span, found, "{src}"),
+                res => panic!("expected a syntax error, got {res:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn reader_macros() {
+        let store = &mut Store::<Fr>::default();
+        let mut reader = Reader::default();
+        reader.register_macro(
+            "#hex",
+            Box::new(|digits: &str| {
+                u64::from_str_radix(digits, 16)
+                    .map(|n| Syntax::UInt(Pos::No, UInt::U64(n)))
+                    .map_err(error::ParseErrorKind::ParseIntErr)
+            }),
+        );
+
+        let expected = store.read("(+ 255u64 '(1 16u64) \"#hex\")").unwrap();
+        let form = reader
+            .read(store, "(+ #hex\"ff\" '(1 #hex\"10\") \"#hex\")")
+            .unwrap();
+        assert_eq!(expected, form);
+        assert_eq!(
+            store.uint64(0xab),
+            reader.read(store, " #hex\"AB\"").unwrap()
+        );
+
+        // the handler's syntax is interned like the rest, so it can hold data of any shape
+        reader.register_macro(
+            "#pair",
+            Box::new(|s: &str| {
+                let (a, b) = s.split_once(',').ok_or_else(|| {
+                    error::ParseErrorKind::ReaderMacro(format!("no comma in {s}"))
+                })?;
+                Ok(Syntax::List(
+                    Pos::No,
+                    vec![
+                        Syntax::String(Pos::No, a.into()),
+                        Syntax::String(Pos::No, b.into()),
+                    ],
+                ))
+            }),
+        );
+        assert_eq!(
+            store.read("(\"a\" \"b\")").unwrap(),
+            reader.read(store, "#pair\"a,b\"").unwrap()
+        );
+
+        // a failing handler fails the read at its literal, and other readers don't know the macro
+        assert!(matches!(
+            reader.read(store, "(1 #hex\"fg\")"),
+            Err(Error::Syntax { span, .. }) if span == (3..11)
+        ));
+        let ff = store.uint64(0xff);
+        assert_ne!(Some(ff), store.read("#hex\"ff\"").ok());
+        assert_ne!(Some(ff), Reader::default().read(store, "#hex\"ff\"").ok());
+    }
+
+    #[test]
+    fn syntax_error_spans() {
+        assert_eq!(0..4, syntax_error_span("\"abc"));
+        assert_eq!(8..14, syntax_error_span("(cons 1 \"ab cd"));
+
+        let too_big = "0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+        assert_eq!(0..66, syntax_error_span(too_big));
+        assert_eq!(5..71, syntax_error_span(&format!("(+ 1 {too_big})")));
+        assert_eq!(5..72, syntax_error_span(&format!("(+ 1 -{too_big})")));
+    }
+}
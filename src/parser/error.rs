@@ -16,6 +16,10 @@ pub enum ParseErrorKind<F: LurkField> {
     InvalidChar(String),
     Nom(ErrorKind),
     InterningError(String),
+    UnterminatedString,
+    UnterminatedComment,
+    /// A reader macro's handler rejected its literal, see `syntax::ReaderMacro`
+    ReaderMacro(String),
 }
 
 impl<F: LurkField> fmt::Display for ParseErrorKind<F> {
@@ -27,6 +31,16 @@ impl<F: LurkField> fmt::Display for ParseErrorKind<F> {
             Self::ParseIntErr(e) => {
                 write!(f, "Error parsing number: {}", e)
             }
+            Self::NumLiteralTooBig(_, max) => {
+                write!(
+                    f,
+                    "Number literal is larger than the field modulus, max is {max}"
+                )
+            }
+            Self::UnterminatedString => write!(f, "Unterminated string"),
+            Self::UnterminatedComment => write!(f, "Unterminated block comment"),
+            Self::NumError(e) => write!(f, "Error parsing number: {}", e),
+            Self::ReaderMacro(e) => write!(f, "Reader macro error: {}", e),
             e => write!(f, "internal parser error {:?}", e),
         }
     }
@@ -41,6 +55,8 @@ impl<F: LurkField> ParseErrorKind<F> {
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct ParseError<I: AsBytes, F: LurkField> {
     pub input: I,
+    /// Where the offending input ends, if known
+    pub upto: Option<I>,
     pub expected: Option<&'static str>,
     pub errors: Vec<ParseErrorKind<F>>,
 }
@@ -49,6 +65,17 @@ impl<I: AsBytes, F: LurkField> ParseError<I, F> {
     pub fn new(input: I, error: ParseErrorKind<F>) -> Self {
         ParseError {
             input,
+            upto: None,
+            expected: None,
+            errors: vec![error],
+        }
+    }
+
+    /// An error for the input from `input` up to `upto`
+    pub fn spanning(input: I, upto: I, error: ParseErrorKind<F>) -> Self {
+        ParseError {
+            input,
+            upto: Some(upto),
             expected: None,
             errors: vec![error],
         }
@@ -161,12 +188,14 @@ where
         match input.input_len().cmp(&other.input.input_len()) {
             Ordering::Less => ParseError {
                 input,
+                upto: None,
                 expected: Some(ctx),
                 errors: vec![],
             },
             Ordering::Equal => match other.expected {
                 None => ParseError {
                     input,
+                    upto: other.upto,
                     expected: Some(ctx),
                     errors: other.errors,
                 },
@@ -8,6 +8,7 @@ use nom::{
     error::context,
     multi::{many0, many_till, separated_list1},
     sequence::{delimited, preceded, terminated},
+    Slice,
 };
 use nom_locate::LocatedSpan;
 
@@ -21,27 +22,57 @@ use crate::{
         position::Pos,
         string, ParseResult, Span,
     },
-    state::{meta_package_symbol, State},
+    state::{lurk_sym, meta_package_symbol, State},
     symbol,
     syntax::Syntax,
     uint::UInt,
 };
 
 pub fn parse_line_comment<F: LurkField>(i: Span<'_>) -> ParseResult<'_, F, Span<'_>> {
-    let (i, _) = tag(";;")(i)?;
+    let (i, _) = tag(";")(i)?;
     let (i, com) = take_till(|c| c == '\n')(i)?;
     Ok((i, com))
 }
+
+/// Parses a `#| ... |#` block comment, which may nest other block comments, returning its contents
+pub fn parse_block_comment<'a, F: LurkField>(from: Span<'a>) -> ParseResult<'a, F, Span<'a>> {
+    let (mut i, _) = tag("#|")(from)?;
+    let mut depth = 1;
+    while depth > 0 {
+        if let Ok((rest, _)) = tag::<_, _, ParseError<Span<'a>, F>>("|#")(i) {
+            depth -= 1;
+            i = rest;
+        } else if let Ok((rest, _)) = tag::<_, _, ParseError<Span<'a>, F>>("#|")(i) {
+            depth += 1;
+            i = rest;
+        } else if let Ok((rest, _)) = anychar::<_, ParseError<Span<'a>, F>>(i) {
+            i = rest;
+        } else {
+            return Err(nom::Err::Failure(ParseError::spanning(
+                from,
+                i,
+                ParseErrorKind::UnterminatedComment,
+            )));
+        }
+    }
+    let len = i.location_offset() - from.location_offset();
+    Ok((i, from.slice(2..len - 2)))
+}
+
+pub fn parse_comment<F: LurkField>(i: Span<'_>) -> ParseResult<'_, F, Span<'_>> {
+    alt((parse_line_comment, parse_block_comment))(i)
+}
+
+/// Parses any whitespace and comments, returning the comments
 pub fn parse_space<F: LurkField>(i: Span<'_>) -> ParseResult<'_, F, Vec<Span<'_>>> {
     let (i, _) = multispace0(i)?;
-    let (i, com) = many0(terminated(parse_line_comment, multispace1))(i)?;
+    let (i, com) = many0(terminated(parse_comment, multispace0))(i)?;
     Ok((i, com))
 }
 
 pub fn parse_space1<F: LurkField>(i: Span<'_>) -> ParseResult<'_, F, Vec<Span<'_>>> {
-    let (i, _) = multispace1(i)?;
-    let (i, com) = many0(terminated(parse_line_comment, multispace1))(i)?;
-    Ok((i, com))
+    let (i, _) = alt((multispace1, peek(parse_comment)))(i)?;
+    parse_space(i)
 }
 
 pub fn parse_symbol_limb<F: LurkField>(
@@ -249,10 +280,11 @@ pub fn parse_num_inner<F: LurkField>(
         let max_bytes = (F::ZERO - F::ONE).to_bytes();
         let max_uint = num_bigint::BigUint::from_bytes_le(&max_bytes);
         if num_bigint::BigUint::from_bytes_le(&bytes) > max_uint {
-            ParseError::throw(
+            Err(nom::Err::Failure(ParseError::spanning(
                 from,
+                upto,
                 ParseErrorKind::NumLiteralTooBig(F::most_positive(), max_uint),
-            )
+            )))
         } else {
             let f = f_from_le_bytes::<F>(&bytes);
             if let Some(x) = f.to_u64() {
@@ -266,13 +298,28 @@ pub fn parse_num_inner<F: LurkField>(
 
 pub fn parse_num<F: LurkField>() -> impl Fn(Span<'_>) -> ParseResult<'_, F, Syntax<F>> {
     move |from: Span<'_>| {
-        let (i, neg) = opt(tag("-"))(from)?;
+        let (after_sign, neg) = opt(tag("-"))(from)?;
         let (i, base) = alt((
             preceded(tag("0"), base::parse_litbase_code()),
             success(base::LitBase::Dec),
-        ))(i)?;
-        let (i, num) = parse_num_inner(base)(i)?;
-        let (upto, denom) = opt(preceded(tag("/"), parse_num_inner(base)))(i)?;
+        ))(after_sign)?;
+        // a base code must be followed by digits, so `0x` is a malformed literal, not a symbol
+        let has_code = i.location_offset() > after_sign.location_offset();
+        if has_code && !i.chars().next().map_or(false, |c| base.is_digit(c)) {
+            return Err(nom::Err::Failure(ParseError::spanning(
+                from,
+                i,
+                ParseErrorKind::NumError(format!("expected digits after `0{}`", base.code())),
+            )));
+        }
+        // report literals that are too big from their start, including any sign and base code
+        let from_start = |e| match e {
+            nom::Err::Failure(e) => nom::Err::Failure(ParseError { input: from, ..e }),
+            e => e,
+        };
+        let (i, num) = parse_num_inner(base)(i).map_err(from_start)?;
+        let (upto, denom) =
+            opt(preceded(tag("/"), parse_num_inner(base)))(i).map_err(from_start)?;
         let pos = Pos::from_upto(from, upto);
         let mut tmp = Num::<F>::U64(0);
         if neg.is_some() {
@@ -289,9 +336,20 @@ pub fn parse_num<F: LurkField>() -> impl Fn(Span<'_>) -> ParseResult<'_, F, Synt
 
 pub fn parse_string<F: LurkField>() -> impl Fn(Span<'_>) -> ParseResult<'_, F, Syntax<F>> {
     move |from: Span<'_>| {
-        let (upto, s) = string::parse_string('"')(from)?;
-        let pos = Pos::from_upto(from, upto);
-        Ok((upto, Syntax::String(pos, s)))
+        let (i, _) = char('"')(from)?;
+        let (i, s) = string::parse_string_inner('"', true, "")(i)?;
+        match char('"')(i) {
+            Ok((upto, _)) => {
+                let pos = Pos::from_upto(from, upto);
+                Ok((upto, Syntax::String(pos, s)))
+            }
+            Err(_) if i.is_empty() => Err(nom::Err::Failure(ParseError::spanning(
+                from,
+                i,
+                ParseErrorKind::UnterminatedString,
+            ))),
+            Err(e) => Err(e),
+        }
     }
 }
 
@@ -321,8 +379,42 @@ pub fn parse_char<F: LurkField>() -> impl Fn(Span<'_>) -> ParseResult<'_, F, Syn
     }
 }
 
+/// How a reader macro reads the contents of the string literal following its prefix
+pub type ReaderMacro<F> = Box<dyn Fn(&str) -> Result<Syntax<F>, ParseErrorKind<F>>>;
+
+/// The prefixes of the reader macros in effect, with the handlers they dispatch to
+pub type ReaderMacros<F> = Rc<Vec<(String, ReaderMacro<F>)>>;
+
+/// Parses a literal of one of `macros`, its prefix immediately followed by a string literal, as
+/// the syntax its handler reads from the string. A failing handler fails the parse.
+pub fn parse_reader_macro<F: LurkField>(
+    macros: ReaderMacros<F>,
+) -> impl Fn(Span<'_>) -> ParseResult<'_, F, Syntax<F>> {
+    move |from: Span<'_>| {
+        for (prefix, handler) in macros.iter() {
+            let Ok((i, _)) = tag::<_, _, ParseError<Span<'_>, F>>(prefix.as_str())(from) else {
+                continue;
+            };
+            let (upto, contents) = match parse_string::<F>()(i) {
+                Ok((upto, Syntax::String(_, contents))) => (upto, contents),
+                Err(nom::Err::Failure(e)) => return Err(nom::Err::Failure(e)),
+                _ => continue,
+            };
+            let pos = Pos::from_upto(from, upto);
+            let len = upto.location_offset() - from.location_offset();
+            let literal = from.fragment()[..len].to_owned();
+            return match handler(&contents) {
+                Ok(syntax) => Ok((upto, Syntax::ReaderMacro(pos, literal, Box::new(syntax)))),
+                Err(e) => Err(nom::Err::Failure(ParseError::spanning(from, upto, e))),
+            };
+        }
+        ParseError::throw(from, ParseErrorKind::Nom(nom::error::ErrorKind::Tag))
+    }
+}
+
 pub fn parse_list<F: LurkField>(
     state: Rc<RefCell<State>>,
+    macros: ReaderMacros<F>,
     meta: bool,
     create_unknown_packages: bool,
 ) -> impl Fn(Span<'_>) -> ParseResult<'_, F, Syntax<F>> {
@@ -346,7 +438,12 @@ pub fn parse_list<F: LurkField>(
                 .expect("previous package is available");
             let (i, t) = many0(preceded(
                 parse_space,
-                parse_syntax(state.clone(), false, create_unknown_packages),
+                parse_syntax_with_macros(
+                    state.clone(),
+                    macros.clone(),
+                    false,
+                    create_unknown_packages,
+                ),
             ))(i)?;
             let mut xs = vec![h];
             xs.extend(t);
@@ -354,14 +451,24 @@ pub fn parse_list<F: LurkField>(
         } else {
             many0(preceded(
                 parse_space,
-                parse_syntax(state.clone(), false, create_unknown_packages),
+                parse_syntax_with_macros(
+                    state.clone(),
+                    macros.clone(),
+                    false,
+                    create_unknown_packages,
+                ),
             ))(i)?
         };
         let (i, end) = opt(preceded(
             preceded(parse_space, tag(".")),
             preceded(
                 parse_space,
-                parse_syntax(state.clone(), false, create_unknown_packages),
+                parse_syntax_with_macros(
+                    state.clone(),
+                    macros.clone(),
+                    false,
+                    create_unknown_packages,
+                ),
             ),
         ))(i)?;
         let (i, _) = parse_space(i)?;
@@ -377,6 +484,7 @@ pub fn parse_list<F: LurkField>(
 
 pub fn parse_quote<F: LurkField>(
     state: Rc<RefCell<State>>,
+    macros: ReaderMacros<F>,
     create_unknown_packages: bool,
 ) -> impl Fn(Span<'_>) -> ParseResult<'_, F, Syntax<F>> {
     move |from: Span<'_>| {
@@ -385,25 +493,73 @@ pub fn parse_quote<F: LurkField>(
             Ok((i, c))
         } else {
             let (i, _) = tag("'")(from)?;
-            let (upto, s) = parse_syntax(state.clone(), false, create_unknown_packages)(i)?;
+            let (upto, s) = parse_syntax_with_macros(
+                state.clone(),
+                macros.clone(),
+                false,
+                create_unknown_packages,
+            )(i)?;
             let pos = Pos::from_upto(from, upto);
             Ok((upto, Syntax::Quote(pos, Box::new(s))))
         }
     }
 }
 
+/// Parses the reader macros of quasiquotation. `,x` and `,@x` read as `(unquote x)` and
+/// `(unquote-splicing x)`, with both names in the Lurk package, and `` `x `` reads as the
+/// expression building `x` given by `Syntax::quasiquote`.
+pub fn parse_quasiquote<F: LurkField>(
+    state: Rc<RefCell<State>>,
+    macros: ReaderMacros<F>,
+    create_unknown_packages: bool,
+) -> impl Fn(Span<'_>) -> ParseResult<'_, F, Syntax<F>> {
+    move |from: Span<'_>| {
+        let (i, prefix) = alt((tag("`"), tag(",@"), tag(",")))(from)?;
+        let (upto, s) = parse_syntax_with_macros(
+            state.clone(),
+            macros.clone(),
+            false,
+            create_unknown_packages,
+        )(i)?;
+        let pos = Pos::from_upto(from, upto);
+        let syntax = match *prefix.fragment() {
+            "`" => Syntax::quasiquote(pos, s),
+            prefix => {
+                let name = if prefix == ",@" {
+                    "unquote-splicing"
+                } else {
+                    "unquote"
+                };
+                Syntax::List(pos, vec![Syntax::Symbol(pos, lurk_sym(name).into()), s])
+            }
+        };
+        Ok((upto, syntax))
+    }
+}
+
 // top-level syntax parser
 pub fn parse_syntax<F: LurkField>(
     state: Rc<RefCell<State>>,
     meta: bool,
     // this parameter triggers a less strict mode for testing purposes
     create_unknown_packages: bool,
+) -> impl Fn(Span<'_>) -> ParseResult<'_, F, Syntax<F>> {
+    parse_syntax_with_macros(state, Rc::default(), meta, create_unknown_packages)
+}
+
+/// Like `parse_syntax`, but also reading the literals of `macros`, in forms at any depth
+pub fn parse_syntax_with_macros<F: LurkField>(
+    state: Rc<RefCell<State>>,
+    macros: ReaderMacros<F>,
+    meta: bool,
+    create_unknown_packages: bool,
 ) -> impl Fn(Span<'_>) -> ParseResult<'_, F, Syntax<F>> {
     move |from: Span<'_>| {
         alt((
+            parse_reader_macro(macros.clone()),
             context(
                 "list",
-                parse_list(state.clone(), meta, create_unknown_packages),
+                parse_list(state.clone(), macros.clone(), meta, create_unknown_packages),
             ),
             parse_uint(),
             parse_num(),
@@ -412,7 +568,14 @@ pub fn parse_syntax<F: LurkField>(
                 parse_symbol(state.clone(), create_unknown_packages),
             ),
             parse_string(),
-            context("quote", parse_quote(state.clone(), create_unknown_packages)),
+            context(
+                "quote",
+                parse_quote(state.clone(), macros.clone(), create_unknown_packages),
+            ),
+            context(
+                "quasiquote",
+                parse_quasiquote(state.clone(), macros.clone(), create_unknown_packages),
+            ),
             parse_hash_char(),
         ))(from)
     }
@@ -675,27 +838,27 @@ pub mod tests {
         let state_ = State::default().rccell();
         let state = || state_.clone();
         assert!(test(
-            parse_list(state(), false, true),
+            parse_list(state(), Rc::default(), false, true),
             "()",
             Some(list!([]))
         ));
         assert!(test(
-            parse_list(state(), false, true),
+            parse_list(state(), Rc::default(), false, true),
             "(1 2)",
             Some(list!([num!(1), num!(2)])),
         ));
         assert!(test(
-            parse_list(state(), false, true),
+            parse_list(state(), Rc::default(), false, true),
             "(1)",
             Some(list!([num!(1)])),
         ));
         assert!(test(
-            parse_list(state(), false, true),
+            parse_list(state(), Rc::default(), false, true),
             "(a)",
             Some(list!([symbol!(["a"])])),
         ));
         assert!(test(
-            parse_list(state(), false, true),
+            parse_list(state(), Rc::default(), false, true),
             "(a b)",
             Some(list!([symbol!(["a"]), symbol!(["b"])])),
         ));
@@ -803,7 +966,7 @@ pub mod tests {
         let state_ = State::default().rccell();
         let state = || state_.clone();
         assert!(test(
-            parse_quote(state(), true),
+            parse_quote(state(), Rc::default(), true),
             "'.a",
             Some(Syntax::Quote(Pos::No, Box::new(symbol!(["a"]))))
         ));
@@ -817,8 +980,16 @@ pub mod tests {
             "'a",
             Some(Syntax::Quote(Pos::No, Box::new(symbol!(["a"]))))
         ));
-        assert!(test(parse_quote(state(), true), "'a'", Some(char!('a'))));
-        assert!(test(parse_quote(state(), true), "'a'", Some(char!('a'))));
+        assert!(test(
+            parse_quote(state(), Rc::default(), true),
+            "'a'",
+            Some(char!('a'))
+        ));
+        assert!(test(
+            parse_quote(state(), Rc::default(), true),
+            "'a'",
+            Some(char!('a'))
+        ));
         assert!(test(
             parse_syntax(state(), false, true),
             "'(a b)",
@@ -839,6 +1010,58 @@ pub mod tests {
         ));
     }
 
+    #[test]
+    fn unit_parse_quasiquote() {
+        let state_ = State::default().rccell();
+        let state = || state_.clone();
+        let form = |name: &str, x: Syntax<Scalar>| list!([symbol!(["lurk", name]), x]);
+        assert!(test(
+            parse_syntax(state(), false, true),
+            ",a",
+            Some(form("unquote", symbol!(["a"])))
+        ));
+        assert!(test(
+            parse_syntax(state(), false, true),
+            ",@(a)",
+            Some(form("unquote-splicing", list!([symbol!(["a"])])))
+        ));
+        // without unquotes, a quasiquotation is a quotation
+        assert!(test(
+            parse_syntax(state(), false, true),
+            "`(a b)",
+            Some(Syntax::Quote(
+                Pos::No,
+                Box::new(list!([symbol!(["a"]), symbol!(["b"])]))
+            ))
+        ));
+        assert!(test(
+            parse_syntax(state(), false, true),
+            "`,a",
+            Some(symbol!(["a"]))
+        ));
+        assert!(test(
+            parse_syntax(state(), false, true),
+            "`(,a)",
+            Some(list!([
+                symbol!(["lurk", "cons"]),
+                symbol!(["a"]),
+                symbol!(["lurk", "nil"])
+            ]))
+        ));
+        assert!(test(
+            parse_syntax(state(), false, true),
+            "`(,@a)",
+            Some(symbol!(["a"]))
+        ));
+        // backquotes in symbols are escaped
+        assert!(test(
+            parse_syntax(state(), false, true),
+            ".a\\`b",
+            Some(symbol!(["a`b"]))
+        ));
+        assert_eq!(".a\\`b", symbol!(Scalar, ["a`b"]).to_string());
+    }
+
     #[test]
     fn unit_parse_num() {
         assert!(test(parse_num(), "0", Some(num!(0))));
@@ -888,6 +1111,45 @@ pub mod tests {
         assert!(test(parse_num(), "-1/2", Some(Syntax::Num(Pos::No, tmp))));
     }
 
+    #[test]
+    fn unit_parse_num_bases() {
+        assert!(test(parse_num(), "0xFF", Some(num!(255))));
+        assert!(test(parse_num(), "0o17", Some(num!(15))));
+        assert!(test(parse_num(), "0b1010", Some(num!(10))));
+        assert!(test(
+            parse_num(),
+            "-0b1",
+            Some(num!(Num::Scalar(-Scalar::ONE)))
+        ));
+
+        // 2^64 doesn't fit in a u64, but is still a field element
+        let two_64 = Num::Scalar(Scalar::from(u64::MAX) + Scalar::from(1u64));
+        assert!(test(
+            parse_num(),
+            "0x1_0000_0000_0000_0000",
+            Some(num!(two_64))
+        ));
+        assert!(test(
+            parse_num(),
+            "0o2000000000000000000000",
+            Some(num!(two_64))
+        ));
+        assert!(test(
+            parse_num(),
+            &format!("0b1{}", "0".repeat(64)),
+            Some(num!(two_64))
+        ));
+
+        let state = State::default().rccell();
+        for malformed in ["0x", "0o", "0b", "0d", "0xg", "-0x", "(0b 1)"] {
+            assert!(test(
+                parse_syntax(state.clone(), false, true),
+                malformed,
+                None::<Syntax<Scalar>>
+            ));
+        }
+    }
+
     #[test]
     fn unit_parse_syntax_misc() {
         let vec: Vec<u8> = vec![
@@ -0,0 +1,155 @@
+use bellpepper_core::SynthesisError;
+use std::sync::Arc;
+
+use crate::circuit::MultiFrame;
+use crate::coprocessor::Coprocessor;
+use crate::error::ProofError;
+use crate::eval::{lang::Lang, Evaluator, Frame, Witness, IO};
+use crate::field::LurkField;
+use crate::proof::{verify_sequential_css, Prover, PublicParameters};
+use crate::ptr::Ptr;
+use crate::store::Store;
+
+/// The public parameters of the `DummyProver`, which needs none.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DummyParams;
+
+impl PublicParameters for DummyParams {}
+
+/// The token `DummyProver` returns in place of a SNARK. It records the statement whose circuits
+/// were found to be satisfied, and proves nothing to anyone who didn't run the prover.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DummyProof<F: LurkField> {
+    /// The number of multiframes that were checked.
+    pub num_steps: usize,
+    /// The public input of the first step.
+    pub z0: Vec<F>,
+    /// The public output of the last step.
+    pub zi: Vec<F>,
+}
+
+impl<F: LurkField> DummyProof<F> {
+    /// Accepts the statement the proof was made for.
+    pub fn verify(&self, num_steps: usize, z0: &[F], zi: &[F]) -> bool {
+        self.num_steps == num_steps && self.z0 == z0 && self.zi == zi
+    }
+}
+
+/// A prover which synthesizes the same circuits as the real backends into test constraint
+/// systems and checks that they are satisfied, but skips the SNARK. This exercises the circuit
+/// logic end to end at a fraction of the cost of proving.
+#[derive(Debug)]
+pub struct DummyProver<F: LurkField, C: Coprocessor<F>> {
+    reduction_count: usize,
+    lang: Lang<F, C>,
+}
+
+impl<'a, F: LurkField, C: Coprocessor<F> + 'a> Prover<'a, '_, F, C> for DummyProver<F, C> {
+    type PublicParams = DummyParams;
+
+    fn new(reduction_count: usize, lang: Lang<F, C>) -> Self {
+        Self {
+            reduction_count,
+            lang,
+        }
+    }
+
+    fn reduction_count(&self) -> usize {
+        self.reduction_count
+    }
+
+    fn lang(&self) -> &Lang<F, C> {
+        &self.lang
+    }
+}
+
+impl<F: LurkField, C: Coprocessor<F>> DummyProver<F, C> {
+    /// Checks the circuits for `frames`, whose store must have its scalar cache hydrated. Returns
+    /// `SynthesisError::Unsatisfiable` if any constraint system isn't satisfied, or if the
+    /// multiframes don't follow each other, and `ProofError::NoFrames` if there are none.
+    pub fn prove(
+        &self,
+        frames: &[Frame<IO<F>, Witness<F>, C>],
+        store: &Store<F>,
+    ) -> Result<(DummyProof<F>, Vec<F>, Vec<F>, usize), ProofError> {
+        let (Some(first), Some(last)) = (frames.first(), frames.last()) else {
+            return Err(ProofError::NoFrames);
+        };
+        let z0 = first.input.to_vector(store)?;
+        let zi = last.output.to_vector(store)?;
+        let lang = Arc::new(self.lang.clone());
+        let multiframes = MultiFrame::from_frames(self.reduction_count, frames, store, lang);
+        let css = self.outer_synthesize(&multiframes)?;
+        if !verify_sequential_css(&css)? {
+            return Err(SynthesisError::Unsatisfiable.into());
+        }
+        let num_steps = multiframes.len();
+        let proof = DummyProof {
+            num_steps,
+            z0: z0.clone(),
+            zi: zi.clone(),
+        };
+        Ok((proof, z0, zi, num_steps))
+    }
+
+    /// Evaluates `expr` and checks the circuits for its frames.
+    pub fn evaluate_and_prove(
+        &self,
+        expr: Ptr<F>,
+        env: Ptr<F>,
+        store: &mut Store<F>,
+        limit: usize,
+    ) -> Result<(DummyProof<F>, Vec<F>, Vec<F>, usize), ProofError> {
+        let padding_predicate = |count| self.needs_frame_padding(count);
+        let frames =
+            Evaluator::generate_frames(expr, env, store, limit, padding_predicate, &self.lang)?;
+        store.hydrate_scalar_cache();
+        self.prove(&frames, store)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::{empty_sym_env, lang::Coproc};
+    use blstrs::Scalar as Fr;
+
+    #[test]
+    fn dummy_prove_and_verify() {
+        let s = &mut Store::<Fr>::default();
+        let prover = DummyProver::<Fr, Coproc<Fr>>::new(2, Lang::new());
+        let expr = s
+            .read("(letrec ((fact (lambda (n) (if (= n 0) 1 (* n (fact (- n 1))))))) (fact 3))")
+            .unwrap();
+        let env = empty_sym_env(s);
+
+        let (proof, z0, zi, num_steps) = prover.evaluate_and_prove(expr, env, s, 1000).unwrap();
+        assert!(proof.verify(num_steps, &z0, &zi));
+        assert!(!proof.verify(num_steps, &z0, &z0));
+    }
+
+    #[test]
+    fn dummy_prove_without_frames() {
+        let s = &mut Store::<Fr>::default();
+        let prover = DummyProver::<Fr, Coproc<Fr>>::new(1, Lang::new());
+        assert!(matches!(prover.prove(&[], s), Err(ProofError::NoFrames)));
+    }
+
+    #[test]
+    fn dummy_prove_rejects_wrong_output() {
+        let s = &mut Store::<Fr>::default();
+        let lang = Lang::<Fr, Coproc<Fr>>::new();
+        let prover = DummyProver::new(1, lang.clone());
+        let expr = s.read("(+ 1 2)").unwrap();
+        let env = empty_sym_env(s);
+
+        let mut frames = Evaluator::generate_frames(expr, env, s, 1000, |_| false, &lang).unwrap();
+        // claim that the last step outputs something the circuit doesn't compute
+        frames.last_mut().unwrap().output.expr = s.num(4);
+        s.hydrate_scalar_cache();
+        assert!(matches!(
+            prover.prove(&frames, s),
+            Err(ProofError::Synthesis(SynthesisError::Unsatisfiable))
+        ));
+    }
+}
@@ -5,10 +5,14 @@
 //! has two instantiations:
 //! - the Groth16/SnarkPack proving system, implemented in the `groth16` module
 //! - the Nova proving system, implemented in the `nova` module.
+/// A prover that only checks constraint satisfaction, for fast testing.
+pub mod dummy;
 /// An adapter to a Groth16 proving system implementation.
 pub mod groth16;
 /// An adapter to a Nova proving system implementation.
 pub mod nova;
+/// Circuit selection for SuperNova's non-uniform folding.
+pub mod supernova;
 
 use crate::circuit::MultiFrame;
 use crate::coprocessor::Coprocessor;
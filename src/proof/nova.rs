@@ -1,6 +1,8 @@
 #![allow(non_snake_case)]
 
+#[cfg(feature = "prover")]
 use std::marker::PhantomData;
+#[cfg(feature = "prover")]
 use std::sync::Mutex;
 
 use abomonation::Abomonation;
@@ -19,8 +21,10 @@ use nova::{
     CompressedSNARK, ProverKey, RecursiveSNARK, VerifierKey,
 };
 use pasta_curves::{pallas, vesta};
+#[cfg(feature = "prover")]
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
 
 use crate::circuit::{
@@ -30,15 +34,23 @@ use crate::circuit::{
     },
     CircuitFrame, MultiFrame,
 };
-use crate::config::CONFIG;
+#[cfg(feature = "prover")]
+use crate::config::{Flow, CONFIG};
 
 use crate::coprocessor::Coprocessor;
 use crate::error::ProofError;
-use crate::eval::{lang::Lang, Evaluator, Frame, Witness, IO};
-use crate::field::LurkField;
-use crate::proof::{Prover, PublicParameters};
+use crate::eval::{lang::Lang, Witness, IO};
+#[cfg(feature = "prover")]
+use crate::eval::{Evaluator, Frame};
+use crate::field::{FWrap, LurkField};
+#[cfg(feature = "prover")]
+use crate::proof::Prover;
+use crate::proof::PublicParameters;
+#[cfg(feature = "prover")]
 use crate::ptr::Ptr;
 use crate::store::Store;
+use crate::tag::{ExprTag, Tag};
+use crate::z_ptr::ZExprPtr;
 
 /// This trait defines most of the requirements for programming generically over the supported Nova curve cycles
 /// (currently Pallas/Vesta and BN254/Grumpkin). It being pegged on the `LurkField` trait encodes that we do
@@ -164,20 +176,97 @@ where
     }
 }
 
+impl<'a, F: CurveCycleEquipped, C: Coprocessor<F>> PublicParams<'a, F, C>
+where
+    <<G1<F> as Group>::Scalar as ff::PrimeField>::Repr: Abomonation,
+    <<G2<F> as Group>::Scalar as ff::PrimeField>::Repr: Abomonation,
+{
+    /// A SHA-256 digest of the verifier key, identifying these parameters in serialized proofs
+    pub fn digest(&self) -> [u8; 32] {
+        let vk = bincode::serialize(&self.vk).expect("verifier key is serializable");
+        Sha256::digest(vk).into()
+    }
+}
+
+/// The version of the layout written by `Proof::to_bytes`
+const PROOF_FORMAT_VERSION: u8 = 2;
+
+/// What a proof claims: that `num_steps` Nova steps take the public input, the hashes of an
+/// expression, environment and continuation, to the public output. A proof only backs its claim
+/// once it verifies against it, see `Proof::verify_claim`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Claim<F: LurkField> {
+    z0: Vec<FWrap<F>>,
+    zi: Vec<FWrap<F>>,
+    num_steps: usize,
+}
+
+impl<F: LurkField> Claim<F> {
+    #[cfg(feature = "prover")]
+    fn new(z0: &[F], zi: &[F], num_steps: usize) -> Self {
+        Self {
+            z0: z0.iter().copied().map(FWrap).collect(),
+            zi: zi.iter().copied().map(FWrap).collect(),
+            num_steps,
+        }
+    }
+
+    /// The expression the evaluation starts from, or `None` if the claimed tag isn't that of an
+    /// expression, which no proof of an evaluation has
+    pub fn input(&self) -> Option<ZExprPtr<F>> {
+        Self::expr(&self.z0)
+    }
+
+    /// The expression the evaluation reaches, see `input`
+    pub fn output(&self) -> Option<ZExprPtr<F>> {
+        Self::expr(&self.zi)
+    }
+
+    pub fn num_steps(&self) -> usize {
+        self.num_steps
+    }
+
+    /// The public input, as `Proof::verify` takes it
+    pub fn z0(&self) -> Vec<F> {
+        self.z0.iter().map(|f| f.0).collect()
+    }
+
+    /// The public output, as `Proof::verify` takes it
+    pub fn zi(&self) -> Vec<F> {
+        self.zi.iter().map(|f| f.0).collect()
+    }
+
+    fn expr(z: &[FWrap<F>]) -> Option<ZExprPtr<F>> {
+        let tag = ExprTag::from_field(&z.first()?.0)?;
+        Some(ZExprPtr::from_parts(tag, z.get(1)?.0))
+    }
+}
+
 /// An enum representing the two types of proofs that can be generated and verified.
 #[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
 pub enum Proof<'a, F: CurveCycleEquipped, C: Coprocessor<F>>
 where
     <<G1<F> as Group>::Scalar as ff::PrimeField>::Repr: Abomonation,
     <<G2<F> as Group>::Scalar as ff::PrimeField>::Repr: Abomonation,
 {
     /// A proof for the intermediate steps of a recursive computation
-    Recursive(Box<RecursiveSNARK<G1<F>, G2<F>, C1<'a, F, C>, C2<F>>>),
+    Recursive(
+        Box<RecursiveSNARK<G1<F>, G2<F>, C1<'a, F, C>, C2<F>>>,
+        Claim<F>,
+    ),
     /// A proof for the final step of a recursive computation
-    Compressed(Box<CompressedSNARK<G1<F>, G2<F>, C1<'a, F, C>, C2<F>, SS1<F>, SS2<F>>>),
+    Compressed(
+        Box<CompressedSNARK<G1<F>, G2<F>, C1<'a, F, C>, C2<F>, SS1<F>, SS2<F>>>,
+        Claim<F>,
+    ),
 }
 
 /// Generates the public parameters for the Nova proving system.
+///
+/// A build without the `prover` feature still generates them in full, since Nova only derives
+/// the verifier key along with the prover key.
 pub fn public_params<'a, F: CurveCycleEquipped, C: Coprocessor<F>>(
     num_iters_per_step: usize,
     lang: Arc<Lang<F, C>>,
@@ -203,6 +292,7 @@ impl<'a, F: CurveCycleEquipped, C: Coprocessor<F>> C1<'a, F, C> {
 }
 
 /// A struct for the Nova prover that operates on field elements of type `F`.
+#[cfg(feature = "prover")]
 #[derive(Debug)]
 pub struct NovaProver<F: CurveCycleEquipped, C: Coprocessor<F>> {
     // `reduction_count` specifies the number of small-step reductions are performed in each recursive step.
@@ -218,6 +308,7 @@ where
 {
 }
 
+#[cfg(feature = "prover")]
 impl<'a, F: CurveCycleEquipped, C: Coprocessor<F> + 'a> Prover<'a, '_, F, C> for NovaProver<F, C>
 where
     <<G1<F> as Group>::Scalar as ff::PrimeField>::Repr: Abomonation,
@@ -240,6 +331,7 @@ where
     }
 }
 
+#[cfg(feature = "prover")]
 impl<F: CurveCycleEquipped, C: Coprocessor<F>> NovaProver<F, C>
 where
     <<G1<F> as Group>::Scalar as ff::PrimeField>::Repr: Abomonation,
@@ -297,13 +389,13 @@ where
     }
 }
 
+#[cfg(feature = "prover")]
 impl<'a, F: LurkField, C: Coprocessor<F>> MultiFrame<'a, F, IO<F>, Witness<F>, C> {
     fn compute_witness(&self, s: &Store<F>) -> WitnessCS<F> {
         let mut wcs = WitnessCS::new();
 
         let input = self.input.unwrap();
 
-        use crate::tag::Tag;
         let expr = s.hash_expr(&input.expr).unwrap();
         let env = s.hash_expr(&input.env).unwrap();
         let cont = s.hash_cont(&input.cont).unwrap();
@@ -411,6 +503,7 @@ where
     <<G2<F> as Group>::Scalar as ff::PrimeField>::Repr: Abomonation,
 {
     /// Proves the computation recursively, generating a recursive SNARK proof.
+    #[cfg(feature = "prover")]
     pub fn prove_recursively(
         pp: &'a PublicParams<'_, F, C>,
         store: &'a Store<F>,
@@ -418,10 +511,35 @@ where
         num_iters_per_step: usize,
         z0: Vec<F>,
         lang: Arc<Lang<F, C>>,
+    ) -> Result<Self, ProofError> {
+        Self::prove_recursively_with_flow(
+            pp,
+            store,
+            circuits,
+            num_iters_per_step,
+            z0,
+            lang,
+            &CONFIG.parallelism.recursive_steps,
+        )
+    }
+
+    /// Like `prove_recursively`, but `flow` rather than the global config decides whether the
+    /// witnesses of later steps are computed in parallel while earlier ones are folded. Folding
+    /// itself is always sequential, so the proof is the same either way.
+    #[cfg(feature = "prover")]
+    pub fn prove_recursively_with_flow(
+        pp: &'a PublicParams<'_, F, C>,
+        store: &'a Store<F>,
+        circuits: &[C1<'a, F, C>],
+        num_iters_per_step: usize,
+        z0: Vec<F>,
+        lang: Arc<Lang<F, C>>,
+        flow: &Flow,
     ) -> Result<Self, ProofError> {
         assert!(!circuits.is_empty());
         assert_eq!(circuits[0].arity(), z0.len());
         let debug = false;
+        let claim = Claim::new(&z0, &Self::claimed_output(circuits)?, circuits.len());
         let z0_primary = z0;
         let z0_secondary = Self::z0_secondary();
 
@@ -440,7 +558,7 @@ where
         let mut recursive_snark: Option<RecursiveSNARK<G1<F>, G2<F>, C1<'a, F, C>, C2<F>>> = None;
 
         // the shadowing here is voluntary
-        let recursive_snark = if CONFIG.parallelism.recursive_steps.is_parallel() {
+        let recursive_snark = if flow.is_parallel() {
             let cc = circuits
                 .iter()
                 .map(|c| Mutex::new(c.clone()))
@@ -542,28 +660,82 @@ where
             recursive_snark
         };
 
-        Ok(Self::Recursive(Box::new(recursive_snark.unwrap())))
+        Ok(Self::Recursive(Box::new(recursive_snark.unwrap()), claim))
+    }
+
+    /// The public output of the last of `circuits`, which a proof of them ends in
+    #[cfg(feature = "prover")]
+    fn claimed_output(circuits: &[C1<'a, F, C>]) -> Result<Vec<F>, ProofError> {
+        let last = circuits.last().expect("there are circuits to prove");
+        let output = last.output.expect("circuits to prove aren't blank");
+        Ok(output.to_vector(last.get_store())?)
+    }
+
+    /// Folds the steps of more `circuits` onto a recursive proof, so that it proves its previous
+    /// steps followed by these ones, just as if they had all been proven at once. `z0` is the
+    /// input the proof started from.
+    #[cfg(feature = "prover")]
+    pub fn extend(
+        &mut self,
+        pp: &'a PublicParams<'_, F, C>,
+        circuits: &[C1<'a, F, C>],
+        z0: &[F],
+    ) -> Result<(), ProofError> {
+        let Self::Recursive(recursive_snark, claim) = self else {
+            return Err(ProofError::NotRecursive);
+        };
+        let z0_secondary = Self::z0_secondary();
+        let circuit_secondary = C2::<F>::default();
+        for circuit_primary in circuits {
+            recursive_snark.prove_step(
+                &pp.pp,
+                circuit_primary,
+                &circuit_secondary,
+                z0.to_vec(),
+                z0_secondary.clone(),
+            )?;
+        }
+        if !circuits.is_empty() {
+            *claim = Claim::new(
+                z0,
+                &Self::claimed_output(circuits)?,
+                claim.num_steps + circuits.len(),
+            );
+        }
+        Ok(())
     }
 
     /// Compresses the proof using a (Spartan) Snark (finishing step)
+    #[cfg(feature = "prover")]
     pub fn compress(self, pp: &'a PublicParams<'_, F, C>) -> Result<Self, ProofError> {
         match &self {
-            Self::Recursive(recursive_snark) => Ok(Self::Compressed(Box::new(CompressedSNARK::<
-                _,
-                _,
-                _,
-                _,
-                SS1<F>,
-                SS2<F>,
-            >::prove(
-                &pp.pp,
-                &pp.pk,
-                recursive_snark,
-            )?))),
-            Self::Compressed(_) => Ok(self),
+            Self::Recursive(recursive_snark, claim) => Ok(Self::Compressed(
+                Box::new(CompressedSNARK::<_, _, _, _, SS1<F>, SS2<F>>::prove(
+                    &pp.pp,
+                    &pp.pk,
+                    recursive_snark,
+                )?),
+                claim.clone(),
+            )),
+            Self::Compressed(..) => Ok(self),
         }
     }
 
+    /// The input, output and number of steps the proof was made for. A verifier learns from the
+    /// claim what computation it's checking, but only `verify_claim` tells whether the proof
+    /// actually backs it.
+    pub fn claim(&self) -> Claim<F> {
+        match self {
+            Self::Recursive(_, claim) | Self::Compressed(_, claim) => claim.clone(),
+        }
+    }
+
+    /// Verifies the proof against its own claim, see `claim`
+    pub fn verify_claim(&self, pp: &PublicParams<'_, F, C>) -> Result<bool, NovaError> {
+        let claim = self.claim();
+        self.verify(pp, claim.num_steps(), &claim.z0(), &claim.zi())
+    }
+
     /// Verifies the proof given the public parameters, the number of steps, and the input and output values.
     pub fn verify(
         &self,
@@ -577,19 +749,52 @@ where
         let zi_secondary = z0_secondary.clone();
 
         let (zi_primary_verified, zi_secondary_verified) = match self {
-            Self::Recursive(p) => p.verify(&pp.pp, num_steps, z0_primary, &z0_secondary),
-            Self::Compressed(p) => p.verify(&pp.vk, num_steps, z0_primary.to_vec(), z0_secondary),
+            Self::Recursive(p, _) => p.verify(&pp.pp, num_steps, z0_primary, &z0_secondary),
+            Self::Compressed(p, _) => {
+                p.verify(&pp.vk, num_steps, z0_primary.to_vec(), z0_secondary)
+            }
         }?;
 
         Ok(zi_primary == zi_primary_verified && zi_secondary == zi_secondary_verified)
     }
 
+    /// Serializes the proof for storage or transmission. The layout is a format version byte,
+    /// then the digest of `pp`, then the bincode-encoded proof.
+    pub fn to_bytes(&self, pp: &PublicParams<'_, F, C>) -> Result<Vec<u8>, ProofError> {
+        let mut bytes = vec![PROOF_FORMAT_VERSION];
+        bytes.extend_from_slice(&pp.digest());
+        bincode::serialize_into(&mut bytes, self)
+            .map_err(|e| ProofError::Serialization(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// Reads a proof written by `to_bytes`, failing with `ProofError::ParamsMismatch` if it was
+    /// created with public parameters other than `pp`.
+    pub fn from_bytes(bytes: &[u8], pp: &PublicParams<'_, F, C>) -> Result<Self, ProofError> {
+        let Some((&version, rest)) = bytes.split_first() else {
+            return Err(ProofError::Serialization("empty proof".into()));
+        };
+        if version != PROOF_FORMAT_VERSION {
+            return Err(ProofError::Serialization(format!(
+                "unsupported proof format version {version}"
+            )));
+        }
+        if rest.len() < 32 {
+            return Err(ProofError::Serialization("truncated proof".into()));
+        }
+        let (digest, proof) = rest.split_at(32);
+        if digest != pp.digest() {
+            return Err(ProofError::ParamsMismatch);
+        }
+        bincode::deserialize(proof).map_err(|e| ProofError::Serialization(e.to_string()))
+    }
+
     fn z0_secondary() -> Vec<<F::G2 as Group>::Scalar> {
         vec![<G2<F> as Group>::Scalar::ZERO]
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 pub mod tests {
     use std::cell::RefCell;
     use std::rc::Rc;
@@ -599,8 +804,8 @@ pub mod tests {
     use crate::state::{user_sym, State};
 
     use super::*;
-    use crate::eval::empty_sym_env;
     use crate::eval::lang::Coproc;
+    use crate::eval::{empty_sym_env, reduce_reference, Status};
     use crate::proof::Provable;
     use crate::ptr::ContPtr;
     use crate::tag::{Op, Op1, Op2};
@@ -813,6 +1018,233 @@ pub mod tests {
         assert_eq!(adjusted_iterations, len);
     }
 
+    /// Runs `expr` to completion with `reduce_reference`, checks its result against `expected`,
+    /// read from source, or against an error for `None`, and checks that chaining the outputs the
+    /// circuit computes for each `MultiFrame` arrives at the same final `IO`. The circuit's witness
+    /// comes from `reduce_reference` too, so only `expected` is independent of it.
+    fn differential_test_aux(
+        s: &mut Store<Fr>,
+        expr: &str,
+        expected: Option<&str>,
+        reduction_count: usize,
+    ) {
+        let limit = 10000;
+        let lang = Arc::new(Lang::<Fr, Coproc<Fr>>::new());
+        let expr = s.read(expr).unwrap();
+        let env = empty_sym_env(s);
+
+        let mut reference = IO {
+            expr,
+            env,
+            cont: s.intern_cont_outermost(),
+        };
+        for _ in 0..limit {
+            if Status::from(reference.cont).is_complete() {
+                break;
+            }
+            reference =
+                reduce_reference(reference.expr, reference.env, reference.cont, s, &lang).unwrap();
+        }
+        match expected {
+            Some(expected) => {
+                let expected = s.read(expected).unwrap();
+                assert_eq!(s.get_cont_terminal(), reference.cont);
+                assert!(s.ptr_eq(&expected, &reference.expr).unwrap());
+            }
+            None => assert_eq!(s.get_cont_error(), reference.cont),
+        }
+
+        let nova_prover = NovaProver::<Fr, Coproc<Fr>>::new(reduction_count, (*lang).clone());
+        let frames = nova_prover
+            .get_evaluation_frames(expr, env, s, limit, &lang)
+            .unwrap();
+        let multiframes = MultiFrame::from_frames(reduction_count, &frames, s, lang.clone());
+
+        let mut z = multiframes[0].input.unwrap().to_vector(s).unwrap();
+        for multiframe in &multiframes {
+            let mut cs = TestConstraintSystem::new();
+            let allocated_z = z
+                .iter()
+                .enumerate()
+                .map(|(i, x)| AllocatedNum::alloc(cs.namespace(|| format!("z{i}")), || Ok(*x)))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            let output = StepCircuit::synthesize(multiframe, &mut cs, &allocated_z).unwrap();
+            assert!(cs.is_satisfied());
+            z = output.iter().map(|x| x.get_value().unwrap()).collect();
+        }
+
+        assert_eq!(reference.to_vector(s).unwrap(), z);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_reference_matches_circuit() {
+        let corpus = [
+            ("(+ 1 2)", Some("3")),
+            ("(let ((x 3) (y 4)) (* x y))", Some("12")),
+            ("((lambda (x) (cons x x)) 'a)", Some("(a . a)")),
+            (
+                "(letrec ((fact (lambda (n) (if (= n 0) 1 (* n (fact (- n 1))))))) (fact 4))",
+                Some("24"),
+            ),
+            ("(car (cdr '(1 2 3)))", Some("2")),
+            ("(strcons #\\a \"bc\")", Some("\"abc\"")),
+            ("(eq (cons 1 2) (cons 1 2))", Some("t")),
+            ("(begin (emit 1) (emit 2))", Some("2")),
+            ("(/ 1 0)", None),
+            ("(car 1)", None),
+            ("undefined-symbol", None),
+        ];
+        let s = &mut Store::<Fr>::default();
+        for (expr, expected) in corpus {
+            for reduction_count in REDUCTION_COUNTS_TO_TEST {
+                differential_test_aux(s, expr, expected, reduction_count);
+            }
+        }
+    }
+
+    #[test]
+    fn test_proof_bytes_roundtrip() {
+        let s = &mut Store::<Fr>::default();
+        let expr = s.read("(+ 1 2)").unwrap();
+        let lang = Arc::new(Lang::<Fr, Coproc<Fr>>::new());
+        let nova_prover =
+            NovaProver::<Fr, Coproc<Fr>>::new(DEFAULT_REDUCTION_COUNT, (*lang).clone());
+        let pp = public_params(DEFAULT_REDUCTION_COUNT, lang.clone());
+        let (proof, z0, zi, num_steps) = nova_prover
+            .evaluate_and_prove(&pp, expr, empty_sym_env(s), s, 10, lang)
+            .unwrap();
+
+        let bytes = proof.to_bytes(&pp).unwrap();
+        let loaded = Proof::from_bytes(&bytes, &pp).unwrap();
+        assert!(loaded.verify(&pp, num_steps, &z0, &zi).unwrap());
+
+        let mut corrupted = bytes.clone();
+        corrupted[1] ^= 1;
+        assert!(matches!(
+            Proof::from_bytes(&corrupted, &pp),
+            Err(ProofError::ParamsMismatch)
+        ));
+
+        let mut future = bytes;
+        future[0] = PROOF_FORMAT_VERSION + 1;
+        assert!(matches!(
+            Proof::from_bytes(&future, &pp),
+            Err(ProofError::Serialization(_))
+        ));
+    }
+
+    #[test]
+    fn test_proof_claim() {
+        let s = &mut Store::<Fr>::default();
+        let expr = s.read("(+ 1 2)").unwrap();
+        let lang = Arc::new(Lang::<Fr, Coproc<Fr>>::new());
+        let nova_prover =
+            NovaProver::<Fr, Coproc<Fr>>::new(DEFAULT_REDUCTION_COUNT, (*lang).clone());
+        let pp = public_params(DEFAULT_REDUCTION_COUNT, lang.clone());
+        // the proof borrows the store, so the expected hashes are taken first
+        let input = s.hash_expr(&expr);
+        let three = s.num(3);
+        let output = s.hash_expr(&three);
+        let (proof, z0, zi, num_steps) = nova_prover
+            .evaluate_and_prove(&pp, expr, empty_sym_env(s), s, 10, lang)
+            .unwrap();
+
+        let claim = proof.claim();
+        assert_eq!(
+            (z0, zi, num_steps),
+            (claim.z0(), claim.zi(), claim.num_steps())
+        );
+        assert_eq!(input, claim.input());
+        assert_eq!(output, claim.output());
+        assert!(proof.verify_claim(&pp).unwrap());
+
+        // the claim survives compression and serialization
+        let compressed = proof.compress(&pp).unwrap();
+        assert_eq!(claim, compressed.claim());
+        assert!(compressed.verify_claim(&pp).unwrap());
+        let loaded = Proof::from_bytes(&compressed.to_bytes(&pp).unwrap(), &pp).unwrap();
+        assert_eq!(claim, loaded.claim());
+    }
+
+    #[test]
+    fn test_extend_recursive_proof() {
+        let s = &mut Store::<Fr>::default();
+        let expr = s
+            .read("(letrec ((fact (lambda (n) (if (= n 0) 1 (* n (fact (- n 1))))))) (fact 2))")
+            .unwrap();
+        let reduction_count = 1;
+        let lang = Arc::new(Lang::<Fr, Coproc<Fr>>::new());
+        let nova_prover = NovaProver::<Fr, Coproc<Fr>>::new(reduction_count, (*lang).clone());
+        let pp = public_params(reduction_count, lang.clone());
+        let frames = nova_prover
+            .get_evaluation_frames(expr, empty_sym_env(s), s, 100, &lang)
+            .unwrap();
+        let circuits = MultiFrame::from_frames(reduction_count, &frames, s, lang.clone());
+        assert!(circuits.len() >= 8);
+        let z0 = frames[0].input.to_vector(s).unwrap();
+        let zi = circuits[7].output.unwrap().to_vector(s).unwrap();
+
+        let mut proof = Proof::prove_recursively(
+            &pp,
+            s,
+            &circuits[..5],
+            reduction_count,
+            z0.clone(),
+            lang.clone(),
+        )
+        .unwrap();
+        proof.extend(&pp, &circuits[5..8], &z0).unwrap();
+        assert!(proof.verify(&pp, 8, &z0, &zi).unwrap());
+
+        let direct =
+            Proof::prove_recursively(&pp, s, &circuits[..8], reduction_count, z0.clone(), lang)
+                .unwrap();
+        assert_eq!(
+            bincode::serialize(&direct).unwrap(),
+            bincode::serialize(&proof).unwrap()
+        );
+
+        let mut compressed = direct.compress(&pp).unwrap();
+        assert!(matches!(
+            compressed.extend(&pp, &circuits[8..], &z0),
+            Err(ProofError::NotRecursive)
+        ));
+    }
+
+    #[test]
+    fn test_parallel_witnesses_prove_the_same() {
+        let s = &mut Store::<Fr>::default();
+        let expr = s
+            .read("(let ((x 1) (y 2)) (cons (+ x y) (* x y)))")
+            .unwrap();
+        let reduction_count = 2;
+        let lang = Arc::new(Lang::<Fr, Coproc<Fr>>::new());
+        let nova_prover = NovaProver::<Fr, Coproc<Fr>>::new(reduction_count, (*lang).clone());
+        let pp = public_params(reduction_count, lang.clone());
+        let frames = nova_prover
+            .get_evaluation_frames(expr, empty_sym_env(s), s, 100, &lang)
+            .unwrap();
+        let circuits = MultiFrame::from_frames(reduction_count, &frames, s, lang.clone());
+        let z0 = frames[0].input.to_vector(s).unwrap();
+
+        let [sequential, parallel] = [Flow::Sequential, Flow::ParallelN(4)].map(|flow| {
+            let proof = Proof::prove_recursively_with_flow(
+                &pp,
+                s,
+                &circuits,
+                reduction_count,
+                z0.clone(),
+                lang.clone(),
+                &flow,
+            )
+            .unwrap();
+            bincode::serialize(&proof).unwrap()
+        });
+        assert_eq!(sequential, parallel);
+    }
+
     // IMPORTANT: Run next tests at least once. Some are ignored because they
     // are expensive. The criteria is that if the number of iteractions is
     // more than 30 we ignore it.
@@ -3555,6 +3987,20 @@ pub mod tests {
         test_aux::<Coproc<Fr>>(s, expr2, Some(res), None, Some(terminal), None, 6, None);
     }
 
+    #[test]
+    fn test_prove_u64_add_wraps_unlike_num() {
+        let s = &mut Store::<Fr>::default();
+
+        let expr = "(+ 18446744073709551615u64 1u64)";
+        let expr2 = "(+ 18446744073709551615 1)";
+        let res = s.uint64(0);
+        let res2 = s.num(Num::Scalar(Fr::from(u64::MAX) + Fr::from(1)));
+        let terminal = s.get_cont_terminal();
+
+        test_aux::<Coproc<Fr>>(s, expr, Some(res), None, Some(terminal), None, 3, None);
+        test_aux::<Coproc<Fr>>(s, expr2, Some(res2), None, Some(terminal), None, 3, None);
+    }
+
     #[test]
     fn test_prove_test_u64_sub() {
         let s = &mut Store::<Fr>::default();
@@ -3612,6 +4058,79 @@ pub mod tests {
         test_aux::<Coproc<Fr>>(s, expr3, None, None, Some(error), None, 3, None);
     }
 
+    #[test]
+    fn test_prove_u64_bitwise() {
+        let s = &mut Store::<Fr>::default();
+        let terminal = s.get_cont_terminal();
+        let error = s.get_cont_error();
+
+        let (a, b) = (0xdead_beef_0123_4567u64, 0xf0f0_f0f0_ffff_0000u64);
+        for (expr, expected) in [
+            (format!("(bit-and {a}u64 {b}u64)"), a & b),
+            (format!("(bit-or {a}u64 {b}u64)"), a | b),
+            (format!("(bit-xor {a}u64 {b}u64)"), a ^ b),
+            (format!("(shl {a}u64 13u64)"), a << 13),
+            (format!("(shr {a}u64 13u64)"), a >> 13),
+            (format!("(shl {a}u64 64u64)"), 0),
+            (format!("(shr {a}u64 {b}u64)"), 0),
+        ] {
+            let res = s.uint64(expected);
+            test_aux::<Coproc<Fr>>(s, &expr, Some(res), None, Some(terminal), None, 3, None);
+        }
+        test_aux::<Coproc<Fr>>(s, "(bit-xor 6 3)", None, None, Some(error), None, 3, None);
+        test_aux::<Coproc<Fr>>(s, "(shl 6u64 3)", None, None, Some(error), None, 3, None);
+    }
+
+    #[test]
+    fn test_prove_lambda_rest_args() {
+        let s = &mut Store::<Fr>::default();
+        let terminal = s.get_cont_terminal();
+
+        let nil = s.nil();
+        test_aux::<Coproc<Fr>>(
+            s,
+            "((lambda (a . rest) rest) 1)",
+            Some(nil),
+            None,
+            Some(terminal),
+            None,
+            6,
+            None,
+        );
+        let two = s.num(2);
+        let rest = s.list(&[two]);
+        test_aux::<Coproc<Fr>>(
+            s,
+            "((lambda (a . rest) rest) 1 2)",
+            Some(rest),
+            None,
+            Some(terminal),
+            None,
+            22,
+            None,
+        );
+        test_aux::<Coproc<Fr>>(
+            s,
+            "(((lambda (a . r) (lambda (x) x)) 1) 2)",
+            Some(two),
+            None,
+            Some(terminal),
+            None,
+            10,
+            None,
+        );
+        test_aux::<Coproc<Fr>>(
+            s,
+            "((lambda (a . rest) rest))",
+            None,
+            None,
+            Some(terminal),
+            None,
+            3,
+            None,
+        );
+    }
+
     #[test]
     fn test_prove_test_num_mod() {
         let s = &mut Store::<Fr>::default();
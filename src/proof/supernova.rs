@@ -0,0 +1,70 @@
+use crate::tag::ContTag;
+
+/// Selects which of SuperNova's circuits proves a reduction step, from the tag of the step's
+/// input continuation, so that each step only synthesizes the circuit for its kind of
+/// continuation. Every `ContTag` has its own circuit, and indices follow the declaration order of
+/// `ContTag`, so they are stable as long as its variants are.
+///
+/// Note that only the selection is implemented so far: the per-continuation circuits and the
+/// SuperNova prover that would fold them aren't part of this crate yet.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CircuitSelector;
+
+impl CircuitSelector {
+    /// The number of circuits, one per `ContTag`.
+    pub const NUM_CIRCUITS: usize = 16;
+
+    /// The index of the circuit proving steps whose input continuation has tag `tag`.
+    pub const fn circuit_index(&self, tag: ContTag) -> usize {
+        match tag {
+            ContTag::Outermost => 0,
+            ContTag::Call0 => 1,
+            ContTag::Call => 2,
+            ContTag::Call2 => 3,
+            ContTag::Tail => 4,
+            ContTag::Error => 5,
+            ContTag::Lookup => 6,
+            ContTag::Unop => 7,
+            ContTag::Binop => 8,
+            ContTag::Binop2 => 9,
+            ContTag::If => 10,
+            ContTag::Let => 11,
+            ContTag::LetRec => 12,
+            ContTag::Dummy => 13,
+            ContTag::Terminal => 14,
+            ContTag::Emit => 15,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn every_cont_tag_has_a_circuit() {
+        let selector = CircuitSelector;
+        let tags: Vec<ContTag> = ContTag::all().collect();
+        assert_eq!(CircuitSelector::NUM_CIRCUITS, tags.len());
+
+        let indices: HashSet<usize> = tags
+            .iter()
+            .map(|tag| selector.circuit_index(*tag))
+            .collect();
+        assert_eq!(tags.len(), indices.len());
+        assert!(indices.iter().all(|i| *i < CircuitSelector::NUM_CIRCUITS));
+    }
+
+    #[test]
+    fn circuit_indices_are_stable() {
+        // the index of a tag is its position in `ContTag`, which existing proofs depend on
+        let selector = CircuitSelector;
+        for (i, tag) in ContTag::all().enumerate() {
+            assert_eq!(i, selector.circuit_index(tag), "{tag}");
+            assert_eq!(i, (tag as u16 - ContTag::Outermost as u16) as usize);
+        }
+        assert_eq!(0, selector.circuit_index(ContTag::Outermost));
+        assert_eq!(15, selector.circuit_index(ContTag::Emit));
+    }
+}
@@ -1,11 +1,23 @@
+#[cfg(not(target_arch = "wasm32"))]
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::hash::Hash;
 use std::marker::PhantomData;
+use std::str::FromStr;
+use thiserror::Error;
 
 use crate::field::LurkField;
-use crate::tag::{ContTag, ExprTag};
+use crate::tag::{ContTag, ExprTag, Tag};
 
 /// The internal untagged raw Store pointer
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// `RawPtr`s serialize as externally tagged enums,
+/// e.g. `{"Index":3}`. Indices are only meaningful relative to the `Store`
+/// that issued them, so a serialized pointer must be paired with a snapshot
+/// of that store.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Arbitrary))]
 pub enum RawPtr {
     /// Null is used to represent ZPtrs with hash digests of F::zero()
     /// currently only ZExpr::StrNil and ZExpr::SymNil
@@ -49,42 +61,189 @@ impl RawPtr {
             _ => None,
         }
     }
+
+    /// Rewrite the index of an `Index` RawPtr, leaving `Null` and `Opaque`
+    /// pointers untouched. Useful when relocating `Store` contents.
+    pub fn map_index(self, f: impl FnOnce(usize) -> usize) -> RawPtr {
+        match self {
+            Self::Index(x) => Self::Index(f(x)),
+            _ => self,
+        }
+    }
+
+    /// Returns the stable textual encoding of a RawPtr, which can be read
+    /// back with `RawPtr::from_str`
+    pub fn to_tag_string(&self) -> String {
+        self.to_string()
+    }
 }
 
-/// A `Store` pointer
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct Ptr<F: LurkField> {
-    /// An expression tag
-    pub tag: ExprTag,
+/// The textual encoding of a `RawPtr` is stable: `null`, `opq:<n>` or `idx:<n>`
+impl fmt::Display for RawPtr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => write!(f, "null"),
+            Self::Opaque(x) => write!(f, "opq:{x}"),
+            Self::Index(x) => write!(f, "idx:{x}"),
+        }
+    }
+}
+
+/// Errors arising when parsing the textual encoding of a `RawPtr`
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RawPtrParseError {
+    #[error("Malformed RawPtr: {0}")]
+    Malformed(String),
+    #[error("Unknown RawPtr kind: {0}")]
+    UnknownKind(String),
+    #[error("Invalid RawPtr index: {0}")]
+    InvalidIndex(String),
+}
+
+impl FromStr for RawPtr {
+    type Err = RawPtrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "null" {
+            return Ok(Self::Null);
+        }
+        let (kind, idx) = s
+            .split_once(':')
+            .ok_or_else(|| RawPtrParseError::Malformed(s.into()))?;
+        let idx = idx
+            .parse::<usize>()
+            .map_err(|_| RawPtrParseError::InvalidIndex(idx.into()))?;
+        match kind {
+            "opq" => Ok(Self::Opaque(idx)),
+            "idx" => Ok(Self::Index(idx)),
+            _ => Err(RawPtrParseError::UnknownKind(kind.into())),
+        }
+    }
+}
+
+/// A tagged `Store` pointer. The tag determines how the `raw` pointer is to
+/// be interpreted, e.g. which `IndexSet` in the `Store` an `Index` refers to.
+/// Expression and continuation pointers are both `TaggedPtr`s, differing only
+/// in their tag type (see `Ptr` and `ContPtr`).
+///
+/// As with `RawPtr`, serialized `TaggedPtr`s are only meaningful alongside a
+/// snapshot of the `Store` they point into.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaggedPtr<F: LurkField, T: Tag> {
+    /// An expression or continuation tag
+    pub tag: T,
     /// The underlying pointer, which can be null, opaque, or an index
     pub raw: RawPtr,
     /// PhantomData is needed to consume the `F: LurkField` parameter, since
     /// we want to pin our Ptr to a specific field (even though we don't
     /// actually use it)
+    #[serde(skip)]
     pub _f: PhantomData<F>,
 }
 
+/// A `Store` pointer to an expression
+pub type Ptr<F> = TaggedPtr<F, ExprTag>;
+
+/// A `Store` pointer to a continuation
+pub type ContPtr<F> = TaggedPtr<F, ContTag>;
+
 #[allow(clippy::derived_hash_with_manual_eq)]
-impl<F: LurkField> Hash for Ptr<F> {
+impl<F: LurkField, T: Tag + Hash> Hash for TaggedPtr<F, T> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.tag.hash(state);
         self.raw.hash(state);
     }
 }
 
+/// Pointers are ordered by tag first, then by raw pointer, so that sorting
+/// them is deterministic
+impl<F: LurkField, T: Tag + Ord> PartialOrd for TaggedPtr<F, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: LurkField, T: Tag + Ord> Ord for TaggedPtr<F, T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (self.tag, self.raw).cmp(&(other.tag, other.raw))
+    }
+}
+
+impl<F: LurkField, T: Tag> TaggedPtr<F, T> {
+    /// Construct a TaggedPtr from a tag and a RawPtr
+    pub fn new(tag: T, raw: RawPtr) -> Self {
+        Self {
+            tag,
+            raw,
+            _f: Default::default(),
+        }
+    }
+
+    /// check if a TaggedPtr is an opaque pointer
+    pub const fn is_opaque(&self) -> bool {
+        self.raw.is_opaque()
+    }
+
+    /// Construct a TaggedPtr from an index
+    pub fn index(tag: T, idx: usize) -> Self {
+        Self::new(tag, RawPtr::Index(idx))
+    }
+
+    /// Construct a TaggedPtr from an opaque index
+    pub fn opaque(tag: T, idx: usize) -> Self {
+        Self::new(tag, RawPtr::Opaque(idx))
+    }
+
+    /// Construct a null TaggedPtr
+    pub fn null(tag: T) -> Self {
+        Self::new(tag, RawPtr::Null)
+    }
+
+    /// Rewrite the index of the underlying raw pointer, as with `RawPtr::map_index`
+    pub fn map_raw_index(self, f: impl FnOnce(usize) -> usize) -> Self {
+        Self::new(self.tag, self.raw.map_index(f))
+    }
+
+    #[inline]
+    pub fn cast(self, tag: T) -> Self {
+        Self {
+            tag,
+            raw: self.raw,
+            _f: self._f,
+        }
+    }
+}
+
 impl<F: LurkField> Ptr<F> {
     // TODO: Make these methods and the similar ones defined on expression consistent, probably including a shared trait.
 
+    /// Classify a Ptr, so that callers can `match` on its kind rather than
+    /// combining the `is_*` predicates below
+    pub const fn kind(&self) -> PtrKind {
+        match (self.tag, self.raw) {
+            (tag, RawPtr::Opaque(_)) => PtrKind::Opaque { tag },
+            (ExprTag::Nil, _) => PtrKind::Nil,
+            (ExprTag::Cons, _) => PtrKind::Cons,
+            (tag, _) => PtrKind::Atom { tag },
+        }
+    }
+
     // NOTE: Although this could be a type predicate now, when NIL becomes a symbol, it won't be possible.
-    /// check if a Ptr is `Nil` pointer
+    /// check if a Ptr is `Nil` pointer. This only checks the tag, so it is a cheap precheck; use
+    /// `Store::ptr_is_nil` to confirm that a pointer is the canonical `nil`.
     pub const fn is_nil(&self) -> bool {
-        matches!(self.tag, ExprTag::Nil)
-        // FIXME: check value also, probably
+        matches!(
+            self.kind(),
+            PtrKind::Nil | PtrKind::Opaque { tag: ExprTag::Nil }
+        )
     }
 
     /// check if a Ptr is a `Cons` pointer
     pub const fn is_cons(&self) -> bool {
-        matches!(self.tag, ExprTag::Cons)
+        matches!(
+            self.kind(),
+            PtrKind::Cons | PtrKind::Opaque { tag: ExprTag::Cons }
+        )
     }
 
     // TODO: Is this still needed?
@@ -95,12 +254,7 @@ impl<F: LurkField> Ptr<F> {
 
     // check if a Ptr is a list pointer
     pub const fn is_list(&self) -> bool {
-        matches!(self.tag, ExprTag::Nil | ExprTag::Cons)
-    }
-
-    /// check if a Ptr is an opaque pointer
-    pub const fn is_opaque(&self) -> bool {
-        self.raw.is_opaque()
+        self.is_nil() || self.is_cons()
     }
 
     // TODO: Is this still needed?
@@ -121,41 +275,61 @@ impl<F: LurkField> Ptr<F> {
         }
     }
 
-    /// Construct a Ptr from an index
-    pub fn index(tag: ExprTag, idx: usize) -> Self {
-        Ptr {
-            tag,
-            raw: RawPtr::Index(idx),
-            _f: Default::default(),
+    /// Like `cast`, but fails if the raw pointer can't be meaningfully
+    /// reinterpreted under the new tag
+    pub fn try_cast(self, tag: ExprTag) -> Result<Self, CastError> {
+        let from = self.tag;
+        match self.raw {
+            RawPtr::Null if !is_nullable(tag) => Err(CastError::NotNullable { from, to: tag }),
+            RawPtr::Null => Ok(self.cast(tag)),
+            RawPtr::Opaque(_) | RawPtr::Index(_) if !shares_payload(from, tag) => {
+                Err(CastError::IncompatiblePayload { from, to: tag })
+            }
+            RawPtr::Opaque(_) | RawPtr::Index(_) => Ok(self.cast(tag)),
         }
     }
 
-    /// Construct a Ptr from an opaque index
-    pub fn opaque(tag: ExprTag, idx: usize) -> Self {
-        Ptr {
-            tag,
-            raw: RawPtr::Opaque(idx),
-            _f: Default::default(),
+    /// Recover the char encoded by a `Char` Ptr. Returns `None` for other tags
+    /// or if the index isn't a valid unicode scalar value.
+    pub fn as_char(&self) -> Option<char> {
+        if self.tag != ExprTag::Char {
+            return None;
         }
+        char::from_u32(u32::try_from(self.raw.idx()?).ok()?)
     }
+}
 
-    /// Construct a null Ptr
-    pub fn null(tag: ExprTag) -> Self {
-        Ptr {
-            tag,
-            raw: RawPtr::Null,
-            _f: Default::default(),
-        }
-    }
+/// The coarse classification of a `Ptr` returned by `Ptr::kind`. Opaque
+/// pointers are kept apart because their contents can't be inspected, but
+/// they retain their tag, so an opaque cons still counts as a cons for
+/// `Ptr::is_cons`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PtrKind {
+    Nil,
+    Cons,
+    Atom { tag: ExprTag },
+    Opaque { tag: ExprTag },
+}
 
-    #[inline]
-    pub fn cast(self, tag: ExprTag) -> Self {
-        Ptr {
-            tag,
-            raw: self.raw,
-            _f: self._f,
-        }
-    }
+/// Errors arising from a checked `Ptr` cast
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CastError {
+    #[error("Cannot cast a null {from} pointer to {to}, which has no null representation")]
+    NotNullable { from: ExprTag, to: ExprTag },
+    #[error("Cannot cast a {from} pointer to {to}, whose payloads are incompatible")]
+    IncompatiblePayload { from: ExprTag, to: ExprTag },
+}
+
+/// Tags which admit a `RawPtr::Null` representation
+const fn is_nullable(tag: ExprTag) -> bool {
+    matches!(tag, ExprTag::Str | ExprTag::Sym | ExprTag::Key)
+}
+
+/// Whether two tags interpret a non-null `RawPtr` in the same way. `Nil`, `Sym`
+/// and `Key` all point into the `Store`'s symbol storage.
+fn shares_payload(a: ExprTag, b: ExprTag) -> bool {
+    use ExprTag::{Key, Nil, Sym};
+    a == b || matches!((a, b), (Nil | Sym | Key, Nil | Sym | Key))
 }
 
 impl<F: LurkField> From<char> for Ptr<F> {
@@ -168,74 +342,253 @@ impl<F: LurkField> From<char> for Ptr<F> {
     }
 }
 
-/// A pointer to a continuation. Logically this is the same a Ptr and should
-/// probably be combined with it in a future refactor
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct ContPtr<F: LurkField> {
-    pub tag: ContTag,
-    pub raw: RawPtr,
-    pub _f: PhantomData<F>,
-}
-
-#[allow(clippy::derived_hash_with_manual_eq)]
-impl<F: LurkField> Hash for ContPtr<F> {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.tag.hash(state);
-        self.raw.hash(state);
-    }
-}
-
 impl<F: LurkField> ContPtr<F> {
-    pub fn new(tag: ContTag, raw: RawPtr) -> Self {
-        Self {
-            tag,
-            raw,
-            _f: Default::default(),
-        }
-    }
     pub const fn is_error(&self) -> bool {
         matches!(self.tag, ContTag::Error)
     }
 
-    pub fn index(tag: ContTag, idx: usize) -> Self {
-        ContPtr {
-            tag,
-            raw: RawPtr::Index(idx),
-            _f: Default::default(),
-        }
-    }
-
-    pub fn opaque(tag: ContTag, idx: usize) -> Self {
-        ContPtr {
-            tag,
-            raw: RawPtr::Index(idx),
-            _f: Default::default(),
-        }
-    }
-
-    pub fn null(tag: ContTag) -> Self {
-        ContPtr {
-            tag,
-            raw: RawPtr::Null,
-            _f: Default::default(),
-        }
+    /// Returns true if this is the continuation of a call in tail position. Calls made under it
+    /// reuse it instead of nesting another, so tail recursion doesn't grow the continuation.
+    pub const fn is_tail(&self) -> bool {
+        matches!(self.tag, ContTag::Tail)
     }
 }
 
+/// Groups of tags, as defined by `ExprTag::is_self_evaluating` and friends
 pub trait TypePredicates {
     fn is_fun(&self) -> bool;
     fn is_self_evaluating(&self) -> bool;
+    fn is_numeric(&self) -> bool;
+    fn is_callable(&self) -> bool;
     fn is_potentially(&self, tag: ExprTag) -> bool;
 }
 
-impl<F: LurkField> TypePredicates for Ptr<F> {
+impl<F: LurkField, T: Tag + TypePredicates> TypePredicates for TaggedPtr<F, T> {
     fn is_fun(&self) -> bool {
         self.tag.is_fun()
     }
     fn is_self_evaluating(&self) -> bool {
         self.tag.is_self_evaluating()
     }
+    fn is_numeric(&self) -> bool {
+        self.tag.is_numeric()
+    }
+    fn is_callable(&self) -> bool {
+        self.tag.is_callable()
+    }
     fn is_potentially(&self, tag: ExprTag) -> bool {
         self.tag.is_potentially(tag)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blstrs::Scalar as Fr;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn prop_ptr_kind_predicates(tag in any::<ExprTag>(), raw in any::<RawPtr>()) {
+            let ptr = Ptr::<Fr>::new(tag, raw);
+            let kind = ptr.kind();
+            match kind {
+                PtrKind::Nil => assert_eq!(ExprTag::Nil, tag),
+                PtrKind::Cons => assert_eq!(ExprTag::Cons, tag),
+                PtrKind::Atom { tag: t } | PtrKind::Opaque { tag: t } => assert_eq!(t, tag),
+            }
+            assert_eq!(ptr.is_opaque(), matches!(kind, PtrKind::Opaque { .. }));
+            assert_eq!(ptr.is_nil(), tag == ExprTag::Nil);
+            assert_eq!(ptr.is_cons(), tag == ExprTag::Cons);
+            assert_eq!(ptr.is_atom(), !ptr.is_cons());
+            assert_eq!(ptr.is_list(), ptr.is_nil() || ptr.is_cons());
+            if !ptr.is_opaque() {
+                assert_eq!(ptr.is_nil(), kind == PtrKind::Nil);
+                assert_eq!(ptr.is_cons(), kind == PtrKind::Cons);
+                assert_eq!(ptr.is_atom(), matches!(kind, PtrKind::Nil | PtrKind::Atom { .. }));
+            }
+        }
+
+        #[test]
+        fn prop_raw_ptr_tag_string(x in any::<RawPtr>()) {
+            assert_eq!(x, RawPtr::from_str(&x.to_tag_string()).unwrap());
+        }
+    }
+
+    #[test]
+    fn unit_raw_ptr_tag_string() {
+        assert_eq!("null", RawPtr::Null.to_tag_string());
+        assert_eq!("opq:3", RawPtr::Opaque(3).to_tag_string());
+        assert_eq!("idx:3", RawPtr::Index(3).to_tag_string());
+
+        assert_eq!(
+            Err(RawPtrParseError::Malformed("nul".into())),
+            RawPtr::from_str("nul")
+        );
+        assert_eq!(
+            Err(RawPtrParseError::UnknownKind("ptr".into())),
+            RawPtr::from_str("ptr:3")
+        );
+        assert_eq!(
+            Err(RawPtrParseError::InvalidIndex("x".into())),
+            RawPtr::from_str("idx:x")
+        );
+    }
+
+    #[test]
+    fn char_ptr_as_char() {
+        for c in ['a', '0', '😀'] {
+            assert_eq!(Some(c), Ptr::<Fr>::from(c).as_char());
+        }
+        // surrogate code points are not valid chars
+        assert_eq!(None, Ptr::<Fr>::index(ExprTag::Char, 0xD800).as_char());
+        assert_eq!(None, Ptr::<Fr>::index(ExprTag::Num, 97).as_char());
+        assert_eq!(None, Ptr::<Fr>::null(ExprTag::Char).as_char());
+    }
+
+    #[test]
+    fn ptr_try_cast() {
+        let null_str = Ptr::<Fr>::null(ExprTag::Str);
+        assert_eq!(
+            Err(CastError::NotNullable {
+                from: ExprTag::Str,
+                to: ExprTag::Num
+            }),
+            null_str.try_cast(ExprTag::Num)
+        );
+        assert_eq!(
+            Ok(Ptr::null(ExprTag::Key)),
+            Ptr::<Fr>::null(ExprTag::Sym).try_cast(ExprTag::Key)
+        );
+
+        let nil = Ptr::<Fr>::index(ExprTag::Nil, 3);
+        assert_eq!(Ok(Ptr::index(ExprTag::Sym, 3)), nil.try_cast(ExprTag::Sym));
+        assert_eq!(Ok(nil), nil.try_cast(ExprTag::Nil));
+
+        let cons = Ptr::<Fr>::index(ExprTag::Cons, 3);
+        assert_eq!(
+            Err(CastError::IncompatiblePayload {
+                from: ExprTag::Cons,
+                to: ExprTag::Num
+            }),
+            cons.try_cast(ExprTag::Num)
+        );
+        assert!(Ptr::<Fr>::opaque(ExprTag::Cons, 0)
+            .try_cast(ExprTag::Fun)
+            .is_err());
+    }
+
+    #[test]
+    fn ptr_ordering() {
+        let mut ptrs = vec![
+            Ptr::<Fr>::index(ExprTag::Num, 2),
+            Ptr::index(ExprTag::Cons, 5),
+            Ptr::null(ExprTag::Str),
+            Ptr::opaque(ExprTag::Cons, 0),
+            Ptr::index(ExprTag::Num, 1),
+            Ptr::index(ExprTag::Nil, 9),
+            Ptr::null(ExprTag::Sym),
+        ];
+        ptrs.sort();
+        assert_eq!(
+            ptrs,
+            vec![
+                Ptr::index(ExprTag::Nil, 9),
+                Ptr::opaque(ExprTag::Cons, 0),
+                Ptr::index(ExprTag::Cons, 5),
+                Ptr::null(ExprTag::Sym),
+                Ptr::index(ExprTag::Num, 1),
+                Ptr::index(ExprTag::Num, 2),
+                Ptr::null(ExprTag::Str),
+            ]
+        );
+
+        let mut conts = vec![
+            ContPtr::<Fr>::index(ContTag::Let, 0),
+            ContPtr::null(ContTag::Outermost),
+            ContPtr::index(ContTag::Call0, 1),
+            ContPtr::index(ContTag::Call0, 0),
+        ];
+        conts.sort();
+        assert_eq!(
+            conts,
+            vec![
+                ContPtr::null(ContTag::Outermost),
+                ContPtr::index(ContTag::Call0, 0),
+                ContPtr::index(ContTag::Call0, 1),
+                ContPtr::index(ContTag::Let, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn ptr_map_raw_index() {
+        let ptrs = vec![
+            Ptr::<Fr>::index(ExprTag::Cons, 0),
+            Ptr::opaque(ExprTag::Cons, 1),
+            Ptr::null(ExprTag::Str),
+            Ptr::index(ExprTag::Num, 3),
+        ];
+        let remapped = ptrs
+            .iter()
+            .map(|ptr| ptr.map_raw_index(|i| i + 10))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            remapped,
+            vec![
+                Ptr::index(ExprTag::Cons, 10),
+                Ptr::opaque(ExprTag::Cons, 1),
+                Ptr::null(ExprTag::Str),
+                Ptr::index(ExprTag::Num, 13),
+            ]
+        );
+        assert_eq!(
+            ContPtr::<Fr>::index(ContTag::Emit, 12),
+            ContPtr::index(ContTag::Emit, 2).map_raw_index(|i| i + 10)
+        );
+    }
+
+    #[test]
+    fn cont_ptr_opaque() {
+        let ptr = ContPtr::<Fr>::opaque(ContTag::Outermost, 7);
+        assert!(ptr.raw.is_opaque());
+        assert!(ptr.is_opaque());
+        assert_eq!(ptr.raw.opaque_idx(), Some(7));
+        assert_eq!(ptr.raw.idx(), None);
+    }
+
+    #[test]
+    fn raw_ptr_serde_roundtrip() {
+        for (raw, json) in [
+            (RawPtr::Null, r#""Null""#),
+            (RawPtr::Opaque(7), r#"{"Opaque":7}"#),
+            (RawPtr::Index(3), r#"{"Index":3}"#),
+        ] {
+            assert_eq!(json, serde_json::to_string(&raw).unwrap());
+            assert_eq!(raw, serde_json::from_str(json).unwrap());
+
+            let bytes = bincode::serialize(&raw).unwrap();
+            assert_eq!(raw, bincode::deserialize(&bytes).unwrap());
+
+            let ptr = Ptr::<Fr>::new(ExprTag::Cons, raw);
+            let json = serde_json::to_string(&ptr).unwrap();
+            assert_eq!(ptr, serde_json::from_str(&json).unwrap());
+            let bytes = bincode::serialize(&ptr).unwrap();
+            assert_eq!(ptr, bincode::deserialize(&bytes).unwrap());
+
+            let cont_ptr = ContPtr::<Fr>::new(ContTag::Outermost, raw);
+            let json = serde_json::to_string(&cont_ptr).unwrap();
+            assert_eq!(cont_ptr, serde_json::from_str(&json).unwrap());
+            let bytes = bincode::serialize(&cont_ptr).unwrap();
+            assert_eq!(cont_ptr, bincode::deserialize(&bytes).unwrap());
+        }
+    }
+
+    #[test]
+    fn ptr_serde_skips_field() {
+        let ptr = Ptr::<Fr>::index(ExprTag::Num, 3);
+        let json = serde_json::to_string(&ptr).unwrap();
+        assert!(json.contains(r#""raw":{"Index":3}"#));
+        assert!(!json.contains("_f"));
+    }
+}
@@ -1,15 +1,20 @@
 use std::fs::{create_dir_all, File};
-use std::io::{BufReader, BufWriter, Read};
+use std::io::{BufReader, BufWriter, Read, Write};
 use std::marker::PhantomData;
 
 use abomonation::{encode, Abomonation};
 use camino::{Utf8Path, Utf8PathBuf};
 use nova::traits::Group;
+use sha2::{Digest, Sha256};
 
 use crate::coprocessor::Coprocessor;
 use crate::proof::nova::{CurveCycleEquipped, PublicParams, G1, G2};
 use crate::public_parameters::error::Error;
 
+/// The version of the layout of serialized (non-abomonated) entries: a version byte, a SHA-256
+/// checksum of the payload, then the bincode-encoded params
+const FORMAT_VERSION: u8 = 1;
+
 pub(crate) struct PublicParamDiskCache<F, C>
 where
     F: CurveCycleEquipped,
@@ -38,10 +43,30 @@ where
         self.dir.join(Utf8PathBuf::from(key))
     }
 
+    /// Reads the params stored under `key`, failing if the entry is missing, was written with
+    /// another format version or doesn't match its checksum
     pub(crate) fn get(&self, key: &str) -> Result<PublicParams<'static, F, C>, Error> {
-        let file = File::open(self.key_path(key))?;
-        let reader = BufReader::new(file);
-        bincode::deserialize_from(reader).map_err(|e| {
+        let bytes = self.get_raw_bytes(key)?;
+        let Some((&version, rest)) = bytes.split_first() else {
+            return Err(Error::CacheError("Empty public param cache entry".into()));
+        };
+        if version != FORMAT_VERSION {
+            return Err(Error::CacheError(format!(
+                "Public param cache entry has format version {version}, expected {FORMAT_VERSION}"
+            )));
+        }
+        if rest.len() < 32 {
+            return Err(Error::CacheError(
+                "Truncated public param cache entry".into(),
+            ));
+        }
+        let (checksum, payload) = rest.split_at(32);
+        if checksum != Sha256::digest(payload).as_slice() {
+            return Err(Error::CacheError(
+                "Public param cache entry doesn't match its checksum".into(),
+            ));
+        }
+        bincode::deserialize(payload).map_err(|e| {
             Error::CacheError(format!("Public param cache deserialization error: {}", e))
         })
     }
@@ -55,11 +80,16 @@ where
     }
 
     pub(crate) fn set(&self, key: &str, data: &PublicParams<'static, F, C>) -> Result<(), Error> {
-        let file = File::create(self.key_path(key)).expect("failed to create file");
-        let writer = BufWriter::new(&file);
-        bincode::serialize_into(writer, &data).map_err(|e| {
+        let payload = bincode::serialize(&data).map_err(|e| {
             Error::CacheError(format!("Public param cache serialization error: {}", e))
-        })
+        })?;
+        let file = File::create(self.key_path(key))?;
+        let mut writer = BufWriter::new(&file);
+        writer.write_all(&[FORMAT_VERSION])?;
+        writer.write_all(&Sha256::digest(&payload))?;
+        writer.write_all(&payload)?;
+        writer.flush()?;
+        Ok(())
     }
 
     pub(crate) fn set_abomonated<V: Abomonation>(&self, key: &str, data: &V) -> Result<(), Error> {
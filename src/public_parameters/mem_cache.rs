@@ -17,6 +17,7 @@ use crate::{
 };
 use crate::{proof::nova::CurveCycleEquipped, public_parameters::error::Error};
 
+use super::cache_key;
 use super::disk_cache::PublicParamDiskCache;
 
 type AnyMap = anymap::Map<dyn core::any::Any + Send + Sync>;
@@ -58,18 +59,22 @@ impl PublicParamMemCache {
         let disk_cache = PublicParamDiskCache::new(disk_cache_path).unwrap();
         // use the cached language key
         let lang_key = lang.key();
-        let quick_suffix = if abomonated { "-abomonated" } else { "" };
         // Sanity-check: we're about to use a lang-dependent disk cache, which should be specialized
         // for this lang/coprocessor.
-        let key = format!("public-params-rc-{rc}-coproc-{lang_key}{quick_suffix}");
+        let key = cache_key(rc, &lang, abomonated);
         // read the file if it exists, otherwise initialize
         if abomonated {
-            match disk_cache.get_raw_bytes(&key) {
-                Ok(mut bytes) => {
+            let mut bytes = disk_cache.get_raw_bytes(&key);
+            let decoded = match &mut bytes {
+                Ok(bytes) => match unsafe { decode::<PublicParams<'_, F, C>>(bytes) } {
+                    Some((pp, rest)) if rest.is_empty() => Ok(pp),
+                    _ => Err(Error::CacheError(format!("Failed to decode {key}"))),
+                },
+                Err(e) => Err(Error::CacheError(format!("{e}"))),
+            };
+            match decoded {
+                Ok(pp) => {
                     info!("Using abomonated public params for lang {lang_key}");
-                    let (pp, rest) =
-                        unsafe { decode::<PublicParams<'_, F, C>>(&mut bytes).unwrap() };
-                    assert!(rest.is_empty());
                     Ok(Arc::new(pp.clone())) // this clone is VERY expensive
                 }
                 Err(e) => {
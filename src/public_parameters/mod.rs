@@ -1,9 +1,12 @@
 use ::nova::traits::Group;
 use abomonation::{decode, Abomonation};
 use camino::{Utf8Path, Utf8PathBuf};
+use log::info;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use crate::coprocessor::Coprocessor;
+use crate::field::LurkField;
 use crate::proof::nova::{CurveCycleEquipped, G1, G2};
 use crate::{
     eval::lang::Lang,
@@ -28,6 +31,75 @@ pub fn public_params_default_dir() -> Utf8PathBuf {
     Utf8PathBuf::from(".lurk/public_params")
 }
 
+/// The disk cache key for the params of a circuit shape: the field, the reduction count, and the
+/// language's coprocessors
+pub(crate) fn cache_key<F: LurkField, C: Coprocessor<F>>(
+    rc: usize,
+    lang: &Lang<F, C>,
+    abomonated: bool,
+) -> String {
+    let field = F::FIELD;
+    let lang_key = lang.key();
+    let quick_suffix = if abomonated { "-abomonated" } else { "" };
+    format!("public-params-{field}-rc-{rc}-coproc-{lang_key}{quick_suffix}")
+}
+
+/// A directory of public params, reused across runs by circuit shape (see `cache_key`). Entries
+/// that can't be read back intact, e.g. because they are corrupt or were written in an older
+/// format, are regenerated and overwritten.
+pub struct PublicParamCache<F: CurveCycleEquipped, C: Coprocessor<F> + 'static> {
+    disk_cache: disk_cache::PublicParamDiskCache<F, C>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl<F: CurveCycleEquipped, C: Coprocessor<F> + 'static> PublicParamCache<F, C>
+where
+    <<G1<F> as Group>::Scalar as ff::PrimeField>::Repr: Abomonation,
+    <<G2<F> as Group>::Scalar as ff::PrimeField>::Repr: Abomonation,
+{
+    pub fn new(dir: &Utf8Path) -> Result<Self, Error> {
+        Ok(Self {
+            disk_cache: disk_cache::PublicParamDiskCache::new(dir)?,
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        })
+    }
+
+    /// Loads the params for reduction count `rc` and `lang` from the cache, or generates and
+    /// stores them if there is no usable entry
+    pub fn public_params(
+        &self,
+        rc: usize,
+        lang: Arc<Lang<F, C>>,
+    ) -> Result<Arc<PublicParams<'static, F, C>>, Error> {
+        let key = cache_key(rc, &lang, false);
+        match self.disk_cache.get(&key) {
+            Ok(pp) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Ok(Arc::new(pp))
+            }
+            Err(e) => {
+                info!("Regenerating public params {key}: {e}");
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                let pp = nova::public_params(rc, lang);
+                self.disk_cache.set(&key, &pp)?;
+                Ok(Arc::new(pp))
+            }
+        }
+    }
+
+    /// The number of calls to `public_params` that loaded an existing entry
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// The number of calls to `public_params` that had to generate the params
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
 pub fn public_params<F: CurveCycleEquipped, C: Coprocessor<F> + 'static>(
     rc: usize,
     abomonated: bool,
@@ -72,7 +144,7 @@ where
     let lang_key = lang.key();
     // Sanity-check: we're about to use a lang-dependent disk cache, which should be specialized
     // for this lang/coprocessor.
-    let key = format!("public-params-rc-{rc}-coproc-{lang_key}-abomonated");
+    let key = cache_key(rc, &lang, true);
 
     match disk_cache.get_raw_bytes(&key) {
         Ok(mut bytes) => {
@@ -125,4 +197,33 @@ mod tests {
         // With disk cache, reads from tmpfile
         let _public_params = public_params(10, true, lang, &public_params_dir).unwrap();
     }
+
+    #[test]
+    fn public_param_cache_by_shape() {
+        let tmp_dir = Builder::new().prefix("tmp").tempdir().unwrap();
+        let public_params_dir = Utf8Path::from_path(tmp_dir.path())
+            .unwrap()
+            .join("public_params");
+
+        let lang: Arc<Lang<S1, Coproc<S1>>> = Arc::new(Lang::new());
+        let cache = PublicParamCache::new(&public_params_dir).unwrap();
+        cache.public_params(1, lang.clone()).unwrap();
+        assert_eq!((0, 1), (cache.hits(), cache.misses()));
+        cache.public_params(1, lang.clone()).unwrap();
+        assert_eq!((1, 1), (cache.hits(), cache.misses()));
+        // a different reduction count is a different shape
+        cache.public_params(2, lang.clone()).unwrap();
+        assert_eq!((1, 2), (cache.hits(), cache.misses()));
+
+        // a corrupt entry is regenerated, not used
+        let path = public_params_dir.join(cache_key(1, &lang, false));
+        let mut bytes = std::fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        std::fs::write(&path, bytes).unwrap();
+        cache.public_params(1, lang.clone()).unwrap();
+        assert_eq!((1, 3), (cache.hits(), cache.misses()));
+        cache.public_params(1, lang).unwrap();
+        assert_eq!((2, 3), (cache.hits(), cache.misses()));
+    }
 }
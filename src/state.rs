@@ -214,7 +214,7 @@ const LURK_PACKAGE_SYMBOL_NAME: &str = "lurk";
 const USER_PACKAGE_SYMBOL_NAME: &str = "user";
 const META_PACKAGE_SYMBOL_NAME: &str = "meta";
 
-const LURK_PACKAGE_SYMBOLS_NAMES: [&str; 36] = [
+const LURK_PACKAGE_SYMBOLS_NAMES: [&str; 41] = [
     "atom",
     "begin",
     "car",
@@ -250,6 +250,11 @@ const LURK_PACKAGE_SYMBOLS_NAMES: [&str; 36] = [
     ">",
     "<=",
     ">=",
+    "bit-and",
+    "bit-or",
+    "bit-xor",
+    "shl",
+    "shr",
     "_",
 ];
 
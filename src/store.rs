@@ -1,15 +1,16 @@
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::sync::Arc;
 use std::usize;
 use thiserror;
 
 use once_cell::sync::OnceCell;
 
 use crate::cache_map::CacheMap;
-use crate::cont::Continuation;
+use crate::cont::{ContDescription, Continuation};
 use crate::expr;
-use crate::expr::{Expression, Thunk};
+use crate::expr::{ExprView, Expression, Thunk};
 use crate::field::{FWrap, LurkField};
 use crate::ptr::{ContPtr, Ptr, RawPtr};
 use crate::state::{lurk_sym, user_sym};
@@ -21,43 +22,63 @@ use crate::z_ptr::{ZContPtr, ZExprPtr, ZPtr};
 use crate::z_store::ZStore;
 use crate::{Num, UInt};
 
-use crate::hash::{HashConstants, InversePoseidonCache, PoseidonCache};
+use crate::hash::{HashConstants, HashFunction, InversePoseidonCache, PoseidonCache};
+
+mod bounded;
+mod commitment;
+mod env;
+mod frozen;
+mod gc;
+mod json;
+mod layered;
+mod snapshot;
+mod summary;
+
+pub use bounded::BoundedStore;
+pub use commitment::{Commitment, CommitmentError};
+pub use env::EnvBuilder;
+pub use frozen::FrozenStore;
+use gc::Node;
+pub use gc::Relocation;
+pub use json::JsonError;
+pub use layered::LayeredSet;
 
 type IndexSet<K> = indexmap::IndexSet<K, ahash::RandomState>;
 
-#[derive(Debug)]
+/// `Debug` prints a `summary` rather than every entry
+#[derive(Clone)]
 pub struct Store<F: LurkField> {
-    pub cons_store: IndexSet<(Ptr<F>, Ptr<F>)>,
-    pub comm_store: IndexSet<(FWrap<F>, Ptr<F>)>,
+    pub cons_store: LayeredSet<(Ptr<F>, Ptr<F>)>,
+    pub comm_store: LayeredSet<(FWrap<F>, Ptr<F>)>,
 
-    pub fun_store: IndexSet<(Ptr<F>, Ptr<F>, Ptr<F>)>,
+    pub fun_store: LayeredSet<(Ptr<F>, Ptr<F>, Ptr<F>)>,
 
     /// Holds a Sym or Key which is a string head and a symbol tail
-    pub sym_store: IndexSet<(Ptr<F>, Ptr<F>)>,
+    pub sym_store: LayeredSet<(Ptr<F>, Ptr<F>)>,
 
     // Other sparse storage format without hashing is likely more efficient
-    pub num_store: IndexSet<Num<F>>,
+    pub num_store: LayeredSet<Num<F>>,
 
     /// Holds a Str, which is a char head and a string tail
-    pub str_store: IndexSet<(Ptr<F>, Ptr<F>)>,
-    pub thunk_store: IndexSet<Thunk<F>>,
-    pub call0_store: IndexSet<(Ptr<F>, ContPtr<F>)>,
-    pub call_store: IndexSet<(Ptr<F>, Ptr<F>, ContPtr<F>)>,
-    pub call2_store: IndexSet<(Ptr<F>, Ptr<F>, ContPtr<F>)>,
-    pub tail_store: IndexSet<(Ptr<F>, ContPtr<F>)>,
-    pub lookup_store: IndexSet<(Ptr<F>, ContPtr<F>)>,
-    pub unop_store: IndexSet<(Op1, ContPtr<F>)>,
-    pub binop_store: IndexSet<(Op2, Ptr<F>, Ptr<F>, ContPtr<F>)>,
-    pub binop2_store: IndexSet<(Op2, Ptr<F>, ContPtr<F>)>,
-    pub if_store: IndexSet<(Ptr<F>, ContPtr<F>)>,
-    pub let_store: IndexSet<(Ptr<F>, Ptr<F>, Ptr<F>, ContPtr<F>)>,
-    pub letrec_store: IndexSet<(Ptr<F>, Ptr<F>, Ptr<F>, ContPtr<F>)>,
-    pub emit_store: IndexSet<ContPtr<F>>,
+    pub str_store: LayeredSet<(Ptr<F>, Ptr<F>)>,
+    pub thunk_store: LayeredSet<Thunk<F>>,
+    pub call0_store: LayeredSet<(Ptr<F>, ContPtr<F>)>,
+    pub call_store: LayeredSet<(Ptr<F>, Ptr<F>, ContPtr<F>, bool)>,
+    pub call2_store: LayeredSet<(Ptr<F>, Ptr<F>, ContPtr<F>, bool)>,
+    pub tail_store: LayeredSet<(Ptr<F>, ContPtr<F>)>,
+    pub lookup_store: LayeredSet<(Ptr<F>, ContPtr<F>)>,
+    pub unop_store: LayeredSet<(Op1, ContPtr<F>)>,
+    pub binop_store: LayeredSet<(Op2, Ptr<F>, Ptr<F>, ContPtr<F>)>,
+    pub binop2_store: LayeredSet<(Op2, Ptr<F>, ContPtr<F>)>,
+    pub if_store: LayeredSet<(Ptr<F>, ContPtr<F>)>,
+    pub let_store: LayeredSet<(Ptr<F>, Ptr<F>, Ptr<F>, ContPtr<F>)>,
+    pub letrec_store: LayeredSet<(Ptr<F>, Ptr<F>, Ptr<F>, ContPtr<F>)>,
+    pub emit_store: LayeredSet<ContPtr<F>>,
 
     /// Holds opaque pointers
-    pub opaque_ptrs: IndexSet<ZExprPtr<F>>,
+    pub opaque_ptrs: LayeredSet<ZExprPtr<F>>,
     /// Holds opaque continuation pointers
-    pub opaque_cont_ptrs: IndexSet<ZContPtr<F>>,
+    pub opaque_cont_ptrs: LayeredSet<ZContPtr<F>>,
 
     /// Holds a mapping of `ZExprPtr` -> `Ptr` for reverse lookups
     pub z_expr_ptr_map: CacheMap<ZExprPtr<F>, Box<Ptr<F>>>,
@@ -80,10 +101,27 @@ pub struct Store<F: LurkField> {
     symbol_cache: HashMap<Symbol, Ptr<F>>,
 
     pub constants: OnceCell<NamedConstants<F>>,
+
+    intern_stats: InternStats,
+
+    /// The frozen store this is an overlay of, whose caches are consulted on misses in these
+    base: Option<Arc<Store<F>>>,
 }
 
 impl<F: LurkField> Default for Store<F> {
     fn default() -> Self {
+        Self::with_poseidon_cache(Default::default())
+    }
+}
+
+impl<F: LurkField> Store<F> {
+    /// A store that hashes with `hash_function` instead of Poseidon. Its digests can't be proven,
+    /// and `ZStore`s of its contents convert back to stores hashing with Poseidon.
+    pub fn with_hash_function(hash_function: Arc<dyn HashFunction<F>>) -> Self {
+        Self::with_poseidon_cache(PoseidonCache::with_hash_function(hash_function))
+    }
+
+    fn with_poseidon_cache(poseidon_cache: PoseidonCache<F>) -> Self {
         let mut store = Self {
             cons_store: Default::default(),
             comm_store: Default::default(),
@@ -110,13 +148,15 @@ impl<F: LurkField> Default for Store<F> {
             z_cont_ptr_map: Default::default(),
             z_expr_ptr_cache: Default::default(),
             z_cont_ptr_cache: Default::default(),
-            poseidon_cache: Default::default(),
+            poseidon_cache,
             inverse_poseidon_cache: Default::default(),
             dehydrated: Default::default(),
             dehydrated_cont: Default::default(),
             str_cache: Default::default(),
             symbol_cache: Default::default(),
             constants: Default::default(),
+            intern_stats: Default::default(),
+            base: None,
         };
         store.ensure_constants();
         store
@@ -132,6 +172,224 @@ impl fmt::Display for Error {
     }
 }
 
+/// Structured errors arising when interning into a store
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum StoreError {
+    #[error("Store capacity of {capacity} entries exceeded")]
+    CapacityExceeded { capacity: usize },
+    #[error("{0} is not a known opaque pointer")]
+    NotOpaque(String),
+    #[error("Preimage hashes to {found}, expected {expected}")]
+    DigestMismatch { expected: String, found: String },
+    #[error("A {tag} can't be a cons of a {car} and a {cdr}")]
+    WrongTags {
+        tag: ExprTag,
+        car: ExprTag,
+        cdr: ExprTag,
+    },
+}
+
+/// Checks that `car` and `cdr` have the tags `expected` of the parts of a `tag` cons, which the
+/// interning methods assert instead
+pub(crate) fn check_tags<F: LurkField>(
+    tag: ExprTag,
+    car: &Ptr<F>,
+    cdr: &Ptr<F>,
+    expected: (ExprTag, ExprTag),
+) -> Result<(), StoreError> {
+    if (car.tag, cdr.tag) == expected {
+        Ok(())
+    } else {
+        Err(StoreError::WrongTags {
+            tag,
+            car: car.tag,
+            cdr: cdr.tag,
+        })
+    }
+}
+
+impl From<StoreError> for Error {
+    fn from(e: StoreError) -> Self {
+        Error(e.to_string())
+    }
+}
+
+/// The interning operations shared by the different kinds of stores. Stores with a fixed
+/// capacity, like `BoundedStore`, fail instead of growing when full.
+pub trait Intern<F: LurkField> {
+    fn try_intern_cons(&mut self, car: Ptr<F>, cdr: Ptr<F>) -> Result<Ptr<F>, StoreError>;
+    fn try_intern_strcons(&mut self, car: Ptr<F>, cdr: Ptr<F>) -> Result<Ptr<F>, StoreError>;
+    fn try_intern_symcons(&mut self, car: Ptr<F>, cdr: Ptr<F>) -> Result<Ptr<F>, StoreError>;
+    fn try_intern_num(&mut self, num: Num<F>) -> Result<Ptr<F>, StoreError>;
+
+    fn try_intern_string(&mut self, s: &str) -> Result<Ptr<F>, StoreError> {
+        s.chars().rev().try_fold(Ptr::null(ExprTag::Str), |acc, c| {
+            self.try_intern_strcons(c.into(), acc)
+        })
+    }
+
+    /// Interns `sym` as `Store::intern_symbol` does, with `nil` and keywords cast from the symbol
+    /// with the same path
+    fn try_intern_symbol(&mut self, sym: &Symbol) -> Result<Ptr<F>, StoreError> {
+        let path_ptr = sym
+            .path()
+            .iter()
+            .try_fold(Ptr::null(ExprTag::Sym), |acc, s| {
+                let s_ptr = self.try_intern_string(s)?;
+                self.try_intern_symcons(s_ptr, acc)
+            })?;
+        Ok(if sym == &lurk_sym("nil") {
+            path_ptr.cast(ExprTag::Nil)
+        } else if sym.is_keyword() {
+            path_ptr.cast(ExprTag::Key)
+        } else {
+            path_ptr
+        })
+    }
+}
+
+impl<F: LurkField> Intern<F> for Store<F> {
+    fn try_intern_cons(&mut self, car: Ptr<F>, cdr: Ptr<F>) -> Result<Ptr<F>, StoreError> {
+        Ok(self.intern_cons(car, cdr))
+    }
+
+    fn try_intern_strcons(&mut self, car: Ptr<F>, cdr: Ptr<F>) -> Result<Ptr<F>, StoreError> {
+        check_tags(ExprTag::Str, &car, &cdr, (ExprTag::Char, ExprTag::Str))?;
+        Ok(self.intern_strcons(car, cdr))
+    }
+
+    fn try_intern_symcons(&mut self, car: Ptr<F>, cdr: Ptr<F>) -> Result<Ptr<F>, StoreError> {
+        check_tags(ExprTag::Sym, &car, &cdr, (ExprTag::Str, ExprTag::Sym))?;
+        Ok(self.intern_symcons(car, cdr))
+    }
+
+    fn try_intern_num(&mut self, num: Num<F>) -> Result<Ptr<F>, StoreError> {
+        Ok(self.intern_num(num))
+    }
+
+    fn try_intern_string(&mut self, s: &str) -> Result<Ptr<F>, StoreError> {
+        Ok(self.intern_string(s))
+    }
+
+    fn try_intern_symbol(&mut self, sym: &Symbol) -> Result<Ptr<F>, StoreError> {
+        Ok(self.intern_symbol(sym))
+    }
+}
+
+/// The number of entries interned in each of a `Store`'s sets, as reported by `Store::stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StoreStats {
+    pub cons: usize,
+    pub comm: usize,
+    pub fun: usize,
+    pub sym: usize,
+    pub num: usize,
+    pub str: usize,
+    pub thunk: usize,
+    /// Entries across all of the continuation sets
+    pub cont: usize,
+    /// Opaque expression and continuation pointers
+    pub opaque: usize,
+    /// The bytes taken by the entries above. This ignores the overhead of the sets themselves and
+    /// the hash caches, so it underestimates the real footprint.
+    pub estimated_bytes: usize,
+}
+
+/// How often interning found an entry already in a `Store` (a hit) rather than adding one (a
+/// miss), as reported by `Store::intern_stats`. Each expression node and continuation interned
+/// counts once, except that a whole string or symbol found in the store's caches counts as a
+/// single hit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InternStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl InternStats {
+    /// The fraction of interning that found an existing entry, or `None` if nothing has been
+    /// interned
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total > 0).then(|| self.hits as f64 / total as f64)
+    }
+}
+
+/// A step from a cons into one of its components
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStep {
+    Car,
+    Cdr,
+}
+
+/// Where two expressions first differ, as found by `Store::diff`. Following `path` from the roots
+/// of both expressions leads to `left` and `right`, which differ in their tags or values and can't
+/// be compared further because they aren't both conses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PtrDiff<F: LurkField> {
+    pub path: Vec<PathStep>,
+    pub left: Ptr<F>,
+    pub right: Ptr<F>,
+}
+
+/// An iterator over the elements of a cons list, created by `Store::iter_list`. Iteration stops
+/// at `nil`. Anything that prevents walking the rest of the list, like an opaque cdr or a cycle,
+/// is reported as a final `Err` item.
+#[derive(Debug)]
+pub struct ListIter<'a, F: LurkField> {
+    store: &'a Store<F>,
+    rest: Option<Ptr<F>>,
+    tail: Option<Ptr<F>>,
+    allow_improper: bool,
+    visited: HashSet<Ptr<F>>,
+}
+
+impl<'a, F: LurkField> ListIter<'a, F> {
+    fn new(store: &'a Store<F>, head: Ptr<F>, allow_improper: bool) -> Self {
+        Self {
+            store,
+            rest: Some(head),
+            tail: None,
+            allow_improper,
+            visited: HashSet::new(),
+        }
+    }
+
+    fn fail(&mut self, msg: String) -> Option<Result<Ptr<F>, Error>> {
+        self.rest = None;
+        Some(Err(Error(msg)))
+    }
+}
+
+impl<'a, F: LurkField> Iterator for ListIter<'a, F> {
+    type Item = Result<Ptr<F>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ptr = self.rest?;
+        match ptr.tag {
+            ExprTag::Cons if ptr.is_opaque() => self.fail(format!("opaque cdr {ptr:?} in list")),
+            ExprTag::Cons if !self.visited.insert(ptr) => self.fail(format!("cycle at {ptr:?}")),
+            ExprTag::Cons => match self.store.fetch_cons(&ptr) {
+                Some((car, cdr)) => {
+                    self.rest = Some(*cdr);
+                    Some(Ok(*car))
+                }
+                None => self.fail(format!("unknown cons {ptr:?} in list")),
+            },
+            ExprTag::Nil => {
+                self.rest = None;
+                self.tail = Some(ptr);
+                None
+            }
+            _ if self.allow_improper => {
+                self.rest = None;
+                self.tail = Some(ptr);
+                None
+            }
+            _ => self.fail(format!("improper list ending in {ptr:?}")),
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! lurk_sym_ptr {
     ( $store:expr, $sym:ident ) => {{
@@ -139,6 +397,42 @@ macro_rules! lurk_sym_ptr {
     }};
 }
 
+/// Interns a Lurk expression written as Rust tokens, returning its `Ptr`, as in
+/// `lurk!(store, (let ((x 1)) (+ x (* 2 3))))`. Numbers, `u64`s, strings, symbols, keywords,
+/// nested lists and `'sym` are written as in Lurk, and chars as Rust chars. A `{ expr }` is
+/// replaced by the `Ptr` that `expr` evaluates to.
+///
+/// Rust splits some Lurk tokens, so use `(quote ..)` to quote a list, and `{ .. }` for negative
+/// numbers, dotted pairs, and symbols with a `-` in them.
+#[macro_export]
+macro_rules! lurk {
+    (@list $store:expr, [$($done:expr),*]) => {{
+        let elts: ::std::vec::Vec<$crate::ptr::Ptr<_>> = ::std::vec![$($done),*];
+        $store.list(&elts)
+    }};
+    (@list $store:expr, [$($done:expr),*] : $key:ident $($rest:tt)*) => {
+        $crate::lurk!(@list $store, [$($done,)* $crate::lurk!($store, : $key)] $($rest)*)
+    };
+    (@list $store:expr, [$($done:expr),*] $elt:tt $($rest:tt)*) => {
+        $crate::lurk!(@list $store, [$($done,)* $crate::lurk!($store, $elt)] $($rest)*)
+    };
+    ($store:expr, ( $($elts:tt)* )) => {
+        $crate::lurk!(@list $store, [] $($elts)*)
+    };
+    ($store:expr, { $ptr:expr }) => {
+        $ptr
+    };
+    ($store:expr, : $key:ident) => {
+        $store.read_token(concat!(":", stringify!($key)))
+    };
+    ($store:expr, $quoted:lifetime) => {
+        $store.read_token(stringify!($quoted))
+    };
+    ($store:expr, $token:tt) => {
+        $store.read_token(stringify!($token))
+    };
+}
+
 /// These methods provide a more ergonomic means of constructing and manipulating Lurk data.
 /// They can be thought of as a minimal DSL for working with Lurk data in Rust code.
 /// Prefer these methods when constructing literal data or assembling program fragments in
@@ -250,11 +544,33 @@ impl<F: LurkField> Store<F> {
         self.intern_list(elts)
     }
 
+    pub fn nil(&self) -> Ptr<F> {
+        lurk_sym_ptr!(self, nil)
+    }
+
+    pub fn t(&self) -> Ptr<F> {
+        lurk_sym_ptr!(self, t)
+    }
+
+    /// Reads a single token as written by `lurk!`: Lurk syntax, except that chars are written as
+    /// in Rust
+    #[doc(hidden)]
+    pub fn read_token(&mut self, token: &str) -> Ptr<F> {
+        let mut chars = token.chars();
+        match (chars.next(), chars.next(), chars.next(), chars.next()) {
+            (Some('\''), Some(c), Some('\''), None) => self.intern_char(c),
+            _ => self
+                .read(token)
+                .unwrap_or_else(|e| panic!("`{token}` isn't a Lurk token: {e}")),
+        }
+    }
+
     pub fn num<T: Into<Num<F>>>(&mut self, num: T) -> Ptr<F> {
         self.intern_num(num)
     }
 
-    pub fn uint64(&mut self, n: u64) -> Ptr<F> {
+    /// Unlike `num`, this needs no interning, since a `U64` pointer holds its value inline
+    pub fn uint64(&self, n: u64) -> Ptr<F> {
         self.intern_u64(n)
     }
 
@@ -301,6 +617,7 @@ impl<F: LurkField> Store<F> {
         }
 
         let (p, inserted) = self.cons_store.insert_full((car, cdr));
+        self.count_intern(inserted);
         let ptr = Ptr::index(ExprTag::Cons, p);
         if inserted {
             self.dehydrated.push(ptr);
@@ -314,7 +631,8 @@ impl<F: LurkField> Store<F> {
             self.hash_expr(&cdr);
         }
         assert_eq!((car.tag, cdr.tag), (ExprTag::Char, ExprTag::Str));
-        let (i, _) = self.str_store.insert_full((car, cdr));
+        let (i, inserted) = self.str_store.insert_full((car, cdr));
+        self.count_intern(inserted);
         Ptr::index(ExprTag::Str, i)
     }
 
@@ -324,7 +642,8 @@ impl<F: LurkField> Store<F> {
             self.hash_expr(&cdr);
         }
         assert_eq!((car.tag, cdr.tag), (ExprTag::Str, ExprTag::Sym));
-        let (i, _) = self.sym_store.insert_full((car, cdr));
+        let (i, inserted) = self.sym_store.insert_full((car, cdr));
+        self.count_intern(inserted);
         Ptr::index(ExprTag::Sym, i)
     }
 
@@ -334,7 +653,8 @@ impl<F: LurkField> Store<F> {
             self.hash_expr(&cdr);
         }
         assert_eq!((car.tag, cdr.tag), (ExprTag::Str, ExprTag::Sym));
-        let (i, _) = self.sym_store.insert_full((car, cdr));
+        let (i, inserted) = self.sym_store.insert_full((car, cdr));
+        self.count_intern(inserted);
         Ptr::index(ExprTag::Key, i)
     }
 
@@ -343,6 +663,7 @@ impl<F: LurkField> Store<F> {
             self.hash_expr(&payload);
         }
         let (p, inserted) = self.comm_store.insert_full((FWrap(secret), payload));
+        self.count_intern(inserted);
 
         let ptr = Ptr::index(ExprTag::Comm, p);
 
@@ -367,7 +688,7 @@ impl<F: LurkField> Store<F> {
     pub fn get_maybe_opaque(&self, tag: ExprTag, hash: F) -> Option<Ptr<F>> {
         let z_ptr = ZExprPtr::from_parts(tag, hash);
 
-        let ptr = self.z_expr_ptr_map.get(&z_ptr);
+        let ptr = self.known_z_expr_ptr(&z_ptr);
         if let Some(p) = ptr {
             return Some(*p);
         }
@@ -387,7 +708,7 @@ impl<F: LurkField> Store<F> {
 
         // Scope the first immutable borrow.
         {
-            let ptr = self.z_expr_ptr_map.get(&z_ptr);
+            let ptr = self.known_z_expr_ptr(&z_ptr);
             if let Some(p) = ptr {
                 if return_non_opaque_if_existing || p.is_opaque() {
                     return *p;
@@ -395,7 +716,8 @@ impl<F: LurkField> Store<F> {
             }
         }
 
-        let (i, _) = self.opaque_ptrs.insert_full(z_ptr);
+        let (i, inserted) = self.opaque_ptrs.insert_full(z_ptr);
+        self.count_intern(inserted);
         Ptr::opaque(tag, i)
     }
 
@@ -440,16 +762,23 @@ impl<F: LurkField> Store<F> {
             })
     }
 
-    pub fn intern_symbol_path(&mut self, path: &[String]) -> Ptr<F> {
+    /// Intern the non-keyword symbol with the given path, whose components are unescaped. The
+    /// empty path is the root symbol, represented by the null `Sym` pointer. Keywords are the same
+    /// path `cast` to `ExprTag::Key`, as in `intern_symbol`.
+    pub fn intern_symbol_path<A: AsRef<str>>(&mut self, path: &[A]) -> Ptr<F> {
         path.iter().fold(self.symnil(), |acc, s| {
-            let s_ptr = self.intern_string(s);
+            let s_ptr = self.intern_string(s.as_ref());
             self.intern_symcons(s_ptr, acc)
         })
     }
 
     pub fn intern_symbol(&mut self, sym: &Symbol) -> Ptr<F> {
         match self.symbol_cache.get(sym) {
-            Some(ptr) => *ptr,
+            Some(ptr) => {
+                let ptr = *ptr;
+                self.count_intern(false);
+                ptr
+            }
             None => {
                 use crate::tag::ExprTag::{Key, Nil};
                 let path_ptr = self.intern_symbol_path(sym.path());
@@ -482,7 +811,8 @@ impl<F: LurkField> Store<F> {
             }
             Num::U64(_) => num,
         };
-        let (ptr, _) = self.num_store.insert_full(num);
+        let (ptr, inserted) = self.num_store.insert_full(num);
+        self.count_intern(inserted);
 
         Ptr::index(ExprTag::Num, ptr)
     }
@@ -501,7 +831,7 @@ impl<F: LurkField> Store<F> {
         };
 
         self.num_store
-            .get_index_of::<Num<F>>(&num)
+            .get_index_of(&num)
             .map(|x| Ptr::index(ExprTag::Num, x))
     }
 
@@ -520,12 +850,23 @@ impl<F: LurkField> Store<F> {
         Ptr::index(ExprTag::U64, n as usize)
     }
 
+    /// Intern a whole string at once. The empty string is the null `Str` pointer, and `fetch_string`
+    /// is the inverse.
     pub fn intern_string(&mut self, s: &str) -> Ptr<F> {
         match self.str_cache.get(s) {
-            Some(ptr) => *ptr,
+            Some(ptr) => {
+                let ptr = *ptr;
+                self.count_intern(false);
+                ptr
+            }
             None => {
+                self.str_store.reserve(s.chars().count());
+                // Neither chars nor the suffixes built here can be opaque, so insert directly rather
+                // than going through `intern_strcons`
                 let ptr = s.chars().rev().fold(self.strnil(), |acc, c| {
-                    self.intern_strcons(self.intern_char(c), acc)
+                    let (i, inserted) = self.str_store.insert_full((Ptr::from(c), acc));
+                    self.count_intern(inserted);
+                    Ptr::index(ExprTag::Str, i)
                 });
                 self.str_cache.insert(s.to_string(), ptr);
                 ptr
@@ -537,6 +878,7 @@ impl<F: LurkField> Store<F> {
         // TODO: closed_env must be an env
         assert!(matches!(arg.tag, ExprTag::Sym), "ARG must be a symbol");
         let (p, inserted) = self.fun_store.insert_full((arg, body, closed_env));
+        self.count_intern(inserted);
         let ptr = Ptr::index(ExprTag::Fun, p);
         if inserted {
             self.dehydrated.push(ptr);
@@ -546,6 +888,7 @@ impl<F: LurkField> Store<F> {
 
     pub fn intern_thunk(&mut self, thunk: Thunk<F>) -> Ptr<F> {
         let (p, inserted) = self.thunk_store.insert_full(thunk);
+        self.count_intern(inserted);
         let ptr = Ptr::index(ExprTag::Thunk, p);
         if inserted {
             self.dehydrated.push(ptr);
@@ -553,6 +896,60 @@ impl<F: LurkField> Store<F> {
         ptr
     }
 
+    /// Intern an `Expression`, the inverse of `Store::fetch`
+    pub fn intern_expression(&mut self, expr: &Expression<F>) -> Ptr<F> {
+        match expr {
+            Expression::Nil => lurk_sym_ptr!(self, nil),
+            Expression::Cons(car, cdr) => self.intern_cons(*car, *cdr),
+            Expression::Comm(secret, payload) => self.intern_comm(*secret, *payload),
+            Expression::Fun(arg, body, closed_env) => self.intern_fun(*arg, *body, *closed_env),
+            Expression::Num(num) => self.intern_num(*num),
+            Expression::EmptyStr => self.strnil(),
+            Expression::Str(car, cdr) => self.intern_strcons(*car, *cdr),
+            Expression::Thunk(thunk) => self.intern_thunk(*thunk),
+            Expression::RootSym => self.intern_symnil(false),
+            Expression::RootKey => self.intern_symnil(true),
+            Expression::Sym(car, cdr) => self.intern_symcons(*car, *cdr),
+            Expression::Key(car, cdr) => self.intern_keycons(*car, *cdr),
+            Expression::Char(c) => self.intern_char(*c),
+            Expression::UInt(n) => self.intern_uint(*n),
+        }
+    }
+
+    /// Intern many `Expression`s at once, returning their pointers in input order. Space for
+    /// all the new conses is reserved up front rather than growing the store one insertion at a
+    /// time.
+    pub fn intern_batch(&mut self, items: &[Expression<F>]) -> Vec<Ptr<F>> {
+        let conses = items.iter().filter(|expr| expr.is_cons()).count();
+        self.cons_store.reserve(conses);
+        self.dehydrated.reserve(conses);
+        items
+            .iter()
+            .map(|expr| self.intern_expression(expr))
+            .collect()
+    }
+
+    /// Upgrade an opaque pointer to a concrete one once its preimage is known. The preimage is
+    /// interned and returned if it hashes to the digest `opaque` stands for; afterwards, looking
+    /// that digest up (e.g. with `intern_maybe_opaque`) resolves to the concrete pointer.
+    pub fn reify_opaque(
+        &mut self,
+        opaque: Ptr<F>,
+        preimage: Expression<F>,
+    ) -> Result<Ptr<F>, StoreError> {
+        let expected = self
+            .get_opaque_ptr(opaque)
+            .ok_or_else(|| StoreError::NotOpaque(format!("{opaque:?}")))?;
+        let ptr = self.intern_expression(&preimage);
+        match self.hash_expr(&ptr) {
+            Some(found) if found == expected => Ok(ptr),
+            found => Err(StoreError::DigestMismatch {
+                expected: expected.to_string(),
+                found: found.map_or_else(|| "unknown".into(), |z_ptr| z_ptr.to_string()),
+            }),
+        }
+    }
+
     pub fn mark_dehydrated_cont(&mut self, p: ContPtr<F>) -> ContPtr<F> {
         self.dehydrated_cont.push(p);
         p
@@ -607,11 +1004,11 @@ impl<F: LurkField> Store<F> {
     }
 
     pub fn fetch_z_expr_ptr(&self, z_ptr: &ZExprPtr<F>) -> Option<Ptr<F>> {
-        self.z_expr_ptr_map.get(z_ptr).copied()
+        self.known_z_expr_ptr(z_ptr).copied()
     }
 
     pub fn fetch_z_cont_ptr(&self, z_ptr: &ZContPtr<F>) -> Option<ContPtr<F>> {
-        self.z_cont_ptr_map.get(z_ptr).copied()
+        self.known_z_cont_ptr(z_ptr).copied()
     }
 
     pub fn fetch_maybe_sym(&self, ptr: &Ptr<F>) -> Option<Symbol> {
@@ -668,6 +1065,15 @@ impl<F: LurkField> Store<F> {
         }
     }
 
+    /// The path of a symbol or keyword, the inverse of `intern_symbol_path`. Whether it's a keyword
+    /// is told by `ptr`'s tag.
+    pub fn fetch_symbol_path(&self, ptr: &Ptr<F>) -> Option<Vec<String>> {
+        if ptr.is_opaque() {
+            return None;
+        }
+        self.fetch_symbol(ptr).map(|sym| sym.path().to_vec())
+    }
+
     pub fn fetch_strcons(&self, ptr: &Ptr<F>) -> Option<(Ptr<F>, Ptr<F>)> {
         match (ptr.tag, ptr.raw) {
             (ExprTag::Str, RawPtr::Index(x)) => {
@@ -700,6 +1106,9 @@ impl<F: LurkField> Store<F> {
         char::from_u32(ptr.raw.idx()? as u32)
     }
 
+    /// The parts of a closure: its argument symbol, its body, which is the list of forms after
+    /// the `lambda`'s arguments, and the environment it closed over. Functions of several
+    /// arguments are curried, so the body of one is a `lambda` of the rest.
     pub fn fetch_fun(&self, ptr: &Ptr<F>) -> Option<&(Ptr<F>, Ptr<F>, Ptr<F>)> {
         debug_assert!(matches!(ptr.tag, ExprTag::Fun));
         if ptr.raw.is_opaque() {
@@ -776,6 +1185,37 @@ impl<F: LurkField> Store<F> {
         }
     }
 
+    /// Like `fetch`, but reads strings and symbols out whole, and gives the digest of opaque
+    /// pointers instead of `None`
+    pub fn fetch_expr(&self, ptr: &Ptr<F>) -> Option<ExprView<F>> {
+        if ptr.is_opaque() {
+            return self
+                .hash_expr(ptr)
+                .map(|z_ptr| ExprView::Opaque(*z_ptr.value()));
+        }
+        match ptr.tag {
+            ExprTag::Nil => Some(ExprView::Nil),
+            ExprTag::Cons => self.fetch_cons(ptr).map(|(a, b)| ExprView::Cons(*a, *b)),
+            ExprTag::Comm => self
+                .fetch_comm(ptr)
+                .map(|(secret, payload)| ExprView::Comm {
+                    secret: secret.0,
+                    payload: *payload,
+                }),
+            ExprTag::Sym | ExprTag::Key => self.fetch_symbol(ptr).map(ExprView::Sym),
+            ExprTag::Num => self.fetch_num(ptr).map(|num| ExprView::Num(*num)),
+            ExprTag::Fun => self.fetch_fun(ptr).map(|(arg, body, env)| ExprView::Fun {
+                arg: *arg,
+                body: *body,
+                env: *env,
+            }),
+            ExprTag::Thunk => self.fetch_thunk(ptr).map(|thunk| ExprView::Thunk(*thunk)),
+            ExprTag::Str => self.fetch_string(ptr).map(ExprView::Str),
+            ExprTag::Char => self.fetch_char(ptr).map(ExprView::Char),
+            ExprTag::U64 => self.fetch_uint(ptr).map(ExprView::UInt),
+        }
+    }
+
     /// Returns a `Vec` of `Ptr`s representing the elements of a proper list, `ptr`.
     /// This is intended to be the inverse of `Store::list()`.
     /// IF `ptr` isn't a proper list, return None.
@@ -797,6 +1237,85 @@ impl<F: LurkField> Store<F> {
         Some(list)
     }
 
+    /// Iterate over the elements of the list starting at `head`. A list not ending in `nil`
+    /// yields an error as its last item; use `iter_list_improper` to accept dotted pairs.
+    pub fn iter_list(&self, head: Ptr<F>) -> ListIter<'_, F> {
+        ListIter::new(self, head, false)
+    }
+
+    /// Collect the elements of the possibly improper list starting at `head`, along with the tail
+    /// it ends in, which is `nil` for proper lists.
+    pub fn iter_list_improper(&self, head: Ptr<F>) -> Result<(Vec<Ptr<F>>, Ptr<F>), Error> {
+        let mut iter = ListIter::new(self, head, true);
+        let elements = iter.by_ref().collect::<Result<Vec<_>, _>>()?;
+        let tail = iter
+            .tail
+            .expect("finished iteration without error sets the tail");
+        Ok((elements, tail))
+    }
+
+    /// The subexpressions of `ptr`: the elements of conses, the payloads of commitments, the parts
+    /// of functions and the values of thunks. Other expressions, including symbols and strings,
+    /// and opaque pointers are leaves.
+    fn expr_children(&self, ptr: &Ptr<F>) -> Vec<Ptr<F>> {
+        match self.fetch(ptr) {
+            Some(Expression::Cons(car, cdr)) => vec![car, cdr],
+            Some(Expression::Comm(_, payload)) => vec![payload],
+            Some(Expression::Fun(arg, body, closed_env)) => vec![arg, body, closed_env],
+            Some(Expression::Thunk(thunk)) => vec![thunk.value],
+            _ => vec![],
+        }
+    }
+
+    /// Fold `f` over the expressions reachable from `root`, in depth-first pre-order, visiting
+    /// each distinct pointer once. See `expr_children` for which expressions are traversed.
+    pub fn fold_expr<A>(&self, root: Ptr<F>, init: A, f: impl FnMut(A, Ptr<F>) -> A) -> A {
+        self.fold_expr_with(root, true, init, f)
+    }
+
+    /// Like `fold_expr`, but if `dedupe` is false, shared subexpressions are visited once per
+    /// occurrence. Either way, cycles are only followed once.
+    pub fn fold_expr_with<A>(
+        &self,
+        root: Ptr<F>,
+        dedupe: bool,
+        init: A,
+        mut f: impl FnMut(A, Ptr<F>) -> A,
+    ) -> A {
+        enum Visit<F: LurkField> {
+            Enter(Ptr<F>),
+            Exit(Ptr<F>),
+        }
+
+        let mut acc = init;
+        // without `dedupe`, this only holds the pointers on the current path
+        let mut visited = HashSet::new();
+        let mut stack = vec![Visit::Enter(root)];
+        while let Some(visit) = stack.pop() {
+            match visit {
+                Visit::Enter(ptr) => {
+                    if !visited.insert(ptr) {
+                        continue;
+                    }
+                    acc = f(acc, ptr);
+                    stack.push(Visit::Exit(ptr));
+                    stack.extend(self.expr_children(&ptr).into_iter().rev().map(Visit::Enter));
+                }
+                Visit::Exit(ptr) => {
+                    if !dedupe {
+                        visited.remove(&ptr);
+                    }
+                }
+            }
+        }
+        acc
+    }
+
+    /// Describes what the continuation `ptr` holds, see `ContDescription`
+    pub fn describe_cont(&self, ptr: &ContPtr<F>) -> Option<ContDescription<F>> {
+        self.fetch_cont(ptr).map(|cont| cont.describe())
+    }
+
     pub fn fetch_cont(&self, ptr: &ContPtr<F>) -> Option<Continuation<F>> {
         use ContTag::*;
         match ptr.tag {
@@ -811,20 +1330,21 @@ impl<F: LurkField> Store<F> {
             Call => self
                 .call_store
                 .get_index(ptr.raw.idx()?)
-                .map(|(a, b, c)| Continuation::Call {
+                .map(|(a, b, c, d)| Continuation::Call {
                     unevaled_arg: *a,
                     saved_env: *b,
                     continuation: *c,
+                    expanded: *d,
+                }),
+            Call2 => self
+                .call2_store
+                .get_index(ptr.raw.idx()?)
+                .map(|(a, b, c, d)| Continuation::Call2 {
+                    function: *a,
+                    saved_env: *b,
+                    continuation: *c,
+                    expanded: *d,
                 }),
-            Call2 => {
-                self.call2_store
-                    .get_index(ptr.raw.idx()?)
-                    .map(|(a, b, c)| Continuation::Call2 {
-                        function: *a,
-                        saved_env: *b,
-                        continuation: *c,
-                    })
-            }
             Tail => self
                 .tail_store
                 .get_index(ptr.raw.idx()?)
@@ -901,99 +1421,25 @@ impl<F: LurkField> Store<F> {
         }
     }
 
+    /// Returns the number of continuations `cont` nests before reaching one without a parent,
+    /// like `Outermost`, or `None` if one of them isn't in the store. Since tail calls reuse their
+    /// `Tail` continuation, this stays bounded in tail-recursive loops.
+    pub fn cont_depth(&self, cont: &ContPtr<F>) -> Option<usize> {
+        let mut depth = 0;
+        let mut cont = *cont;
+        while let Some(parent) = self.fetch_cont(&cont)?.continuation() {
+            depth += 1;
+            cont = parent;
+        }
+        Some(depth)
+    }
+
     // TODO: add cycle detection to avoid infinite recursion
     pub fn get_z_expr(
         &self,
         ptr: &Ptr<F>,
         z_store: &mut Option<ZStore<F>>,
     ) -> Result<(ZExprPtr<F>, Option<ZExpr<F>>), Error> {
-        let get_z_expr_aux = |z_store: &mut Option<ZStore<F>>| {
-            let (z_ptr, z_expr) = match self.fetch(ptr) {
-                Some(Expression::Nil) => (ZExpr::Nil.z_ptr(&self.poseidon_cache), Some(ZExpr::Nil)),
-                Some(Expression::Cons(car, cdr)) => {
-                    let (z_car, _) = self.get_z_expr(&car, z_store)?;
-                    let (z_cdr, _) = self.get_z_expr(&cdr, z_store)?;
-                    let z_expr = ZExpr::Cons(z_car, z_cdr);
-                    (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
-                }
-                Some(Expression::Comm(secret, payload)) => {
-                    let (z_payload, _) = self.get_z_expr(&payload, z_store)?;
-                    let z_expr = ZExpr::Comm(secret, z_payload);
-                    (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
-                }
-                Some(Expression::Fun(args, body, env)) => {
-                    let (z_args, _) = self.get_z_expr(&args, z_store)?;
-                    let (z_env, _) = self.get_z_expr(&env, z_store)?;
-                    let (z_body, _) = self.get_z_expr(&body, z_store)?;
-                    let z_expr = ZExpr::Fun {
-                        arg: z_args,
-                        body: z_body,
-                        closed_env: z_env,
-                    };
-                    (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
-                }
-                Some(Expression::Num(n)) => {
-                    let f = match n {
-                        Num::Scalar(f) => f,
-                        Num::U64(u) => F::from_u64(u),
-                    };
-                    let z_expr = ZExpr::Num(f);
-                    (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
-                }
-                Some(Expression::Thunk(Thunk {
-                    value,
-                    continuation,
-                })) => {
-                    let (z_value, _) = self.get_z_expr(&value, z_store)?;
-                    let (z_cont, _) = self.get_z_cont(&continuation, z_store)?;
-                    let z_expr = ZExpr::Thunk(z_value, z_cont);
-                    (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
-                }
-                Some(Expression::Char(c)) => {
-                    let z_expr = ZExpr::Char(c);
-                    (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
-                }
-                Some(Expression::UInt(u)) => {
-                    let z_expr = ZExpr::UInt(u);
-                    (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
-                }
-                Some(Expression::EmptyStr) => (
-                    ZExpr::EmptyStr.z_ptr(&self.poseidon_cache),
-                    Some(ZExpr::EmptyStr),
-                ),
-                Some(Expression::Str(car, cdr)) => {
-                    let (z_car, _) = self.get_z_expr(&car, z_store)?;
-                    let (z_cdr, _) = self.get_z_expr(&cdr, z_store)?;
-                    let z_expr = ZExpr::Str(z_car, z_cdr);
-                    (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
-                }
-                Some(Expression::RootSym) => (
-                    ZExpr::RootSym.z_ptr(&self.poseidon_cache),
-                    Some(ZExpr::RootSym),
-                ),
-                Some(Expression::RootKey) => (
-                    ZExpr::RootKey.z_ptr(&self.poseidon_cache),
-                    Some(ZExpr::RootKey),
-                ),
-                Some(Expression::Sym(car, cdr)) => {
-                    let (z_car, _) = self.get_z_expr(&car, z_store)?;
-                    let (z_cdr, _) = self.get_z_expr(&cdr, z_store)?;
-                    let z_expr = ZExpr::Sym(z_car, z_cdr);
-                    (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
-                }
-                Some(Expression::Key(car, cdr)) => {
-                    let (z_car, _) = self.get_z_expr(&car, z_store)?;
-                    let (z_cdr, _) = self.get_z_expr(&cdr, z_store)?;
-                    let z_expr = ZExpr::Key(z_car, z_cdr);
-                    (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
-                }
-                None => return Err(Error("get_z_expr unknown opaque".into())),
-            };
-            self.z_expr_ptr_map.insert(z_ptr, Box::new(*ptr));
-            self.z_expr_ptr_cache
-                .insert(*ptr, Box::new((z_ptr, z_expr.clone())));
-            Ok((z_ptr, z_expr))
-        };
         if let Some(idx) = ptr.raw.opaque_idx() {
             let z_ptr = self
                 .opaque_ptrs
@@ -1002,23 +1448,190 @@ impl<F: LurkField> Store<F> {
             // TODO: should we try to dereference the opaque pointer?
             Ok((*z_ptr, None))
         } else {
-            // Store all children reachable from Ptr in ZStore
-            if z_store.is_some() {
-                let (z_ptr, z_expr) = get_z_expr_aux(z_store)?;
-                z_store
-                    .as_mut()
-                    .unwrap()
-                    .insert_z_expr(&z_ptr, z_expr.clone());
-                Ok((z_ptr, z_expr))
-            // Check the Ptr cache, used extensively in hydration
-            } else if let Some((z_ptr, z_expr)) = self.z_expr_ptr_cache.get(ptr) {
-                Ok((*z_ptr, z_expr.clone()))
-            } else {
-                get_z_expr_aux(z_store)
+            // Store all children reachable from Ptr in ZStore, if one is being built
+            self.memoize_z_data(Node::Expr(*ptr), z_store)?;
+            let (z_ptr, z_expr) = self.cached_z_expr(ptr).expect("digest was just memoized");
+            Ok((*z_ptr, z_expr.clone()))
+        }
+    }
+
+    /// Whether the digest of `node` is memoized, or known for an opaque pointer, and, when building
+    /// `z_store`, whether it's been inserted there
+    fn z_data_memoized(&self, node: &Node<F>, z_store: &Option<ZStore<F>>) -> bool {
+        match node {
+            Node::Expr(ptr) => {
+                ptr.is_opaque()
+                    || self.cached_z_expr(ptr).map_or(false, |(z_ptr, _)| {
+                        z_store.as_ref().map_or(true, |z_store| {
+                            z_store.expr_map.contains_key(z_ptr)
+                                || ZStore::immediate_z_expr(z_ptr).is_some()
+                        })
+                    })
+            }
+            Node::Cont(ptr) => {
+                ptr.is_opaque()
+                    || self.cached_z_cont(ptr).map_or(false, |(z_ptr, _)| {
+                        z_store
+                            .as_ref()
+                            .map_or(true, |z_store| z_store.cont_map.contains_key(z_ptr))
+                    })
             }
         }
     }
 
+    /// The expressions and continuations the digest of `node` is hashed from
+    fn z_children(&self, node: &Node<F>) -> Vec<Node<F>> {
+        match node {
+            Node::Expr(ptr) => match self.fetch(ptr) {
+                Some(
+                    Expression::Cons(a, b)
+                    | Expression::Str(a, b)
+                    | Expression::Sym(a, b)
+                    | Expression::Key(a, b),
+                ) => vec![Node::Expr(a), Node::Expr(b)],
+                Some(Expression::Fun(a, b, c)) => vec![Node::Expr(a), Node::Expr(b), Node::Expr(c)],
+                Some(Expression::Comm(_, a)) => vec![Node::Expr(a)],
+                Some(Expression::Thunk(Thunk {
+                    value,
+                    continuation,
+                })) => vec![Node::Expr(value), Node::Cont(continuation)],
+                _ => vec![],
+            },
+            Node::Cont(ptr) => self
+                .fetch_cont(ptr)
+                .map(|cont| Self::cont_children(&cont))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Memoizes the digests of `node` and everything under it that isn't memoized yet, children
+    /// first, using a work stack rather than recursion so that deeply nested expressions and
+    /// continuations can't overflow the stack. When building `z_store`, everything reachable
+    /// from `node` is also inserted into it.
+    fn memoize_z_data(&self, node: Node<F>, z_store: &mut Option<ZStore<F>>) -> Result<(), Error> {
+        // the common case of an already memoized node doesn't allocate a stack
+        if self.z_data_memoized(&node, z_store) {
+            return Ok(());
+        }
+        let mut stack = vec![(node, false)];
+        while let Some((node, children_memoized)) = stack.pop() {
+            if self.z_data_memoized(&node, z_store) {
+                continue;
+            }
+            if children_memoized {
+                // the children are memoized, so these don't recurse any further
+                match node {
+                    Node::Expr(ptr) => {
+                        let (z_ptr, z_expr) = self.get_z_expr_aux(&ptr, z_store)?;
+                        if let Some(z_store) = z_store {
+                            z_store.insert_z_expr(&z_ptr, z_expr);
+                        }
+                    }
+                    Node::Cont(ptr) => {
+                        self.get_z_cont_aux(&ptr, z_store)?;
+                    }
+                }
+                continue;
+            }
+            let children = self.z_children(&node);
+            stack.push((node, true));
+            stack.extend(children.into_iter().map(|child| (child, false)));
+        }
+        Ok(())
+    }
+
+    fn get_z_expr_aux(
+        &self,
+        ptr: &Ptr<F>,
+        z_store: &mut Option<ZStore<F>>,
+    ) -> Result<(ZExprPtr<F>, Option<ZExpr<F>>), Error> {
+        let (z_ptr, z_expr) = match self.fetch(ptr) {
+            Some(Expression::Nil) => (ZExpr::Nil.z_ptr(&self.poseidon_cache), Some(ZExpr::Nil)),
+            Some(Expression::Cons(car, cdr)) => {
+                let (z_car, _) = self.get_z_expr(&car, z_store)?;
+                let (z_cdr, _) = self.get_z_expr(&cdr, z_store)?;
+                let z_expr = ZExpr::Cons(z_car, z_cdr);
+                (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
+            }
+            Some(Expression::Comm(secret, payload)) => {
+                let (z_payload, _) = self.get_z_expr(&payload, z_store)?;
+                let z_expr = ZExpr::Comm(secret, z_payload);
+                (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
+            }
+            Some(Expression::Fun(args, body, env)) => {
+                let (z_args, _) = self.get_z_expr(&args, z_store)?;
+                let (z_env, _) = self.get_z_expr(&env, z_store)?;
+                let (z_body, _) = self.get_z_expr(&body, z_store)?;
+                let z_expr = ZExpr::Fun {
+                    arg: z_args,
+                    body: z_body,
+                    closed_env: z_env,
+                };
+                (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
+            }
+            Some(Expression::Num(n)) => {
+                let f = match n {
+                    Num::Scalar(f) => f,
+                    Num::U64(u) => F::from_u64(u),
+                };
+                let z_expr = ZExpr::Num(f);
+                (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
+            }
+            Some(Expression::Thunk(Thunk {
+                value,
+                continuation,
+            })) => {
+                let (z_value, _) = self.get_z_expr(&value, z_store)?;
+                let (z_cont, _) = self.get_z_cont(&continuation, z_store)?;
+                let z_expr = ZExpr::Thunk(z_value, z_cont);
+                (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
+            }
+            Some(Expression::Char(c)) => {
+                let z_expr = ZExpr::Char(c);
+                (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
+            }
+            Some(Expression::UInt(u)) => {
+                let z_expr = ZExpr::UInt(u);
+                (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
+            }
+            Some(Expression::EmptyStr) => (
+                ZExpr::EmptyStr.z_ptr(&self.poseidon_cache),
+                Some(ZExpr::EmptyStr),
+            ),
+            Some(Expression::Str(car, cdr)) => {
+                let (z_car, _) = self.get_z_expr(&car, z_store)?;
+                let (z_cdr, _) = self.get_z_expr(&cdr, z_store)?;
+                let z_expr = ZExpr::Str(z_car, z_cdr);
+                (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
+            }
+            Some(Expression::RootSym) => (
+                ZExpr::RootSym.z_ptr(&self.poseidon_cache),
+                Some(ZExpr::RootSym),
+            ),
+            Some(Expression::RootKey) => (
+                ZExpr::RootKey.z_ptr(&self.poseidon_cache),
+                Some(ZExpr::RootKey),
+            ),
+            Some(Expression::Sym(car, cdr)) => {
+                let (z_car, _) = self.get_z_expr(&car, z_store)?;
+                let (z_cdr, _) = self.get_z_expr(&cdr, z_store)?;
+                let z_expr = ZExpr::Sym(z_car, z_cdr);
+                (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
+            }
+            Some(Expression::Key(car, cdr)) => {
+                let (z_car, _) = self.get_z_expr(&car, z_store)?;
+                let (z_cdr, _) = self.get_z_expr(&cdr, z_store)?;
+                let z_expr = ZExpr::Key(z_car, z_cdr);
+                (z_expr.z_ptr(&self.poseidon_cache), Some(z_expr))
+            }
+            None => return Err(Error("get_z_expr unknown opaque".into())),
+        };
+        self.z_expr_ptr_map.insert(z_ptr, Box::new(*ptr));
+        self.z_expr_ptr_cache
+            .insert(*ptr, Box::new((z_ptr, z_expr.clone())));
+        Ok((z_ptr, z_expr))
+    }
+
     // TODO: add cycle detection to avoid infinite recursions
     pub fn get_z_cont(
         &self,
@@ -1032,219 +1645,229 @@ impl<F: LurkField> Store<F> {
                 .ok_or(Error("get_z_cont unknown opaque ".into()))?;
             // TODO: should we try to dereference the opaque pointer?
             Ok((*z_ptr, None))
-        } else if let Some((z_ptr, z_cont)) = self.z_cont_ptr_cache.get(ptr) {
-            Ok((*z_ptr, z_cont.clone()))
         } else {
-            let (z_ptr, z_cont) = match self.fetch_cont(ptr) {
-                Some(Continuation::Outermost) => {
-                    let z_cont = ZCont::<F>::Outermost;
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::Call0 {
-                    saved_env,
-                    continuation,
-                }) => {
-                    let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
-                    let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
-                    let z_cont = ZCont::<F>::Call0 {
-                        saved_env: z_env_ptr,
-                        continuation: z_cont_ptr,
-                    };
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::Call {
-                    saved_env,
-                    unevaled_arg,
-                    continuation,
-                }) => {
-                    let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
-                    let (z_arg_ptr, _) = self.get_z_expr(&unevaled_arg, z_store)?;
-                    let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
-                    let z_cont = ZCont::<F>::Call {
-                        unevaled_arg: z_arg_ptr,
-                        saved_env: z_env_ptr,
-                        continuation: z_cont_ptr,
-                    };
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::Call2 {
-                    saved_env,
-                    function,
-                    continuation,
-                }) => {
-                    let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
-                    let (z_fun_ptr, _) = self.get_z_expr(&function, z_store)?;
-                    let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
-                    let z_cont = ZCont::<F>::Call2 {
-                        function: z_fun_ptr,
-                        saved_env: z_env_ptr,
-                        continuation: z_cont_ptr,
-                    };
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::Tail {
-                    saved_env,
-                    continuation,
-                }) => {
-                    let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
-                    let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
-                    let z_cont = ZCont::<F>::Tail {
-                        saved_env: z_env_ptr,
-                        continuation: z_cont_ptr,
-                    };
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::Error) => {
-                    let z_cont = ZCont::<F>::Error;
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::Lookup {
-                    saved_env,
-                    continuation,
-                }) => {
-                    let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
-                    let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
-                    let z_cont = ZCont::<F>::Lookup {
-                        saved_env: z_env_ptr,
-                        continuation: z_cont_ptr,
-                    };
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::Unop {
+            // Store all children reachable from ContPtr in ZStore, if one is being built
+            self.memoize_z_data(Node::Cont(*ptr), z_store)?;
+            let (z_ptr, z_cont) = self.cached_z_cont(ptr).expect("digest was just memoized");
+            Ok((*z_ptr, z_cont.clone()))
+        }
+    }
+
+    fn get_z_cont_aux(
+        &self,
+        ptr: &ContPtr<F>,
+        z_store: &mut Option<ZStore<F>>,
+    ) -> Result<(ZContPtr<F>, Option<ZCont<F>>), Error> {
+        let (z_ptr, z_cont) = match self.fetch_cont(ptr) {
+            Some(Continuation::Outermost) => {
+                let z_cont = ZCont::<F>::Outermost;
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::Call0 {
+                saved_env,
+                continuation,
+            }) => {
+                let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
+                let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
+                let z_cont = ZCont::<F>::Call0 {
+                    saved_env: z_env_ptr,
+                    continuation: z_cont_ptr,
+                };
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::Call {
+                saved_env,
+                unevaled_arg,
+                continuation,
+                expanded,
+            }) => {
+                let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
+                let (z_arg_ptr, _) = self.get_z_expr(&unevaled_arg, z_store)?;
+                let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
+                let z_cont = ZCont::<F>::Call {
+                    unevaled_arg: z_arg_ptr,
+                    saved_env: z_env_ptr,
+                    continuation: z_cont_ptr,
+                    expanded,
+                };
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::Call2 {
+                saved_env,
+                function,
+                continuation,
+                expanded,
+            }) => {
+                let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
+                let (z_fun_ptr, _) = self.get_z_expr(&function, z_store)?;
+                let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
+                let z_cont = ZCont::<F>::Call2 {
+                    function: z_fun_ptr,
+                    saved_env: z_env_ptr,
+                    continuation: z_cont_ptr,
+                    expanded,
+                };
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::Tail {
+                saved_env,
+                continuation,
+            }) => {
+                let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
+                let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
+                let z_cont = ZCont::<F>::Tail {
+                    saved_env: z_env_ptr,
+                    continuation: z_cont_ptr,
+                };
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::Error) => {
+                let z_cont = ZCont::<F>::Error;
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::Lookup {
+                saved_env,
+                continuation,
+            }) => {
+                let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
+                let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
+                let z_cont = ZCont::<F>::Lookup {
+                    saved_env: z_env_ptr,
+                    continuation: z_cont_ptr,
+                };
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::Unop {
+                operator,
+                continuation,
+            }) => {
+                let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
+                let z_cont = ZCont::<F>::Unop {
                     operator,
-                    continuation,
-                }) => {
-                    let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
-                    let z_cont = ZCont::<F>::Unop {
-                        operator,
-                        continuation: z_cont_ptr,
-                    };
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::Binop {
+                    continuation: z_cont_ptr,
+                };
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::Binop {
+                operator,
+                saved_env,
+                unevaled_args,
+                continuation,
+            }) => {
+                let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
+                let (z_args_ptr, _) = self.get_z_expr(&unevaled_args, z_store)?;
+                let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
+                let z_cont = ZCont::<F>::Binop {
                     operator,
-                    saved_env,
-                    unevaled_args,
-                    continuation,
-                }) => {
-                    let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
-                    let (z_args_ptr, _) = self.get_z_expr(&unevaled_args, z_store)?;
-                    let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
-                    let z_cont = ZCont::<F>::Binop {
-                        operator,
-                        saved_env: z_env_ptr,
-                        unevaled_args: z_args_ptr,
-                        continuation: z_cont_ptr,
-                    };
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::Binop2 {
+                    saved_env: z_env_ptr,
+                    unevaled_args: z_args_ptr,
+                    continuation: z_cont_ptr,
+                };
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::Binop2 {
+                operator,
+                evaled_arg,
+                continuation,
+            }) => {
+                let (z_arg_ptr, _) = self.get_z_expr(&evaled_arg, z_store)?;
+                let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
+                let z_cont = ZCont::<F>::Binop2 {
                     operator,
-                    evaled_arg,
-                    continuation,
-                }) => {
-                    let (z_arg_ptr, _) = self.get_z_expr(&evaled_arg, z_store)?;
-                    let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
-                    let z_cont = ZCont::<F>::Binop2 {
-                        operator,
-                        evaled_arg: z_arg_ptr,
-                        continuation: z_cont_ptr,
-                    };
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::If {
-                    unevaled_args,
-                    continuation,
-                }) => {
-                    let (z_args_ptr, _) = self.get_z_expr(&unevaled_args, z_store)?;
-                    let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
-                    let z_cont = ZCont::<F>::If {
-                        unevaled_args: z_args_ptr,
-                        continuation: z_cont_ptr,
-                    };
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::Let {
-                    var,
-                    body,
-                    saved_env,
-                    continuation,
-                }) => {
-                    let (z_var_ptr, _) = self.get_z_expr(&var, z_store)?;
-                    let (z_body_ptr, _) = self.get_z_expr(&body, z_store)?;
-                    let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
-                    let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
-                    let z_cont = ZCont::<F>::Let {
-                        var: z_var_ptr,
-                        body: z_body_ptr,
-                        saved_env: z_env_ptr,
-                        continuation: z_cont_ptr,
-                    };
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::LetRec {
-                    var,
-                    body,
-                    saved_env,
-                    continuation,
-                }) => {
-                    let (z_var_ptr, _) = self.get_z_expr(&var, z_store)?;
-                    let (z_body_ptr, _) = self.get_z_expr(&body, z_store)?;
-                    let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
-                    let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
-                    let z_cont = ZCont::<F>::LetRec {
-                        var: z_var_ptr,
-                        body: z_body_ptr,
-                        saved_env: z_env_ptr,
-                        continuation: z_cont_ptr,
-                    };
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::Emit { continuation }) => {
-                    let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
-                    let z_cont = ZCont::<F>::Emit {
-                        continuation: z_cont_ptr,
-                    };
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::Dummy) => {
-                    let z_cont = ZCont::<F>::Dummy;
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                Some(Continuation::Terminal) => {
-                    let z_cont = ZCont::<F>::Terminal;
-                    let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
-                    (z_ptr, Some(z_cont))
-                }
-                None => {
-                    let (z_ptr, _) = self.get_z_cont(ptr, z_store)?;
-                    (z_ptr, None)
-                }
-            };
+                    evaled_arg: z_arg_ptr,
+                    continuation: z_cont_ptr,
+                };
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::If {
+                unevaled_args,
+                continuation,
+            }) => {
+                let (z_args_ptr, _) = self.get_z_expr(&unevaled_args, z_store)?;
+                let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
+                let z_cont = ZCont::<F>::If {
+                    unevaled_args: z_args_ptr,
+                    continuation: z_cont_ptr,
+                };
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::Let {
+                var,
+                body,
+                saved_env,
+                continuation,
+            }) => {
+                let (z_var_ptr, _) = self.get_z_expr(&var, z_store)?;
+                let (z_body_ptr, _) = self.get_z_expr(&body, z_store)?;
+                let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
+                let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
+                let z_cont = ZCont::<F>::Let {
+                    var: z_var_ptr,
+                    body: z_body_ptr,
+                    saved_env: z_env_ptr,
+                    continuation: z_cont_ptr,
+                };
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::LetRec {
+                var,
+                body,
+                saved_env,
+                continuation,
+            }) => {
+                let (z_var_ptr, _) = self.get_z_expr(&var, z_store)?;
+                let (z_body_ptr, _) = self.get_z_expr(&body, z_store)?;
+                let (z_env_ptr, _) = self.get_z_expr(&saved_env, z_store)?;
+                let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
+                let z_cont = ZCont::<F>::LetRec {
+                    var: z_var_ptr,
+                    body: z_body_ptr,
+                    saved_env: z_env_ptr,
+                    continuation: z_cont_ptr,
+                };
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::Emit { continuation }) => {
+                let (z_cont_ptr, _) = self.get_z_cont(&continuation, z_store)?;
+                let z_cont = ZCont::<F>::Emit {
+                    continuation: z_cont_ptr,
+                };
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::Dummy) => {
+                let z_cont = ZCont::<F>::Dummy;
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            Some(Continuation::Terminal) => {
+                let z_cont = ZCont::<F>::Terminal;
+                let z_ptr = z_cont.z_ptr(&self.poseidon_cache);
+                (z_ptr, Some(z_cont))
+            }
+            None => return Err(Error("get_z_cont unknown continuation".into())),
+        };
 
-            if let Some(z_store) = z_store {
-                z_store.cont_map.insert(z_ptr, z_cont.clone());
-            };
-            self.z_cont_ptr_map.insert(z_ptr, Box::new(*ptr));
-            self.z_cont_ptr_cache
-                .insert(*ptr, Box::new((z_ptr, z_cont.clone())));
-            Ok((z_ptr, z_cont))
-        }
+        if let Some(z_store) = z_store {
+            z_store.cont_map.insert(z_ptr, z_cont.clone());
+        };
+        self.z_cont_ptr_map.insert(z_ptr, Box::new(*ptr));
+        self.z_cont_ptr_cache
+            .insert(*ptr, Box::new((z_ptr, z_cont.clone())));
+        Ok((z_ptr, z_cont))
     }
 
     pub fn to_z_store_with_ptr(&self, ptr: &Ptr<F>) -> Result<(ZStore<F>, ZExprPtr<F>), Error> {
@@ -1254,6 +1877,28 @@ impl<F: LurkField> Store<F> {
         Ok((store_opt.unwrap(), z_ptr))
     }
 
+    /// The `ZStore` of the nodes reachable from any of `roots`, which are stored once however
+    /// many roots share them, along with the digests of `roots`. It describes the roots without
+    /// this store, see `ZStore::into_store`.
+    ///
+    /// # Panics
+    ///
+    /// If a root isn't a pointer into this store.
+    pub fn to_zstore(&self, roots: &[Ptr<F>]) -> ZStore<F> {
+        let mut store_opt = Some(ZStore::new());
+        let z_roots = roots
+            .iter()
+            .map(|ptr| {
+                self.get_z_expr(ptr, &mut store_opt)
+                    .expect("roots are pointers into this store")
+                    .0
+            })
+            .collect();
+        let mut z_store = store_opt.unwrap();
+        z_store.roots = z_roots;
+        z_store
+    }
+
     pub fn to_z_expr(&self, ptr: &Ptr<F>) -> Option<ZExpr<F>> {
         self.get_z_expr(ptr, &mut None).ok()?.1
     }
@@ -1262,10 +1907,18 @@ impl<F: LurkField> Store<F> {
         self.get_z_expr(ptr, &mut None).ok().map(|x| x.0)
     }
 
-    // TODO: Add errors as below
-    //pub fn hash_expr(&self, ptr: &Ptr<F>) -> Result<ZExprPtr<F>, Error> {
-    //    self.get_z_expr(ptr, None).map(|x| x.0)
-    //}
+    /// Like `hash_expr`, but reports why `ptr` couldn't be hashed. Digests of interior pointers
+    /// are memoized, and opaque pointers resolve to their known digest without hashing.
+    pub fn hash_ptr(&self, ptr: &Ptr<F>) -> Result<ZExprPtr<F>, Error> {
+        self.get_z_expr(ptr, &mut None).map(|x| x.0)
+    }
+
+    /// Drop the memoized digests of expressions and continuations, for callers short on memory.
+    /// Digests are recomputed as needed, and interning is append-only, so they never go stale.
+    pub fn clear_hash_cache(&mut self) {
+        self.z_expr_ptr_cache.clear();
+        self.z_cont_ptr_cache.clear();
+    }
 
     pub fn to_z_cont(&self, ptr: &ContPtr<F>) -> Option<ZCont<F>> {
         self.get_z_cont(ptr, &mut None).ok()?.1
@@ -1289,6 +1942,101 @@ impl<F: LurkField> Store<F> {
             .0
     }
 
+    /// Counts the entries interned so far, for deciding when a long-lived store should be reset
+    pub fn stats(&self) -> StoreStats {
+        fn bytes<K>(set: &LayeredSet<K>) -> usize {
+            set.len() * std::mem::size_of::<K>()
+        }
+        let conts = [
+            (self.call0_store.len(), bytes(&self.call0_store)),
+            (self.call_store.len(), bytes(&self.call_store)),
+            (self.call2_store.len(), bytes(&self.call2_store)),
+            (self.tail_store.len(), bytes(&self.tail_store)),
+            (self.lookup_store.len(), bytes(&self.lookup_store)),
+            (self.unop_store.len(), bytes(&self.unop_store)),
+            (self.binop_store.len(), bytes(&self.binop_store)),
+            (self.binop2_store.len(), bytes(&self.binop2_store)),
+            (self.if_store.len(), bytes(&self.if_store)),
+            (self.let_store.len(), bytes(&self.let_store)),
+            (self.letrec_store.len(), bytes(&self.letrec_store)),
+            (self.emit_store.len(), bytes(&self.emit_store)),
+        ];
+        let estimated_bytes = bytes(&self.cons_store)
+            + bytes(&self.comm_store)
+            + bytes(&self.fun_store)
+            + bytes(&self.sym_store)
+            + bytes(&self.num_store)
+            + bytes(&self.str_store)
+            + bytes(&self.thunk_store)
+            + conts.iter().map(|(_, b)| b).sum::<usize>()
+            + bytes(&self.opaque_ptrs)
+            + bytes(&self.opaque_cont_ptrs);
+        StoreStats {
+            cons: self.cons_store.len(),
+            comm: self.comm_store.len(),
+            fun: self.fun_store.len(),
+            sym: self.sym_store.len(),
+            num: self.num_store.len(),
+            str: self.str_store.len(),
+            thunk: self.thunk_store.len(),
+            cont: conts.iter().map(|(n, _)| n).sum(),
+            opaque: self.opaque_ptrs.len() + self.opaque_cont_ptrs.len(),
+            estimated_bytes,
+        }
+    }
+
+    /// How often interning has found existing entries since the store was made or the counts were
+    /// reset, see `InternStats`
+    pub fn intern_stats(&self) -> InternStats {
+        self.intern_stats
+    }
+
+    /// Starts counting `intern_stats` afresh, to measure the interning done by one piece of work
+    pub fn reset_intern_stats(&mut self) {
+        self.intern_stats = Default::default();
+    }
+
+    pub(crate) fn count_intern(&mut self, inserted: bool) {
+        if inserted {
+            self.intern_stats.misses += 1;
+        } else {
+            self.intern_stats.hits += 1;
+        }
+    }
+
+    /// Returns the `(symbol, value)` pairs bound in `env`, innermost first, including shadowed
+    /// ones. The walk stops at the first part of `env` that isn't a binding list, or that was
+    /// already visited.
+    pub fn env_bindings(&self, env: Ptr<F>) -> Vec<(Ptr<F>, Ptr<F>)> {
+        let mut bindings = vec![];
+        let mut visited = HashSet::new();
+        let mut env = env;
+        while env.tag == ExprTag::Cons && visited.insert(env) {
+            let Ok((binding, rest)) = self.car_cdr(&env) else {
+                break;
+            };
+            if binding.tag != ExprTag::Cons {
+                break;
+            }
+            let Ok((var, val)) = self.car_cdr(&binding) else {
+                break;
+            };
+            if var.tag != ExprTag::Sym {
+                break;
+            }
+            bindings.push((var, val));
+            env = rest;
+        }
+        bindings
+    }
+
+    /// Returns the value of the innermost binding of `sym` in `env`, if any
+    pub fn env_lookup(&self, env: Ptr<F>, sym: Ptr<F>) -> Option<Ptr<F>> {
+        self.env_bindings(env)
+            .into_iter()
+            .find_map(|(var, val)| (var == sym).then_some(val))
+    }
+
     pub fn car_cdr(&self, ptr: &Ptr<F>) -> Result<(Ptr<F>, Ptr<F>), Error> {
         match ptr.tag {
             ExprTag::Nil => Ok((lurk_sym_ptr!(self, nil), lurk_sym_ptr!(self, nil))),
@@ -1325,6 +2073,19 @@ impl<F: LurkField> Store<F> {
         Ptr::opaque(ExprTag::Nil, i)
     }
 
+    /// Check that `ptr` is the canonical `nil`, not merely `Nil`-tagged as `Ptr::is_nil` checks.
+    /// Opaque pointers are nil if their digest is nil's.
+    pub fn ptr_is_nil(&self, ptr: &Ptr<F>) -> bool {
+        let nil = self.expect_constants().nil;
+        if !ptr.is_nil() {
+            false
+        } else if ptr.is_opaque() {
+            self.get_opaque_ptr(*ptr) == Some(nil.z_ptr())
+        } else {
+            *ptr == nil.ptr()
+        }
+    }
+
     pub fn ptr_eq(&self, a: &Ptr<F>, b: &Ptr<F>) -> Result<bool, Error> {
         // In order to compare Ptrs, we *must* resolve the hashes. Otherwise, we risk failing to recognize equality of
         // compound data with opaque data in either element's transitive closure.
@@ -1336,6 +2097,60 @@ impl<F: LurkField> Store<F> {
         }
     }
 
+    /// Like `ptr_eq`, but hashes only when it must. Identical pointers are equal and pointers with
+    /// different tags aren't. Opaque pointers to the same digest are interned once, so two distinct
+    /// opaque pointers are unequal. Otherwise the pointers are compared by digest, which is what
+    /// decides an opaque pointer against concrete data, or concrete data with opaque parts.
+    /// Pointers that can't be hashed are unequal, rather than an error.
+    pub fn ptr_eq_opaque_aware(&self, a: &Ptr<F>, b: &Ptr<F>) -> bool {
+        if a == b {
+            return true;
+        }
+        if a.tag != b.tag || (a.is_opaque() && b.is_opaque()) {
+            return false;
+        }
+        match (self.hash_expr(a), self.hash_expr(b)) {
+            (Some(a_hash), Some(b_hash)) => a_hash == b_hash,
+            _ => false,
+        }
+    }
+
+    /// Like `ptr_eq`, but for `b` from a different store `other`. Pointers from distinct stores
+    /// can only be compared by their content hashes.
+    pub fn ptr_eq_with(&self, a: &Ptr<F>, other: &Store<F>, b: &Ptr<F>) -> Result<bool, Error> {
+        match (self.hash_expr(a), other.hash_expr(b)) {
+            (Some(a_hash), Some(b_hash)) => Ok(a_hash == b_hash),
+            _ => Err(Error(
+                "one or more values missing when comparing Ptrs for equality".into(),
+            )),
+        }
+    }
+
+    /// Returns `None` if `a` and `b` are equal, and otherwise the first position where they differ,
+    /// searching cars before cdrs. Like `ptr_eq`, this compares by hash, so opaque pointers are
+    /// equal to the data they stand for.
+    pub fn diff(&self, a: Ptr<F>, b: Ptr<F>) -> Option<PtrDiff<F>> {
+        let eq = |x: &Ptr<F>, y: &Ptr<F>| x == y || self.ptr_eq(x, y).unwrap_or(false);
+        let fetch = |ptr: &Ptr<F>| match ptr.tag {
+            ExprTag::Cons => self.fetch_cons(ptr).copied(),
+            _ => None,
+        };
+        if eq(&a, &b) {
+            return None;
+        }
+        let (mut left, mut right, mut path) = (a, b, vec![]);
+        while let (Some((l_car, l_cdr)), Some((r_car, r_cdr))) = (fetch(&left), fetch(&right)) {
+            if eq(&l_car, &r_car) {
+                path.push(PathStep::Cdr);
+                (left, right) = (l_cdr, r_cdr);
+            } else {
+                path.push(PathStep::Car);
+                (left, right) = (l_car, r_car);
+            }
+        }
+        Some(PtrDiff { path, left, right })
+    }
+
     pub fn cons_eq(&self, a: &Ptr<F>, b: &Ptr<F>) -> bool {
         assert_eq!(ExprTag::Cons, a.tag);
         assert_eq!(ExprTag::Cons, b.tag);
@@ -1377,6 +2192,33 @@ impl<F: LurkField> Store<F> {
         }
     }
 
+    /// The pointer with digest `z_ptr`, looking in the frozen store this overlays on a miss
+    fn known_z_expr_ptr(&self, z_ptr: &ZExprPtr<F>) -> Option<&Ptr<F>> {
+        self.z_expr_ptr_map
+            .get(z_ptr)
+            .or_else(|| self.base.as_ref()?.z_expr_ptr_map.get(z_ptr))
+    }
+
+    fn known_z_cont_ptr(&self, z_ptr: &ZContPtr<F>) -> Option<&ContPtr<F>> {
+        self.z_cont_ptr_map
+            .get(z_ptr)
+            .or_else(|| self.base.as_ref()?.z_cont_ptr_map.get(z_ptr))
+    }
+
+    /// The cached digest and preimage of `ptr`, looking in the frozen store this overlays on a
+    /// miss. Pointers into the frozen store mean the same in its overlays, so its digests hold.
+    fn cached_z_expr(&self, ptr: &Ptr<F>) -> Option<&(ZExprPtr<F>, Option<ZExpr<F>>)> {
+        self.z_expr_ptr_cache
+            .get(ptr)
+            .or_else(|| self.base.as_ref()?.z_expr_ptr_cache.get(ptr))
+    }
+
+    fn cached_z_cont(&self, ptr: &ContPtr<F>) -> Option<&(ZContPtr<F>, Option<ZCont<F>>)> {
+        self.z_cont_ptr_cache
+            .get(ptr)
+            .or_else(|| self.base.as_ref()?.z_cont_ptr_cache.get(ptr))
+    }
+
     /// The only places that `ZPtr`s for `Ptr`s should be created, to
     /// ensure that they are cached properly
     fn create_z_expr_ptr(&self, ptr: Ptr<F>, hash: F) -> ZExprPtr<F> {
@@ -1388,7 +2230,7 @@ impl<F: LurkField> Store<F> {
     pub fn z_expr_ptr_from_parts(&self, tag: F, value: F) -> Result<ZExprPtr<F>, Error> {
         let tag = ExprTag::from_field(&tag).ok_or(Error("ExprTag error".to_string()))?;
         let zptr = ZPtr(tag, value);
-        if self.z_expr_ptr_map.get(&zptr).is_some() {
+        if self.known_z_expr_ptr(&zptr).is_some() {
             Ok(zptr)
         } else {
             Err(Error("uncached z_expr_ptr".to_string()))
@@ -1398,7 +2240,7 @@ impl<F: LurkField> Store<F> {
     pub fn z_cont_ptr_from_parts(&self, tag: F, value: F) -> Result<ZContPtr<F>, Error> {
         let tag = ContTag::from_field(&tag).ok_or(Error("ContTag error".to_string()))?;
         let zptr = ZPtr(tag, value);
-        if self.z_cont_ptr_map.get(&zptr).is_some() {
+        if self.known_z_cont_ptr(&zptr).is_some() {
             Ok(zptr)
         } else {
             Err(Error("uncached z_cont_ptr".to_string()))
@@ -1514,6 +2356,91 @@ impl<F: LurkField> Store<F> {
         }
     }
 
+    /// Like `intern_z_expr_ptr`, but takes preimages from `provider` rather than a `ZStore`, so
+    /// they can come from a proof witness or anywhere else. Where `provider` has no preimage, the
+    /// pointer is interned opaque. Thunks are always interned opaque, since their continuations
+    /// can't be provided. Every preimage is checked against the digest it's provided for.
+    pub fn intern_zexpr(
+        &mut self,
+        z_ptr: ZExprPtr<F>,
+        mut provider: impl FnMut(ZExprPtr<F>) -> Option<ZExpr<F>>,
+    ) -> Result<Ptr<F>, StoreError> {
+        self.intern_zexpr_aux(z_ptr, &mut provider)
+    }
+
+    fn intern_zexpr_aux<P: FnMut(ZExprPtr<F>) -> Option<ZExpr<F>>>(
+        &mut self,
+        z_ptr: ZExprPtr<F>,
+        provider: &mut P,
+    ) -> Result<Ptr<F>, StoreError> {
+        if let Some(ptr) = self.fetch_z_expr_ptr(&z_ptr) {
+            return Ok(ptr);
+        }
+        let z_expr = match z_ptr.tag() {
+            ExprTag::Thunk => None,
+            _ => provider(z_ptr),
+        };
+        let Some(z_expr) = z_expr else {
+            let ptr = self.intern_maybe_opaque(z_ptr.tag(), *z_ptr.value());
+            self.create_z_expr_ptr(ptr, *z_ptr.value());
+            return Ok(ptr);
+        };
+        let found = z_expr.z_ptr(&self.poseidon_cache);
+        if found != z_ptr {
+            return Err(StoreError::DigestMismatch {
+                expected: z_ptr.to_string(),
+                found: found.to_string(),
+            });
+        }
+        use ZExpr::*;
+        let ptr = match z_expr {
+            Nil => lurk_sym_ptr!(self, nil),
+            Cons(car, cdr) => {
+                let car = self.intern_zexpr_aux(car, provider)?;
+                let cdr = self.intern_zexpr_aux(cdr, provider)?;
+                self.intern_cons(car, cdr)
+            }
+            Comm(secret, payload) => {
+                let payload = self.intern_zexpr_aux(payload, provider)?;
+                self.intern_comm(secret, payload)
+            }
+            RootSym => self.intern_symnil(false),
+            RootKey => self.intern_symnil(true),
+            Sym(car, cdr) => {
+                let car = self.intern_zexpr_aux(car, provider)?;
+                let cdr = self.intern_zexpr_aux(cdr, provider)?;
+                self.intern_symcons(car, cdr)
+            }
+            Key(car, cdr) => {
+                let car = self.intern_zexpr_aux(car, provider)?;
+                let cdr = self.intern_zexpr_aux(cdr, provider)?;
+                self.intern_keycons(car, cdr)
+            }
+            Fun {
+                arg,
+                body,
+                closed_env,
+            } => {
+                let arg = self.intern_zexpr_aux(arg, provider)?;
+                let body = self.intern_zexpr_aux(body, provider)?;
+                let env = self.intern_zexpr_aux(closed_env, provider)?;
+                self.intern_fun(arg, body, env)
+            }
+            Num(x) => self.intern_num(crate::Num::Scalar(x)),
+            EmptyStr => self.strnil(),
+            Str(car, cdr) => {
+                let car = self.intern_zexpr_aux(car, provider)?;
+                let cdr = self.intern_zexpr_aux(cdr, provider)?;
+                self.intern_strcons(car, cdr)
+            }
+            Char(c) => c.into(),
+            UInt(n) => self.intern_uint(n),
+            Thunk(..) => unreachable!("thunk preimages aren't requested"),
+        };
+        self.create_z_expr_ptr(ptr, *z_ptr.value());
+        Ok(ptr)
+    }
+
     fn create_z_cont_ptr(&self, ptr: ContPtr<F>, hash: F) -> ZContPtr<F> {
         let z_ptr = ZPtr(ptr.tag, hash);
         self.z_cont_ptr_map.insert(z_ptr, Box::new(ptr));
@@ -1544,19 +2471,23 @@ impl<F: LurkField> Store<F> {
                     saved_env,
                     unevaled_arg,
                     continuation,
+                    expanded,
                 } => Continuation::Call {
                     saved_env: self.intern_z_expr_ptr(&saved_env, z_store)?,
                     unevaled_arg: self.intern_z_expr_ptr(&unevaled_arg, z_store)?,
                     continuation: self.intern_z_cont_ptr(&continuation, z_store)?,
+                    expanded,
                 },
                 Call2 {
                     saved_env,
                     function,
                     continuation,
+                    expanded,
                 } => Continuation::Call2 {
                     saved_env: self.intern_z_expr_ptr(&saved_env, z_store)?,
                     function: self.intern_z_expr_ptr(&function, z_store)?,
                     continuation: self.intern_z_cont_ptr(&continuation, z_store)?,
+                    expanded,
                 },
                 Tail {
                     saved_env,
@@ -1715,6 +2646,11 @@ pub struct NamedConstants<F: LurkField> {
     pub product: ConstantPtrs<F>,
     pub quotient: ConstantPtrs<F>,
     pub modulo: ConstantPtrs<F>,
+    pub bit_and: ConstantPtrs<F>,
+    pub bit_or: ConstantPtrs<F>,
+    pub bit_xor: ConstantPtrs<F>,
+    pub shift_left: ConstantPtrs<F>,
+    pub shift_right: ConstantPtrs<F>,
     pub num_equal: ConstantPtrs<F>,
     pub equal: ConstantPtrs<F>,
     pub less: ConstantPtrs<F>,
@@ -1733,6 +2669,10 @@ pub struct NamedConstants<F: LurkField> {
     pub open: ConstantPtrs<F>,
     pub secret: ConstantPtrs<F>,
     pub dummy: ConstantPtrs<F>,
+    /// The function `(lambda (f) (lambda (x . xs) (f (cons x xs))))`, closed over the empty
+    /// environment, which collects the rest arguments of a variadic function. See `ContTag::Call2`
+    /// in the reducer.
+    pub rest_gatherer: ConstantPtrs<F>,
 }
 
 impl<F: LurkField> NamedConstants<F> {
@@ -1764,6 +2704,11 @@ impl<F: LurkField> NamedConstants<F> {
         let product = hash_sym("*");
         let quotient = hash_sym("/");
         let modulo = hash_sym("%");
+        let bit_and = hash_sym("bit-and");
+        let bit_or = hash_sym("bit-or");
+        let bit_xor = hash_sym("bit-xor");
+        let shift_left = hash_sym("shl");
+        let shift_right = hash_sym("shr");
         let num_equal = hash_sym("=");
         let equal = hash_sym("eq");
         let less = hash_sym("<");
@@ -1783,6 +2728,26 @@ impl<F: LurkField> NamedConstants<F> {
         let secret = hash_sym("secret");
         let dummy = hash_sym("_");
 
+        let rest_gatherer = {
+            let f = store.intern_symbol(&lurk_sym("f"));
+            let x = store.intern_symbol(&lurk_sym("x"));
+            let xs = store.intern_symbol(&lurk_sym("xs"));
+            // (f (cons x xs))
+            let consed = store.intern_cons(xs, nil_ptr);
+            let consed = store.intern_cons(x, consed);
+            let consed = store.intern_cons(cons.ptr(), consed);
+            let call = store.intern_cons(consed, nil_ptr);
+            let call = store.intern_cons(f, call);
+            // (lambda (x . xs) (f (cons x xs)))
+            let body = store.intern_cons(call, nil_ptr);
+            let formals = store.intern_cons(x, xs);
+            let gatherer = store.intern_cons(formals, body);
+            let gatherer = store.intern_cons(lambda.ptr(), gatherer);
+            let gatherer_body = store.intern_cons(gatherer, nil_ptr);
+            let fun = store.intern_fun(f, gatherer_body, nil_ptr);
+            ConstantPtrs(store.hash_expr(&fun), fun)
+        };
+
         Self {
             t,
             nil,
@@ -1802,6 +2767,11 @@ impl<F: LurkField> NamedConstants<F> {
             product,
             quotient,
             modulo,
+            bit_and,
+            bit_or,
+            bit_xor,
+            shift_left,
+            shift_right,
             num_equal,
             equal,
             less,
@@ -1820,6 +2790,7 @@ impl<F: LurkField> NamedConstants<F> {
             open,
             secret,
             dummy,
+            rest_gatherer,
         }
     }
 }
@@ -1838,19 +2809,29 @@ impl<F: LurkField> ZStore<F> {
     }
 
     pub fn to_store_with_z_ptr(&self, z_ptr: &ZExprPtr<F>) -> Result<(Store<F>, Ptr<F>), Error> {
-        let mut store = Store::new();
-
-        for z_ptr in self.expr_map.keys() {
-            store.intern_z_expr_ptr(z_ptr, self);
-        }
-        for z_ptr in self.cont_map.keys() {
-            store.intern_z_cont_ptr(z_ptr, self);
-        }
+        let mut store = self.to_store();
         match store.intern_z_expr_ptr(z_ptr, self) {
             Some(ptr_ret) => Ok((store, ptr_ret)),
             None => Err(Error("Couldn't find given ZExprPtr".into())),
         }
     }
+
+    /// Rebuilds a store from this `ZStore`, returning it along with the pointers to its `roots`,
+    /// as given to `Store::to_zstore`. A root whose data isn't all held here, as when the
+    /// `ZStore` came from elsewhere, is an opaque pointer to its digest.
+    pub fn into_store(self) -> (Store<F>, Vec<Ptr<F>>) {
+        let mut store = self.to_store();
+        let ptrs = self
+            .roots
+            .iter()
+            .map(|z_ptr| {
+                store
+                    .intern_z_expr_ptr(z_ptr, &self)
+                    .unwrap_or_else(|| store.intern_maybe_opaque(z_ptr.tag(), *z_ptr.value()))
+            })
+            .collect();
+        (store, ptrs)
+    }
 }
 
 #[cfg(test)]
@@ -1874,6 +2855,29 @@ pub mod test {
     use pasta_curves::pallas::Scalar as S1;
     use rand::rngs::OsRng;
 
+    #[test]
+    fn constant_constructors() {
+        let mut store = Store::<Fr>::default();
+        let nil = store.nil();
+        assert_eq!(ExprTag::Nil, nil.tag);
+        assert_eq!(lurk_sym_ptr!(store, nil), nil);
+        assert_eq!(ExprTag::Sym, store.t().tag);
+        assert_eq!(lurk_sym_ptr!(store, t), store.t());
+
+        for n in [0, 1, 42, u64::MAX] {
+            let num = store.num(n);
+            assert_eq!(ExprTag::Num, num.tag);
+            assert_eq!(
+                Some(Fr::from(n)),
+                store.fetch_num(&num).copied().map(Num::into_scalar)
+            );
+
+            let uint = store.uint64(n);
+            assert_eq!(ExprTag::U64, uint.tag);
+            assert_eq!(Some(UInt::U64(n)), store.fetch_uint(&uint));
+        }
+    }
+
     #[test]
     fn tag_vals() {
         assert_eq!(0, ExprTag::Nil as u64);
@@ -1911,6 +2915,43 @@ pub mod test {
         assert_eq!(0b0001_0000_0000_1111, Emit as u16);
     }
 
+    #[test]
+    fn opaque_cont_hash() {
+        let mut store = Store::<Fr>::default();
+
+        let outermost = store.intern_cont_outermost();
+        let z_ptr = store.hash_cont(&outermost).unwrap();
+        let (i, _) = store.opaque_cont_ptrs.insert_full(z_ptr);
+        let opaque = ContPtr::opaque(ContTag::Outermost, i);
+
+        assert!(opaque.is_opaque());
+        assert_eq!(Some(z_ptr), store.hash_cont(&opaque));
+    }
+
+    #[test]
+    fn intern_batch() {
+        let mut store = Store::<Fr>::default();
+
+        let (a, b) = (store.num(1), store.sym("b"));
+        let items = [
+            Expression::Cons(a, b),
+            Expression::Num(Num::U64(7)),
+            Expression::Cons(b, a),
+            Expression::Cons(a, b),
+            Expression::Char('c'),
+            Expression::EmptyStr,
+        ];
+        let ptrs = store.intern_batch(&items);
+
+        assert_eq!(items.len(), ptrs.len());
+        assert_eq!(ptrs[0], ptrs[3]);
+        assert_ne!(ptrs[0], ptrs[2]);
+        for (item, ptr) in items.iter().zip(ptrs) {
+            assert_eq!(Some(item), store.fetch(&ptr).as_ref());
+            assert_eq!(ptr, store.intern_expression(item));
+        }
+    }
+
     #[test]
     fn store() {
         let mut store = Store::<Fr>::default();
@@ -2244,6 +3285,264 @@ pub mod test {
         store.cdr(&opaque_cons).unwrap();
     }
 
+    #[test]
+    fn reify_opaque_cons() {
+        let mut source = Store::<Fr>::default();
+        let cons = make_maybe_opaque_cons(&mut source, 123, 987);
+        let cons_hash = *source.hash_expr(&cons).unwrap().value();
+
+        let mut store = Store::<Fr>::default();
+        let opaque = store.intern_opaque_cons(cons_hash);
+        assert!(store.fetch_cons(&opaque).is_none());
+
+        let num1 = store.num(123);
+        let num2 = store.num(987);
+        assert!(matches!(
+            store.reify_opaque(opaque, Expression::Cons(num2, num1)),
+            Err(StoreError::DigestMismatch { .. })
+        ));
+        assert!(matches!(
+            store.reify_opaque(num1, Expression::Cons(num1, num2)),
+            Err(StoreError::NotOpaque(_))
+        ));
+
+        let reified = store
+            .reify_opaque(opaque, Expression::Cons(num1, num2))
+            .unwrap();
+        assert!(!reified.is_opaque());
+        assert_eq!(Some(&(num1, num2)), store.fetch_cons(&reified));
+        assert_eq!(reified, store.intern_maybe_opaque_cons(cons_hash));
+        assert!(store.ptr_eq(&opaque, &reified).unwrap());
+    }
+
+    #[test]
+    fn ptr_is_nil() {
+        let mut store = Store::<Fr>::default();
+
+        let nil = lurk_sym_ptr!(store, nil);
+        assert!(store.ptr_is_nil(&nil));
+
+        let nil_ish = store.sym("nil-ish");
+        assert_eq!(ExprTag::Sym, nil_ish.tag);
+        assert!(!store.ptr_is_nil(&nil_ish));
+        // a `Nil` tag alone doesn't make a pointer nil
+        let nil_ish = nil_ish.cast(ExprTag::Nil);
+        assert!(nil_ish.is_nil());
+        assert!(!store.ptr_is_nil(&nil_ish));
+
+        let opaque_nil = store.intern_opaque(ExprTag::Nil, store.expect_constants().nil.value());
+        assert!(store.ptr_is_nil(&opaque_nil));
+        let opaque_cons = store.new_opaque_ptr().cast(ExprTag::Cons);
+        assert!(!store.ptr_is_nil(&opaque_cons));
+    }
+
+    #[test]
+    fn hash_ptr_across_stores() {
+        let mut store1 = Store::<Fr>::default();
+        let mut store2 = Store::<Fr>::default();
+        // intern something else first so that the two stores' indices differ
+        store2.read("(unrelated data)").unwrap();
+
+        let ptr1 = store1.read("(1 (2 \"three\") . x)").unwrap();
+        let ptr2 = store2.read("(1 (2 \"three\") . x)").unwrap();
+        assert_ne!(ptr1, ptr2);
+        assert_eq!(
+            store1.hash_ptr(&ptr1).unwrap(),
+            store2.hash_ptr(&ptr2).unwrap()
+        );
+
+        let cached = store1.z_expr_ptr_cache.len();
+        assert!(store1.z_expr_ptr_cache.get(&ptr1).is_some());
+        let z_ptr = store1.hash_ptr(&ptr1).unwrap();
+        assert_eq!(cached, store1.z_expr_ptr_cache.len());
+
+        let opaque = store2.intern_opaque_cons(*z_ptr.value());
+        assert_eq!(z_ptr, store2.hash_ptr(&opaque).unwrap());
+        assert!(store2.hash_ptr(&Ptr::opaque(ExprTag::Cons, 1000)).is_err());
+    }
+
+    #[test]
+    fn iter_list() {
+        let mut store = Store::<Fr>::default();
+        let nil = lurk_sym_ptr!(store, nil);
+        let nums = (1..=3u64).map(|n| store.num(n)).collect::<Vec<_>>();
+
+        let list = store.list(&nums);
+        let elements = store
+            .iter_list(list)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(nums, elements);
+        assert_eq!((nums.clone(), nil), store.iter_list_improper(list).unwrap());
+
+        assert_eq!(0, store.iter_list(nil).count());
+        assert_eq!((vec![], nil), store.iter_list_improper(nil).unwrap());
+
+        let dotted = store.read("(1 2 . 3)").unwrap();
+        let mut iter = store.iter_list(dotted);
+        assert_eq!(nums[0], iter.next().unwrap().unwrap());
+        assert_eq!(nums[1], iter.next().unwrap().unwrap());
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+        assert_eq!(
+            (nums[..2].to_vec(), nums[2]),
+            store.iter_list_improper(dotted).unwrap()
+        );
+
+        let opaque = store.intern_opaque_cons(12345.into());
+        let ends_opaque = store.cons(nums[0], opaque);
+        let mut iter = store.iter_list(ends_opaque);
+        assert_eq!(nums[0], iter.next().unwrap().unwrap());
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+        assert!(store.iter_list_improper(ends_opaque).is_err());
+
+        // a cons whose cdr is itself
+        let cyclic = Ptr::index(ExprTag::Cons, store.cons_store.len());
+        assert_eq!(cyclic, store.cons(nums[0], cyclic));
+        let mut iter = store.iter_list(cyclic);
+        assert_eq!(nums[0], iter.next().unwrap().unwrap());
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn ptr_eq_with() {
+        let mut store1 = Store::<Fr>::default();
+        let mut store2 = Store::<Fr>::default();
+        store2.read("(unrelated data)").unwrap();
+
+        let list1 = store1.read("(a (b 2) \"c\")").unwrap();
+        let list2 = store2.read("(a (b 2) \"c\")").unwrap();
+        let other = store2.read("(a (b 3) \"c\")").unwrap();
+        assert_ne!(list1, list2);
+        assert!(store1.ptr_eq_with(&list1, &store2, &list2).unwrap());
+        assert!(store2.ptr_eq_with(&list2, &store1, &list1).unwrap());
+        assert!(!store1.ptr_eq_with(&list1, &store2, &other).unwrap());
+
+        let unknown = Ptr::opaque(ExprTag::Cons, 1000);
+        assert!(store1.ptr_eq_with(&list1, &store2, &unknown).is_err());
+    }
+
+    #[test]
+    fn intern_string_roundtrip() {
+        let mut store = Store::<Fr>::default();
+
+        for string in [
+            "",
+            "a",
+            "hello, world",
+            "😀 emoji 🦀",
+            "embedded\0null\0",
+            "\0",
+        ] {
+            let ptr = store.intern_string(string);
+            assert_eq!(ExprTag::Str, ptr.tag);
+            assert_eq!(Some(string.to_string()), store.fetch_string(&ptr));
+            assert_eq!(ptr, store.intern_string(string));
+            // the fast path agrees with interning char by char
+            let slow = string.chars().rev().fold(store.strnil(), |acc, c| {
+                let c = store.intern_char(c);
+                store.intern_strcons(c, acc)
+            });
+            assert_eq!(ptr, slow);
+        }
+        assert_eq!(store.strnil(), store.intern_string(""));
+
+        let opaque = store.intern_opaque(ExprTag::Str, 12345.into());
+        assert_eq!(None, store.fetch_string(&opaque));
+        let num = store.num(1);
+        assert_eq!(None, store.fetch_string(&num));
+    }
+
+    #[test]
+    fn symbol_paths() {
+        let mut store = Store::<Fr>::default();
+
+        let root = store.intern_symbol_path::<&str>(&[]);
+        assert_eq!(store.symnil(), root);
+        assert_eq!(Some(vec![]), store.fetch_symbol_path(&root));
+
+        let path = ["lurk", "user", "foo"];
+        let foo = store.intern_symbol_path(&path);
+        assert_eq!(ExprTag::Sym, foo.tag);
+        assert_eq!(store.intern_symbol(&Symbol::sym(&path)), foo);
+        assert_eq!(
+            Some(path.map(String::from).to_vec()),
+            store.fetch_symbol_path(&foo)
+        );
+
+        let key = store.intern_symbol(&Symbol::key(&path));
+        assert_eq!(foo.cast(ExprTag::Key), key);
+        assert_eq!(store.fetch_symbol_path(&foo), store.fetch_symbol_path(&key));
+
+        let dotted = store.intern_symbol_path(&["lurk", "a.b"]);
+        let printed = store.fetch_symbol(&dotted).unwrap().fmt_to_string();
+        assert_eq!(".lurk.a\\.b", printed);
+        assert_eq!(dotted, store.read(&printed).unwrap());
+        assert_eq!(
+            Some(vec!["lurk".to_string(), "a.b".to_string()]),
+            store.fetch_symbol_path(&dotted)
+        );
+
+        let opaque = store.intern_opaque_sym(12345.into());
+        assert_eq!(None, store.fetch_symbol_path(&opaque));
+        let num = store.num(1);
+        assert_eq!(None, store.fetch_symbol_path(&num));
+    }
+
+    #[test]
+    fn cached_hashes_agree() {
+        let mut store = Store::<Fr>::default();
+        let expr = store
+            .read("(letrec ((f (lambda (x) (if (= x 0) \"done\" (f (- x 1)))))) (f 10))")
+            .unwrap();
+
+        let cold = store.hash_ptr(&expr).unwrap();
+        assert!(!store.z_expr_ptr_cache.is_empty());
+        assert_eq!(cold, store.hash_ptr(&expr).unwrap());
+
+        store.clear_hash_cache();
+        assert!(store.z_expr_ptr_cache.is_empty());
+        assert_eq!(cold, store.hash_ptr(&expr).unwrap());
+
+        let z_expr = ZExpr::from_ptr(&store, &expr).unwrap();
+        assert_eq!(cold, z_expr.z_ptr(&store.poseidon_cache));
+    }
+
+    #[test]
+    fn fold_expr() {
+        let mut store = Store::<Fr>::default();
+        let count =
+            |store: &Store<Fr>, ptr, dedupe| store.fold_expr_with(ptr, dedupe, 0, |n, _| n + 1);
+
+        let tree = store.read("((1 . 2) . (3 . \"four\"))").unwrap();
+        assert_eq!(7, store.fold_expr(tree, 0, |n, _| n + 1));
+        let leaves = store.fold_expr(tree, vec![], |mut leaves, ptr| {
+            if !ptr.is_cons() {
+                leaves.push(ptr)
+            }
+            leaves
+        });
+        assert_eq!(store.read("(1 2 3 \"four\")").unwrap(), store.list(&leaves));
+
+        let shared = store.read("(1 . 2)").unwrap();
+        let dag = store.cons(shared, shared);
+        assert_eq!(4, count(&store, dag, true));
+        assert_eq!(7, count(&store, dag, false));
+
+        let opaque = store.intern_opaque_cons(12345.into());
+        let num = store.num(1);
+        let with_opaque = store.cons(opaque, num);
+        assert_eq!(3, count(&store, with_opaque, true));
+
+        // a cons whose cdr is itself
+        let cyclic = Ptr::index(ExprTag::Cons, store.cons_store.len());
+        assert_eq!(cyclic, store.cons(num, cyclic));
+        assert_eq!(2, count(&store, cyclic, true));
+        assert_eq!(2, count(&store, cyclic, false));
+    }
+
     #[test]
     fn sym_and_key_hashes() {
         let s = &mut Store::<Fr>::default();
@@ -2389,6 +3688,543 @@ pub mod test {
         comm
     }
 
+    #[test]
+    fn stats_count_unique_entries() {
+        let store = &mut Store::<Fr>::default();
+        let before = store.stats();
+
+        let nil = lurk_sym_ptr!(store, nil);
+        let conses: Vec<_> = (0..100u64)
+            .map(|i| {
+                let num = store.num(i % 50);
+                store.cons(num, nil)
+            })
+            .collect();
+        let after = store.stats();
+        assert_eq!(before.cons + 50, after.cons);
+        assert!(after.estimated_bytes > before.estimated_bytes);
+
+        let (car, cdr) = store.car_cdr(&conses[7]).unwrap();
+        assert_eq!(conses[7], store.cons(car, cdr));
+        assert_eq!(after, store.stats());
+    }
+
+    #[test]
+    fn ptr_eq_opaque_aware() {
+        let store = &mut Store::<Fr>::default();
+        let list = store.read("(1 (2 3) \"four\")").unwrap();
+        let other = store.read("(1 (2 3) \"five\")").unwrap();
+        store.hydrate_scalar_cache();
+        let digest = *store.hash_expr(&list).unwrap().value();
+        let other_digest = *store.hash_expr(&other).unwrap().value();
+        let opaque = store.intern_opaque_cons(digest);
+        let other_opaque = store.intern_opaque_cons(other_digest);
+        assert!(opaque.is_opaque());
+
+        // opaque against its concrete counterpart, either way round
+        assert!(store.ptr_eq_opaque_aware(&opaque, &list));
+        assert!(store.ptr_eq_opaque_aware(&list, &opaque));
+        let again = store.intern_opaque_cons(digest);
+        assert!(store.ptr_eq_opaque_aware(&opaque, &again));
+        // opaque against unrelated data, concrete or opaque
+        assert!(!store.ptr_eq_opaque_aware(&opaque, &other));
+        assert!(!store.ptr_eq_opaque_aware(&opaque, &other_opaque));
+        let num = store.num(1);
+        assert!(!store.ptr_eq_opaque_aware(&opaque, &num));
+
+        // concrete data with an opaque part is equal to the fully concrete data
+        let (head, tail) = store.car_cdr(&list).unwrap();
+        let tail_digest = *store.hash_expr(&tail).unwrap().value();
+        let opaque_tail = store.intern_opaque_cons(tail_digest);
+        let partly_opaque = store.cons(head, opaque_tail);
+        assert!(store.ptr_eq_opaque_aware(&partly_opaque, &list));
+        assert!(!store.ptr_eq_opaque_aware(&partly_opaque, &other));
+        for (a, b) in [(opaque, list), (opaque, other), (partly_opaque, list)] {
+            assert_eq!(
+                store.ptr_eq(&a, &b).unwrap(),
+                store.ptr_eq_opaque_aware(&a, &b)
+            );
+        }
+    }
+
+    #[test]
+    fn intern_stats_count_hits_and_misses() {
+        let store = &mut Store::<Fr>::default();
+        let one = store.num(1);
+        let nil = lurk_sym_ptr!(store, nil);
+        store.reset_intern_stats();
+        assert_eq!(InternStats::default(), store.intern_stats());
+        assert_eq!(None, store.intern_stats().hit_rate());
+
+        let first = store.cons(one, nil);
+        assert_eq!(InternStats { hits: 0, misses: 1 }, store.intern_stats());
+        assert_eq!(first, store.cons(one, nil));
+        assert_eq!(InternStats { hits: 1, misses: 1 }, store.intern_stats());
+        assert_eq!(Some(0.5), store.intern_stats().hit_rate());
+
+        // a string interns one node per character, and then hits the cache as a whole
+        store.reset_intern_stats();
+        let abc = store.intern_string("abc");
+        assert_eq!(InternStats { hits: 0, misses: 3 }, store.intern_stats());
+        assert_eq!(abc, store.intern_string("abc"));
+        store.intern_string("zbc");
+        assert_eq!(InternStats { hits: 3, misses: 4 }, store.intern_stats());
+    }
+
+    #[test]
+    fn diff_lists() {
+        use PathStep::*;
+        let store = &mut Store::<Fr>::default();
+        let a = store.read("(1 (2 3) 4 5)").unwrap();
+        let b = store.read("(1 (2 3) 6 5)").unwrap();
+
+        assert_eq!(None, store.diff(a, a));
+        let diff = store.diff(a, b).unwrap();
+        assert_eq!(vec![Cdr, Cdr, Car], diff.path);
+        assert_eq!((store.num(4), store.num(6)), (diff.left, diff.right));
+
+        // differing tags and differing lengths
+        let c = store.read("(1 (2 \"3\") 4 5)").unwrap();
+        let diff = store.diff(a, c).unwrap();
+        assert_eq!(vec![Cdr, Car, Cdr, Car], diff.path);
+        assert_eq!(
+            (ExprTag::Num, ExprTag::Str),
+            (diff.left.tag, diff.right.tag)
+        );
+        let d = store.read("(1 (2 3) 4)").unwrap();
+        let diff = store.diff(a, d).unwrap();
+        assert_eq!(vec![Cdr, Cdr, Cdr], diff.path);
+        assert_eq!(ExprTag::Nil, diff.right.tag);
+
+        // opaque pointers are compared by digest
+        store.hydrate_scalar_cache();
+        let a_hash = *store.hash_expr(&a).unwrap().value();
+        let opaque_a = store.intern_opaque_cons(a_hash);
+        assert_eq!(None, store.diff(a, opaque_a));
+        let diff = store.diff(opaque_a, b).unwrap();
+        assert!(diff.path.is_empty());
+        assert_eq!(opaque_a, diff.left);
+    }
+
+    #[test]
+    fn fetch_expr_views() {
+        let store = &mut Store::<Fr>::default();
+        let list = store.read("(1 \"two\" #\\3 :four five 6u64)").unwrap();
+        let elts = store.fetch_list(&list).unwrap();
+        let views: Vec<_> = elts.iter().map(|p| store.fetch_expr(p).unwrap()).collect();
+        assert_eq!(
+            vec![
+                ExprView::Num(Num::U64(1)),
+                ExprView::Str("two".into()),
+                ExprView::Char('3'),
+                ExprView::Sym(Symbol::key(&["four"])),
+                ExprView::Sym(Symbol::sym(&["lurk", "user", "five"])),
+                ExprView::UInt(UInt::U64(6)),
+            ],
+            views
+        );
+        match store.fetch_expr(&list) {
+            Some(ExprView::Cons(car, _)) => assert_eq!(elts[0], car),
+            other => panic!("expected a cons, got {other:?}"),
+        }
+        let nil = lurk_sym_ptr!(store, nil);
+        assert_eq!(Some(ExprView::Nil), store.fetch_expr(&nil));
+        let empty = store.intern_string("");
+        assert_eq!(Some(ExprView::Str("".into())), store.fetch_expr(&empty));
+
+        let comm = store.hide(Fr::from(7), list);
+        assert_eq!(
+            Some(ExprView::Comm {
+                secret: Fr::from(7),
+                payload: list
+            }),
+            store.fetch_expr(&comm)
+        );
+        let arg = store.user_sym("x");
+        let fun = store.intern_fun(arg, list, nil);
+        assert!(matches!(
+            store.fetch_expr(&fun),
+            Some(ExprView::Fun { arg: a, body, env }) if a == arg && body == list && env == nil
+        ));
+
+        // opaque pointers give their digest
+        store.hydrate_scalar_cache();
+        let hash = *store.hash_expr(&list).unwrap().value();
+        let opaque = store.intern_opaque_cons(hash);
+        assert_eq!(Some(ExprView::Opaque(hash)), store.fetch_expr(&opaque));
+    }
+
+    #[test]
+    fn lurk_macro() {
+        let store = &mut Store::<Fr>::default();
+        let cases = [
+            (lurk!(store, (+ 1 (* 2 3))), "(+ 1 (* 2 3))"),
+            (
+                lurk!(store, (let ((x 1u64)) (cons x "a \"b\""))),
+                "(let ((x 1u64)) (cons x \"a \\\"b\\\"\"))",
+            ),
+            (lurk!(store, (quote (a :b 'c ()))), "(quote (a :b 'c ()))"),
+            (lurk!(store, ('x 'a' nil t)), "('x #\\a nil t)"),
+            (
+                lurk!(store, (lambda (x y) (if (<= x y) x y))),
+                "(lambda (x y) (if (<= x y) x y))",
+            ),
+            (lurk!(store, ()), "()"),
+            (lurk!(store, 7), "7"),
+        ];
+        for (built, src) in cases {
+            let read = store.read(src).unwrap();
+            assert_eq!(read, built);
+            assert_eq!(store.hash_expr(&read), store.hash_expr(&built));
+        }
+
+        let env = store.read("(current-env)").unwrap();
+        let built = lurk!(store, (eval (quote (+ 1 2)) { env }));
+        let read = store.read("(eval (quote (+ 1 2)) (current-env))").unwrap();
+        assert_eq!(store.hash_expr(&read), store.hash_expr(&built));
+    }
+
+    #[test]
+    fn symbol_digests_ignore_interning_order() {
+        let names = ["alpha", "beta", "gamma", "delta"];
+        let a = &mut Store::<Fr>::default();
+        let b = &mut Store::<Fr>::default();
+        // `b` gets extra symbols first, so the same symbols land at different indices
+        b.intern_symbol(&Symbol::sym(&["unrelated", "package"]));
+        for name in names.iter().rev() {
+            b.intern_symbol(&Symbol::key(&[*name]));
+            b.user_sym(name);
+        }
+        let a_syms: Vec<_> = names.iter().map(|name| a.user_sym(name)).collect();
+        let b_syms: Vec<_> = names.iter().map(|name| b.user_sym(name)).collect();
+        assert_ne!(a_syms, b_syms);
+        for (a_sym, b_sym) in a_syms.iter().zip(&b_syms) {
+            assert_eq!(a.hash_expr(a_sym), b.hash_expr(b_sym));
+        }
+        let a_key = a.intern_symbol(&Symbol::key(&["beta"]));
+        let b_key = b.intern_symbol(&Symbol::key(&["beta"]));
+        assert_eq!(a.hash_expr(&a_key), b.hash_expr(&b_key));
+        assert_ne!(a.hash_expr(&a_key), a.hash_expr(&a_syms[1]));
+    }
+
+    #[test]
+    fn intern_zexpr_from_provider() {
+        let store = &mut Store::<Fr>::default();
+        let expr = store
+            .read("((lambda (x) (cons x \"str\")) (1 . (2 3)) :key)")
+            .unwrap();
+        store.hydrate_scalar_cache();
+        let (z_store, z_ptr) = ZStore::new_with_expr(store, &expr);
+        let z_ptr = z_ptr.unwrap();
+
+        // with every preimage provided, the expression is rebuilt
+        let rebuilt = &mut Store::<Fr>::default();
+        let ptr = rebuilt
+            .intern_zexpr(z_ptr, |z| z_store.get_expr(&z))
+            .unwrap();
+        assert!(!ptr.is_opaque());
+        assert_eq!(Some(z_ptr), rebuilt.hash_expr(&ptr));
+        let state = initial_lurk_state();
+        assert_eq!(
+            expr.fmt_to_string(store, state),
+            ptr.fmt_to_string(rebuilt, state)
+        );
+
+        // a missing preimage leaves an opaque pointer in its place
+        let missing = store.read("(2 3)").unwrap();
+        let missing = store.hash_expr(&missing).unwrap();
+        let partial = &mut Store::<Fr>::default();
+        let ptr = partial
+            .intern_zexpr(z_ptr, |z| {
+                (z != missing).then(|| z_store.get_expr(&z)).flatten()
+            })
+            .unwrap();
+        assert_eq!(Some(z_ptr), partial.hash_expr(&ptr));
+        let (_, rest) = partial.car_cdr(&ptr).unwrap();
+        let (pair, _) = partial.car_cdr(&rest).unwrap();
+        let (one, opaque) = partial.car_cdr(&pair).unwrap();
+        assert_eq!(partial.num(1), one);
+        assert!(opaque.is_opaque());
+        assert_eq!(Some(missing), partial.hash_expr(&opaque));
+
+        // preimages that don't hash to what they're provided for are rejected
+        let wrong = store.num(7);
+        let wrong = store.hash_expr(&wrong).unwrap();
+        let result = Store::<Fr>::default().intern_zexpr(z_ptr, |z| {
+            z_store.get_expr(&if z == missing { wrong } else { z })
+        });
+        assert!(matches!(result, Err(StoreError::DigestMismatch { .. })));
+    }
+
+    #[derive(Debug)]
+    struct SumHash;
+
+    impl HashFunction<Fr> for SumHash {
+        fn hash(&self, preimage: &[Fr]) -> Fr {
+            preimage.iter().sum()
+        }
+    }
+
+    #[test]
+    fn custom_hash_function() {
+        let store = &mut Store::<Fr>::with_hash_function(Arc::new(SumHash));
+        let pair = store.read("(1 . 2)").unwrap();
+        let num_tag = ExprTag::Num.to_field::<Fr>();
+        let digest = *store.hash_expr(&pair).unwrap().value();
+        assert_eq!(num_tag + Fr::from(1) + num_tag + Fr::from(2), digest);
+
+        // the same expression in a default store is hashed with Poseidon
+        let poseidon = &mut Store::<Fr>::default();
+        let poseidon_pair = poseidon.read("(1 . 2)").unwrap();
+        assert_ne!(
+            Some(digest),
+            poseidon.hash_expr(&poseidon_pair).map(|z| *z.value())
+        );
+        let expected = poseidon
+            .poseidon_cache
+            .hash4(&[num_tag, Fr::from(1), num_tag, Fr::from(2)]);
+        assert_eq!(
+            Some(expected),
+            poseidon.hash_expr(&poseidon_pair).map(|z| *z.value())
+        );
+
+        // evaluation is unaffected, and `commit` hashes with the custom function too
+        let lang = Lang::<Fr, Coproc<Fr>>::new();
+        let expr = store.read("(commit (car (cons 1 2)))").unwrap();
+        let env = empty_sym_env(store);
+        let (output, ..) = Evaluator::new(expr, env, store, 100, &lang).eval().unwrap();
+        let secret = Fr::NON_HIDING_COMMITMENT_SECRET;
+        let one = store.num(1);
+        let one_digest = *store.hash_expr(&one).unwrap().value();
+        assert_eq!(
+            Some(secret + num_tag + one_digest),
+            store.hash_expr(&output.expr).map(|z| *z.value())
+        );
+    }
+
+    #[test]
+    fn hash_deeply_nested() {
+        let store = &mut Store::<Fr>::default();
+        let nil = lurk_sym_ptr!(store, nil);
+        let depth = 100_000u64;
+        let (mut list, mut nested) = (nil, nil);
+        for i in 0..depth {
+            let num = store.num(i);
+            list = store.cons(num, list);
+            nested = store.cons(nested, num);
+        }
+
+        // digests computed bottom-up, one node at a time
+        let nil_digest = *store.hash_expr(&nil).unwrap().value();
+        let (num_tag, cons_tag) = (ExprTag::Num.to_field(), ExprTag::Cons.to_field());
+        let nil_tag = ExprTag::Nil.to_field();
+        let (mut list_digest, mut nested_digest) = (nil_digest, nil_digest);
+        for i in 0..depth {
+            let cdr_tag = if i == 0 { nil_tag } else { cons_tag };
+            let i = Fr::from(i);
+            list_digest = store
+                .poseidon_cache
+                .hash4(&[num_tag, i, cdr_tag, list_digest]);
+            nested_digest = store
+                .poseidon_cache
+                .hash4(&[cdr_tag, nested_digest, num_tag, i]);
+        }
+        assert_eq!(list_digest, *store.hash_expr(&list).unwrap().value());
+        assert_eq!(nested_digest, *store.hash_expr(&nested).unwrap().value());
+
+        // building a `ZStore` goes through the same work stack, as do deep continuations
+        let (z_store, z_ptr) = store.to_z_store_with_ptr(&list).unwrap();
+        assert_eq!(list_digest, *z_ptr.value());
+        // the conses and `nil`, but not the immediate numbers
+        assert_eq!(depth as usize + 1, z_store.expr_map.len());
+        let mut cont = store.intern_cont_outermost();
+        for _ in 0..depth {
+            cont = Continuation::Emit { continuation: cont }.intern_aux(store);
+        }
+        let mut z_store = Some(ZStore::new());
+        let (z_cont, _) = store.get_z_cont(&cont, &mut z_store).unwrap();
+        assert_eq!(Some(z_cont), store.hash_cont(&cont));
+        assert_eq!(depth as usize + 1, z_store.unwrap().cont_map.len());
+
+        // on something small, the digest matches that of a `ZStore` built in a fresh store
+        let small = store
+            .read("((lambda (x) (cons x \"str\")) (1 . (2 3)) :key)")
+            .unwrap();
+        let fresh = &mut Store::<Fr>::default();
+        let fresh_small = fresh
+            .read("((lambda (x) (cons x \"str\")) (1 . (2 3)) :key)")
+            .unwrap();
+        let (_, z_ptr) = ZStore::new_with_expr(fresh, &fresh_small);
+        assert_eq!(z_ptr, store.hash_expr(&small));
+    }
+
+    #[test]
+    fn fetch_closure_parts() {
+        let store = &mut Store::<Fr>::default();
+        let lang = Lang::<Fr, Coproc<Fr>>::new();
+        let expr = store
+            .read("(let ((y 7) (z 8)) (lambda (x w) (+ x y)))")
+            .unwrap();
+        let env = empty_sym_env(store);
+        let (output, ..) = Evaluator::new(expr, env, store, 100, &lang).eval().unwrap();
+        assert_eq!(ExprTag::Fun, output.expr.tag);
+
+        let (arg, body, closed_env) = *store.fetch_fun(&output.expr).unwrap();
+        assert_eq!(store.user_sym("x"), arg);
+        assert_eq!(store.read("((lambda (w) (+ x y)))").unwrap(), body);
+        let (y, z) = (store.user_sym("y"), store.user_sym("z"));
+        assert_eq!(Some(store.num(7)), store.env_lookup(closed_env, y));
+        assert_eq!(Some(store.num(8)), store.env_lookup(closed_env, z));
+        assert_eq!(None, store.env_lookup(closed_env, arg));
+    }
+
+    #[test]
+    fn env_bindings_and_lookup() {
+        let store = &mut Store::<Fr>::default();
+        let expr = store
+            .read("(let ((x 1) (y 4)) (let ((x 2)) (let ((x 3)) (current-env))))")
+            .unwrap();
+        let lang = Lang::<Fr, Coproc<Fr>>::new();
+        let env = empty_sym_env(store);
+        let (output, ..) = Evaluator::new(expr, env, store, 100, &lang).eval().unwrap();
+        let env = output.expr;
+
+        let x = store.read("x").unwrap();
+        let y = store.read("y").unwrap();
+        let z = store.read("z").unwrap();
+        let nums = [1u64, 2, 3, 4].map(|n| store.num(n));
+
+        assert_eq!(
+            vec![(x, nums[2]), (x, nums[1]), (y, nums[3]), (x, nums[0])],
+            store.env_bindings(env)
+        );
+        assert_eq!(Some(nums[2]), store.env_lookup(env, x));
+        assert_eq!(Some(nums[3]), store.env_lookup(env, y));
+        assert_eq!(None, store.env_lookup(env, z));
+
+        let nil = lurk_sym_ptr!(store, nil);
+        assert!(store.env_bindings(nil).is_empty());
+        // a cons that isn't a binding ends the walk
+        let one = store.num(1);
+        let not_env = store.cons(one, env);
+        assert!(store.env_bindings(not_env).is_empty());
+    }
+
+    #[test]
+    fn z_store_roots_roundtrip() {
+        let store = &mut Store::<S1>::default();
+        let shared = store.read("(1 \"two\" (three))").unwrap();
+        let four = store.num(4);
+        let pair = store.cons(shared, shared);
+        let list = store.list(&[four, shared]);
+        let roots = [pair, list, shared];
+        store.hydrate_scalar_cache();
+
+        let z_store = store.to_zstore(&roots);
+        let (alone, z_ptr) = store.to_z_store_with_ptr(&shared).unwrap();
+        assert_eq!(z_ptr, z_store.roots[2]);
+        assert!(alone
+            .expr_map
+            .keys()
+            .all(|k| z_store.expr_map.contains_key(k)));
+
+        // a `ZStore` serialized before it had roots still deserializes, without any
+        let mut json = serde_json::to_value(&z_store).unwrap();
+        json.as_object_mut().unwrap().remove("roots").unwrap();
+        let rootless: ZStore<S1> = serde_json::from_value(json).unwrap();
+        assert_eq!(z_store.expr_map, rootless.expr_map);
+        assert!(rootless.roots.is_empty());
+
+        // the `ZStore` alone is enough to rebuild the roots
+        let json = serde_json::to_string(&z_store).unwrap();
+        let z_store: ZStore<S1> = serde_json::from_str(&json).unwrap();
+        let (rebuilt, ptrs) = z_store.into_store();
+        assert_eq!(roots.len(), ptrs.len());
+        for (root, ptr) in roots.iter().zip(&ptrs) {
+            assert!(store.ptr_eq_with(root, &rebuilt, ptr).unwrap());
+        }
+        // and still shared in the rebuilt store
+        let (car, cdr) = rebuilt.car_cdr(&ptrs[0]).unwrap();
+        assert_eq!(car, cdr);
+        assert_eq!(car, ptrs[2]);
+
+        // a root the `ZStore` doesn't hold is opaque
+        let missing = store.hash_expr(&four).unwrap();
+        let unknown = ZExprPtr::from_parts(ExprTag::Cons, *missing.value());
+        let mut z_store = ZStore::new();
+        z_store.roots.push(unknown);
+        let (rebuilt, ptrs) = z_store.into_store();
+        assert!(ptrs[0].is_opaque());
+        assert_eq!(Some(unknown), rebuilt.hash_expr(&ptrs[0]));
+    }
+
+    #[test]
+    fn z_store_merge() {
+        let store = &mut Store::<S1>::default();
+        let expr = store.read("((1 . 2) \"three\" (four))").unwrap();
+        let four = store.num(4);
+        store.hydrate_scalar_cache();
+        let full = store.to_zstore(&[expr]);
+        let z_ptr = full.roots[0];
+
+        // each part knows every pointer and the root, but holds the preimages of only some of them
+        let (mut first, mut second) = (full.clone(), full.clone());
+        for (i, preimage) in first.expr_map.values_mut().enumerate() {
+            if i % 2 == 0 {
+                *preimage = None;
+            }
+        }
+        for (i, preimage) in second.expr_map.values_mut().enumerate() {
+            if i % 2 == 1 {
+                *preimage = None;
+            }
+        }
+        assert_ne!(full, first);
+        let cache = &store.poseidon_cache;
+        first.merge(second.clone(), cache).unwrap();
+        assert_eq!(full, first);
+        // merging again, or merging entries with no preimage, changes nothing, not even the roots
+        first.merge(second, cache).unwrap();
+        first.merge(ZStore::new(), cache).unwrap();
+        assert_eq!(full, first);
+        let (rebuilt, ptrs) = first.clone().into_store();
+        assert_eq!(1, ptrs.len());
+        assert!(store.ptr_eq_with(&expr, &rebuilt, &ptrs[0]).unwrap());
+
+        // the roots of another part are appended
+        let mut more = store.to_zstore(&[four, expr]);
+        let four_z_ptr = more.roots[0];
+        more.merge(first.clone(), cache).unwrap();
+        assert_eq!(vec![four_z_ptr, z_ptr], more.roots);
+
+        // a different preimage for a known pointer is rejected, leaving the store unchanged
+        let cache = &store.poseidon_cache;
+        let mut conflicting = ZStore::new();
+        let other = ZExpr::Cons(z_ptr, z_ptr);
+        conflicting.insert_z_expr(&z_ptr, Some(other.clone()));
+        conflicting.roots.push(z_ptr);
+        assert_eq!(
+            Err(crate::z_store::MergeError::Expr(z_ptr)),
+            first.merge(conflicting.clone(), cache)
+        );
+        assert_eq!(full, first);
+
+        // and so is a preimage of an opaque pointer that doesn't hash to it
+        let mut opaque = ZStore::new();
+        opaque.insert_z_expr(&z_ptr, None);
+        assert_eq!(
+            Err(crate::z_store::MergeError::ExprPreimage(z_ptr)),
+            opaque.merge(conflicting, cache)
+        );
+        assert_eq!(None, opaque.get_expr(&z_ptr));
+        assert!(opaque.roots.is_empty());
+        let mut matching = ZStore::new();
+        let other_ptr = other.z_ptr(cache);
+        matching.insert_z_expr(&other_ptr, Some(other.clone()));
+        opaque.merge(matching, cache).unwrap();
+        assert_eq!(Some(other), opaque.get_expr(&other_ptr));
+    }
+
     #[test]
     fn commitment_z_store_roundtrip() {
         let store = &mut Store::<S1>::default();
@@ -2412,4 +4248,70 @@ pub mod test {
 
         assert!(store.open(comm3).is_none());
     }
+
+    #[test]
+    fn describe_cont() {
+        use crate::cont::ContOperator;
+
+        let store = &mut Store::<Fr>::default();
+        let outermost = store.intern_cont_outermost();
+        let env = empty_sym_env(store);
+        let args = store.read("(2 3)").unwrap();
+        let one = store.num(1);
+        let binop = Continuation::Binop {
+            operator: Op2::Sum,
+            saved_env: env,
+            unevaled_args: args,
+            continuation: outermost,
+        }
+        .intern_aux(store);
+        let described = store.describe_cont(&binop).unwrap();
+        assert_eq!(ContTag::Binop, described.tag);
+        assert_eq!(Some(ContOperator::Binop(Op2::Sum)), described.operator);
+        assert_eq!(
+            vec![("saved_env", env), ("unevaled_args", args)],
+            described.ptrs
+        );
+        assert_eq!(Some(outermost), described.continuation);
+
+        let binop2 = Continuation::Binop2 {
+            operator: Op2::Sum,
+            evaled_arg: one,
+            continuation: binop,
+        }
+        .intern_aux(store);
+        let described = store.describe_cont(&binop2).unwrap();
+        assert_eq!(vec![("evaled_arg", one)], described.ptrs);
+        assert_eq!(Some(binop), described.continuation);
+
+        let unop = Continuation::Unop {
+            operator: Op1::Car,
+            continuation: binop2,
+        }
+        .intern_aux(store);
+        let described = store.describe_cont(&unop).unwrap();
+        assert_eq!(Some(ContOperator::Unop(Op1::Car)), described.operator);
+        assert!(described.ptrs.is_empty());
+
+        let var = store.user_sym("x");
+        let letrec = Continuation::LetRec {
+            var,
+            body: var,
+            saved_env: env,
+            continuation: unop,
+        }
+        .intern_aux(store);
+        let described = store.describe_cont(&letrec).unwrap();
+        assert_eq!(ContTag::LetRec, described.tag);
+        assert_eq!(None, described.operator);
+        assert_eq!(
+            vec![("var", var), ("body", var), ("saved_env", env)],
+            described.ptrs
+        );
+
+        let described = store.describe_cont(&outermost).unwrap();
+        assert_eq!(ContTag::Outermost, described.tag);
+        assert!(described.ptrs.is_empty());
+        assert_eq!(None, described.continuation);
+    }
 }
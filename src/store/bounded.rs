@@ -0,0 +1,241 @@
+use crate::field::LurkField;
+use crate::ptr::{Ptr, RawPtr};
+use crate::store::{check_tags, Intern, StoreError};
+use crate::tag::ExprTag;
+use crate::Num;
+
+/// A fixed-capacity index set. All of its storage lives inline, so inserting never allocates.
+/// Capacities are expected to be small, so lookups are linear scans.
+#[derive(Debug)]
+struct Arena<T, const N: usize> {
+    items: [Option<T>; N],
+    len: usize,
+}
+
+impl<T: Copy + PartialEq, const N: usize> Arena<T, N> {
+    fn new() -> Self {
+        Self {
+            items: [None; N],
+            len: 0,
+        }
+    }
+
+    fn get_index_of(&self, item: &T) -> Option<usize> {
+        self.items[..self.len]
+            .iter()
+            .position(|x| x.as_ref() == Some(item))
+    }
+
+    fn get_index(&self, i: usize) -> Option<&T> {
+        self.items.get(i)?.as_ref()
+    }
+
+    /// Returns the index of `item` and whether it was newly inserted, like `IndexSet::insert_full`
+    fn insert_full(&mut self, item: T) -> Result<(usize, bool), StoreError> {
+        if let Some(i) = self.get_index_of(&item) {
+            return Ok((i, false));
+        }
+        if self.len == N {
+            return Err(StoreError::CapacityExceeded { capacity: N });
+        }
+        self.items[self.len] = Some(item);
+        self.len += 1;
+        Ok((self.len - 1, true))
+    }
+}
+
+/// A `Store` variant for small, embedded evaluations. Each of its index sets holds at most `N`
+/// entries, and interning into a full one returns `StoreError::CapacityExceeded` instead of
+/// growing, so no heap allocation happens after construction.
+#[derive(Debug)]
+pub struct BoundedStore<F: LurkField, const N: usize> {
+    cons_store: Arena<(Ptr<F>, Ptr<F>), N>,
+    str_store: Arena<(Ptr<F>, Ptr<F>), N>,
+    sym_store: Arena<(Ptr<F>, Ptr<F>), N>,
+    num_store: Arena<Num<F>, N>,
+}
+
+impl<F: LurkField, const N: usize> Default for BoundedStore<F, N> {
+    fn default() -> Self {
+        Self {
+            cons_store: Arena::new(),
+            str_store: Arena::new(),
+            sym_store: Arena::new(),
+            num_store: Arena::new(),
+        }
+    }
+}
+
+impl<F: LurkField, const N: usize> BoundedStore<F, N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fetch_cons(&self, ptr: &Ptr<F>) -> Option<&(Ptr<F>, Ptr<F>)> {
+        match (ptr.tag, ptr.raw) {
+            (ExprTag::Cons, RawPtr::Index(x)) => self.cons_store.get_index(x),
+            _ => None,
+        }
+    }
+
+    pub fn fetch_num(&self, ptr: &Ptr<F>) -> Option<&Num<F>> {
+        match (ptr.tag, ptr.raw) {
+            (ExprTag::Num, RawPtr::Index(x)) => self.num_store.get_index(x),
+            _ => None,
+        }
+    }
+
+    /// Fetches the (string, symbol) cons of a symbol or keyword, like `Store::fetch_symcons`
+    pub fn fetch_symcons(&self, ptr: &Ptr<F>) -> Option<&(Ptr<F>, Ptr<F>)> {
+        match (ptr.tag, ptr.raw) {
+            (ExprTag::Sym | ExprTag::Key, RawPtr::Index(x)) => self.sym_store.get_index(x),
+            _ => None,
+        }
+    }
+
+    pub fn fetch_string(&self, ptr: &Ptr<F>) -> Option<String> {
+        let mut string = String::new();
+        let mut ptr = *ptr;
+        loop {
+            match (ptr.tag, ptr.raw) {
+                (ExprTag::Str, RawPtr::Null) => return Some(string),
+                (ExprTag::Str, RawPtr::Index(x)) => {
+                    let (car, cdr) = self.str_store.get_index(x)?;
+                    string.push(car.as_char()?);
+                    ptr = *cdr
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+impl<F: LurkField, const N: usize> Intern<F> for BoundedStore<F, N> {
+    fn try_intern_cons(&mut self, car: Ptr<F>, cdr: Ptr<F>) -> Result<Ptr<F>, StoreError> {
+        let (i, _) = self.cons_store.insert_full((car, cdr))?;
+        Ok(Ptr::index(ExprTag::Cons, i))
+    }
+
+    fn try_intern_strcons(&mut self, car: Ptr<F>, cdr: Ptr<F>) -> Result<Ptr<F>, StoreError> {
+        check_tags(ExprTag::Str, &car, &cdr, (ExprTag::Char, ExprTag::Str))?;
+        let (i, _) = self.str_store.insert_full((car, cdr))?;
+        Ok(Ptr::index(ExprTag::Str, i))
+    }
+
+    fn try_intern_symcons(&mut self, car: Ptr<F>, cdr: Ptr<F>) -> Result<Ptr<F>, StoreError> {
+        check_tags(ExprTag::Sym, &car, &cdr, (ExprTag::Str, ExprTag::Sym))?;
+        let (i, _) = self.sym_store.insert_full((car, cdr))?;
+        Ok(Ptr::index(ExprTag::Sym, i))
+    }
+
+    fn try_intern_num(&mut self, num: Num<F>) -> Result<Ptr<F>, StoreError> {
+        // normalize as `Store::intern_num` does, so that equal numbers dedupe
+        let num = match num {
+            Num::Scalar(scalar) => scalar.to_u64().map_or(num, Num::U64),
+            Num::U64(_) => num,
+        };
+        let (i, _) = self.num_store.insert_full(num)?;
+        Ok(Ptr::index(ExprTag::Num, i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::Store;
+    use crate::Symbol;
+    use blstrs::Scalar as Fr;
+
+    fn intern_pairs<S: Intern<Fr>>(store: &mut S, n: u64) -> Result<Vec<Ptr<Fr>>, StoreError> {
+        (0..n)
+            .map(|i| {
+                let num = store.try_intern_num(Num::U64(i))?;
+                store.try_intern_cons(num, num)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bounded_store_capacity() {
+        let mut store = BoundedStore::<Fr, 4>::new();
+
+        let conses = intern_pairs(&mut store, 4).unwrap();
+        assert_eq!(
+            Err(StoreError::CapacityExceeded { capacity: 4 }),
+            intern_pairs(&mut store, 5)
+        );
+        // re-interning existing entries still succeeds once full
+        assert_eq!(conses, intern_pairs(&mut store, 4).unwrap());
+
+        let (car, cdr) = store.fetch_cons(&conses[2]).unwrap();
+        assert_eq!(car, cdr);
+        assert_eq!(Some(&Num::U64(2)), store.fetch_num(car));
+    }
+
+    #[test]
+    fn bounded_store_strings() {
+        let mut store = BoundedStore::<Fr, 3>::new();
+
+        let s = store.try_intern_string("abc").unwrap();
+        assert_eq!(Some("abc".to_string()), store.fetch_string(&s));
+        assert_eq!(Ok(s), store.try_intern_string("abc"));
+        // "bc" and "c" are shared suffixes of "abc"
+        assert!(store.try_intern_string("bc").is_ok());
+        assert_eq!(
+            Err(StoreError::CapacityExceeded { capacity: 3 }),
+            store.try_intern_string("abcd")
+        );
+        let empty = store.try_intern_string("").unwrap();
+        assert_eq!(Some(String::new()), store.fetch_string(&empty));
+    }
+
+    #[test]
+    fn bounded_store_symbols() {
+        let mut store = BoundedStore::<Fr, 8>::new();
+
+        let sym = store.try_intern_symbol(&Symbol::sym(&["ab"])).unwrap();
+        assert_eq!(ExprTag::Sym, sym.tag);
+        assert_eq!(Ok(sym), store.try_intern_symbol(&Symbol::sym(&["ab"])));
+        let (name, parent) = *store.fetch_symcons(&sym).unwrap();
+        assert_eq!(Some("ab".to_string()), store.fetch_string(&name));
+        assert_eq!(ExprTag::Sym, parent.tag);
+
+        // a keyword is the symbol with the same path
+        let key = store.try_intern_symbol(&Symbol::key(&["ab"])).unwrap();
+        assert_eq!(ExprTag::Key, key.tag);
+        assert_eq!(
+            Err(StoreError::CapacityExceeded { capacity: 8 }),
+            store.try_intern_symbol(&Symbol::sym(&["abcdefgh"]))
+        );
+    }
+
+    #[test]
+    fn wrong_tags_are_errors() {
+        let mut store = BoundedStore::<Fr, 4>::new();
+        let mut unbounded = Store::<Fr>::default();
+        let num = store.try_intern_num(Num::U64(1)).unwrap();
+        let str = Ptr::null(ExprTag::Str);
+        let expected = Err(StoreError::WrongTags {
+            tag: ExprTag::Str,
+            car: ExprTag::Num,
+            cdr: ExprTag::Str,
+        });
+
+        assert_eq!(expected, store.try_intern_strcons(num, str));
+        assert_eq!(expected, unbounded.try_intern_strcons(num, str));
+        assert_eq!(
+            Err(StoreError::WrongTags {
+                tag: ExprTag::Sym,
+                car: ExprTag::Num,
+                cdr: ExprTag::Str,
+            }),
+            store.try_intern_symcons(num, str)
+        );
+    }
+
+    #[test]
+    fn store_interns_without_bound() {
+        let mut store = Store::<Fr>::default();
+        assert_eq!(100, intern_pairs(&mut store, 100).unwrap().len());
+    }
+}
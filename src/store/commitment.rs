@@ -0,0 +1,193 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::rngs::OsRng;
+
+use crate::field::LurkField;
+use crate::ptr::Ptr;
+use crate::store::Store;
+use crate::tag::ExprTag;
+
+/// A hiding commitment to a value of some `Store`. Its `hash` is the digest of the `comm`
+/// expression, which is what Lurk's `open` takes when given a number. It displays as the
+/// `0x`-prefixed hex digits of that digest, which `FromStr` reads back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Commitment<F: LurkField> {
+    hash: F,
+}
+
+/// A string that isn't the hex digits of a field element, see `Commitment`
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid commitment: {0}")]
+pub struct CommitmentError(pub String);
+
+impl<F: LurkField> Commitment<F> {
+    pub fn hash(&self) -> F {
+        self.hash
+    }
+
+    /// The digest of the `comm` expression, the same as `hash`
+    pub fn as_field(&self) -> F {
+        self.hash
+    }
+
+    /// The commitment whose `comm` expression has digest `hash`, as computed outside a store or
+    /// received from one
+    pub fn from_field(hash: F) -> Self {
+        Self { hash }
+    }
+
+    /// The `comm` expression of this commitment in `store`, opaque unless `store` knows its
+    /// opening. This interns, so it takes `&mut self`.
+    pub fn to_ptr(&self, store: &mut Store<F>) -> Ptr<F> {
+        store.intern_maybe_opaque_comm(self.hash)
+    }
+}
+
+impl<F: LurkField> fmt::Display for Commitment<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", self.hash.hex_digits())
+    }
+}
+
+impl<F: LurkField> FromStr for Commitment<F> {
+    type Err = CommitmentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CommitmentError(s.into());
+        let digits = s.strip_prefix("0x").unwrap_or(s);
+        let mut bytes = hex::decode(digits).map_err(|_| invalid())?;
+        if bytes.len() != F::ZERO.to_bytes().len() {
+            return Err(invalid());
+        }
+        // `hex_digits` writes the bytes of `to_bytes` last first
+        bytes.reverse();
+        F::from_bytes(&bytes)
+            .map(Self::from_field)
+            .ok_or_else(invalid)
+    }
+}
+
+impl<F: LurkField> Store<F> {
+    /// Commits to `value` behind a fresh random secret, returning the commitment along with the
+    /// `comm` pointer. Unlike `commit`, which uses a fixed secret, the commitment reveals nothing
+    /// about `value`. This interns, so it takes `&mut self`.
+    pub fn commit_hiding(&mut self, value: Ptr<F>) -> (Commitment<F>, Ptr<F>) {
+        self.commit_with_secret(F::random(OsRng), value)
+    }
+
+    /// Like `commit_hiding`, but with a caller-supplied `secret`, as `hide` takes. The secret is
+    /// hashed along with `value`, so the same value committed with different secrets gives
+    /// different commitments, and the same secret reproduces the same commitment.
+    pub fn commit_with_secret(&mut self, secret: F, value: Ptr<F>) -> (Commitment<F>, Ptr<F>) {
+        let comm = self.hide(secret, value);
+        let hash = *self
+            .hash_expr(&comm)
+            .expect("a newly interned commitment can be hashed")
+            .value();
+        (Commitment { hash }, comm)
+    }
+
+    /// The value committed to by `comm`, if its opening is known to this store
+    pub fn open_commitment(&self, comm: &Commitment<F>) -> Option<Ptr<F>> {
+        let ptr = self.get_maybe_opaque(ExprTag::Comm, comm.hash)?;
+        self.fetch_comm(&ptr).map(|(_, payload)| *payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::{empty_sym_env, lang::Coproc, lang::Lang, Evaluator};
+    use crate::lurk_sym_ptr;
+    use crate::Num;
+    use blstrs::Scalar as Fr;
+
+    #[test]
+    fn commit_and_open() {
+        let store = &mut Store::<Fr>::default();
+        let value = store.read("(1 \"two\" :three)").unwrap();
+        let (commitment, comm) = store.commit_hiding(value);
+        assert_eq!(ExprTag::Comm, comm.tag);
+        assert_eq!(Some(value), store.open_commitment(&commitment));
+
+        // distinct values, and the same value committed twice, get distinct commitments
+        let other = store.read("(1 \"two\" :four)").unwrap();
+        let (other_commitment, _) = store.commit_hiding(other);
+        let (again, _) = store.commit_hiding(value);
+        assert_ne!(commitment, other_commitment);
+        assert_ne!(commitment, again);
+        assert_eq!(Some(other), store.open_commitment(&other_commitment));
+
+        // a commitment another store made can't be opened here
+        assert_eq!(None, Store::<Fr>::default().open_commitment(&commitment));
+    }
+
+    #[test]
+    fn commit_with_secret() {
+        let store = &mut Store::<Fr>::default();
+        let value = store.read("(lambda (x) x)").unwrap();
+        let (salted, comm) = store.commit_with_secret(Fr::from(1), value);
+        let (resalted, _) = store.commit_with_secret(Fr::from(2), value);
+        let (unsalted, _) = store.commit_with_secret(Fr::from(0), value);
+        assert_ne!(salted, resalted);
+        assert_ne!(salted, unsalted);
+        assert_ne!(resalted, unsalted);
+
+        // the same secret reproduces the commitment, and the secret is recovered on opening
+        assert_eq!(salted, store.commit_with_secret(Fr::from(1), value).0);
+        assert_eq!(comm, store.hide(Fr::from(1), value));
+        assert_eq!(Some((Fr::from(1), value)), store.open(comm));
+        assert_eq!(Some(value), store.open_commitment(&salted));
+
+        // a store holding the same value can't open a commitment it didn't make
+        assert_eq!(None, store.hidden(Fr::from(3), value));
+        let other_store = &mut Store::<Fr>::default();
+        let other_value = other_store.read("(lambda (x) x)").unwrap();
+        let (other, _) = other_store.commit_with_secret(Fr::from(3), other_value);
+        assert_eq!(None, store.open_commitment(&other));
+        assert_eq!(Some(other_value), other_store.open_commitment(&other));
+    }
+
+    #[test]
+    fn commitment_hex_roundtrip() {
+        let store = &mut Store::<Fr>::default();
+        let value = store.read("(1 . 2)").unwrap();
+        let (commitment, comm) = store.commit_hiding(value);
+        let hex = commitment.to_string();
+        assert!(hex.starts_with("0x"));
+        assert_eq!(Ok(commitment), hex.parse());
+        assert_eq!(Ok(commitment), hex[2..].parse());
+        assert_eq!(commitment, Commitment::from_field(commitment.as_field()));
+
+        for invalid in ["", "0x", "0x12", "zz", &format!("{hex}00"), &"f".repeat(64)] {
+            assert!(invalid.parse::<Commitment<Fr>>().is_err(), "{invalid}");
+        }
+
+        // the pointer is the `comm` expression, and opaque in a store that can't open it
+        assert_eq!(comm, commitment.to_ptr(store));
+        let other = &mut Store::<Fr>::default();
+        let opaque = commitment.to_ptr(other);
+        assert_eq!(ExprTag::Comm, opaque.tag);
+        assert!(opaque.is_opaque());
+        assert_eq!(
+            store.hash_expr(&comm).map(|z_ptr| *z_ptr.value()),
+            other.hash_expr(&opaque).map(|z_ptr| *z_ptr.value())
+        );
+    }
+
+    #[test]
+    fn open_commitment_in_lurk() {
+        let store = &mut Store::<Fr>::default();
+        let lang = Lang::<Fr, Coproc<Fr>>::new();
+        let value = store.read("(a . b)").unwrap();
+        let (commitment, _) = store.commit_hiding(value);
+
+        let open = lurk_sym_ptr!(store, open);
+        let hash = store.num(Num::Scalar(commitment.hash()));
+        let expr = store.list(&[open, hash]);
+        let env = empty_sym_env(store);
+        let (output, ..) = Evaluator::new(expr, env, store, 100, &lang).eval().unwrap();
+        assert_eq!(value, output.expr);
+    }
+}
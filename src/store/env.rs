@@ -0,0 +1,102 @@
+use crate::field::LurkField;
+use crate::ptr::Ptr;
+use crate::store::Store;
+
+impl<F: LurkField> Store<F> {
+    /// Returns `env` with `sym` bound to `val`, shadowing any earlier binding of `sym`. This is how
+    /// a host seeds the globals a program evaluates against, starting from `empty_sym_env`. This
+    /// interns, so it takes `&mut self`.
+    pub fn extend_env(&mut self, env: Ptr<F>, sym: Ptr<F>, val: Ptr<F>) -> Ptr<F> {
+        let binding = self.cons(sym, val);
+        self.cons(binding, env)
+    }
+}
+
+/// Collects bindings to extend an environment with all at once, see `Store::extend_env`
+#[derive(Clone, Debug)]
+pub struct EnvBuilder<F: LurkField> {
+    env: Ptr<F>,
+    bindings: Vec<(Ptr<F>, Ptr<F>)>,
+}
+
+impl<F: LurkField> EnvBuilder<F> {
+    /// Starts from `env`, which may itself hold bindings
+    pub fn new(env: Ptr<F>) -> Self {
+        Self {
+            env,
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Binds `sym` to `val`. A later binding of the same symbol shadows this one.
+    pub fn bind(mut self, sym: Ptr<F>, val: Ptr<F>) -> Self {
+        self.bindings.push((sym, val));
+        self
+    }
+
+    /// Interns the bindings in the order they were added, returning the new environment
+    pub fn build(self, store: &mut Store<F>) -> Ptr<F> {
+        self.bindings
+            .into_iter()
+            .fold(self.env, |env, (sym, val)| store.extend_env(env, sym, val))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::{empty_sym_env, lang::Coproc, lang::Lang, Evaluator};
+    use blstrs::Scalar as Fr;
+
+    fn eval(store: &mut Store<Fr>, src: &str, env: Ptr<Fr>) -> Ptr<Fr> {
+        let lang = Lang::<Fr, Coproc<Fr>>::new();
+        let expr = store.read(src).unwrap();
+        let (output, ..) = Evaluator::new(expr, env, store, 100, &lang)
+            .eval_checked()
+            .unwrap();
+        output.expr
+    }
+
+    #[test]
+    fn host_globals() {
+        let store = &mut Store::<Fr>::default();
+        let (x, y, greeting) = (
+            store.user_sym("x"),
+            store.user_sym("y"),
+            store.user_sym("greeting"),
+        );
+        let (one, two, hello) = (store.num(1), store.num(2), store.intern_string("hello"));
+        let empty = empty_sym_env(store);
+        let env = EnvBuilder::new(empty)
+            .bind(x, one)
+            .bind(y, two)
+            .bind(greeting, hello)
+            .build(store);
+
+        let three = store.num(3);
+        assert_eq!(three, eval(store, "(+ x y)", env));
+        let expected = store.cons(hello, two);
+        assert_eq!(expected, eval(store, "(cons greeting (* x y))", env));
+        // globals are shadowed by local bindings, and a lambda closes over them
+        assert_eq!(one, eval(store, "(let ((y x)) y)", env));
+        assert_eq!(three, eval(store, "((lambda (z) (+ x z)) y)", env));
+
+        // building is the same as extending one binding at a time
+        let one_by_one = store.extend_env(empty, x, one);
+        let one_by_one = store.extend_env(one_by_one, y, two);
+        let one_by_one = store.extend_env(one_by_one, greeting, hello);
+        assert_eq!(env, one_by_one);
+    }
+
+    #[test]
+    fn later_bindings_shadow() {
+        let store = &mut Store::<Fr>::default();
+        let x = store.user_sym("x");
+        let (one, two) = (store.num(1), store.num(2));
+        let empty = empty_sym_env(store);
+        let env = store.extend_env(empty, x, one);
+        let env = EnvBuilder::new(env).bind(x, two).build(store);
+        assert_eq!(two, eval(store, "x", env));
+        assert_eq!(EnvBuilder::new(empty).build(store), empty);
+    }
+}
@@ -0,0 +1,154 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::field::LurkField;
+use crate::store::snapshot::for_each_set;
+use crate::store::Store;
+
+/// A `Store` that can no longer be interned into, made by `Store::freeze`. It's `Sync` and cheap
+/// to clone, so threads evaluating against a common library of definitions can share one, each
+/// interning into an `overlay` of its own. It derefs to the frozen `Store`, for all the lookups
+/// that don't need `&mut`.
+#[derive(Clone, Debug)]
+pub struct FrozenStore<F: LurkField> {
+    store: Arc<Store<F>>,
+}
+
+impl<F: LurkField> Store<F> {
+    /// Freezes the store, hashing its contents first so that lookups in the frozen store find
+    /// their hashes already cached
+    pub fn freeze(mut self) -> FrozenStore<F> {
+        self.hydrate_scalar_cache();
+        macro_rules! freeze {
+            ($set:ident) => {
+                self.$set.freeze()
+            };
+        }
+        for_each_set!(freeze);
+        FrozenStore {
+            store: Arc::new(self),
+        }
+    }
+}
+
+impl<F: LurkField> Deref for FrozenStore<F> {
+    type Target = Store<F>;
+
+    fn deref(&self) -> &Store<F> {
+        &self.store
+    }
+}
+
+impl<F: LurkField> FrozenStore<F> {
+    /// A store to intern into, in which every pointer into the frozen store resolves to the same
+    /// data, and interning data the frozen store holds returns the frozen store's pointer. Its
+    /// sets are layered on the frozen store's, which are shared rather than copied, and digests
+    /// it hasn't cached are looked up in the frozen store's caches. Interning into it never
+    /// changes the frozen store or other overlays. Its `intern_stats` start from zero.
+    pub fn overlay(&self) -> Store<F> {
+        let base = &self.store;
+        let mut overlay = Store::with_poseidon_cache(base.poseidon_cache.clone());
+        macro_rules! layer {
+            ($set:ident) => {
+                overlay.$set = base.$set.layer()
+            };
+        }
+        for_each_set!(layer);
+
+        // `Store::with_poseidon_cache` filled these in for the sets just replaced
+        overlay.z_expr_ptr_map = Default::default();
+        overlay.z_cont_ptr_map = Default::default();
+        overlay.z_expr_ptr_cache = Default::default();
+        overlay.z_cont_ptr_cache = Default::default();
+        overlay.str_cache.clear();
+        overlay.symbol_cache.clear();
+        overlay.dehydrated.clear();
+        overlay.dehydrated_cont.clear();
+        // the frozen store's constants are the same pointers in the overlay
+        overlay.constants = base.constants.clone();
+        overlay.base = Some(base.clone());
+        overlay.reset_intern_stats();
+        overlay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::{empty_sym_env, lang::Coproc, lang::Lang, Evaluator};
+    use blstrs::Scalar as Fr;
+
+    #[test]
+    fn threads_share_a_frozen_store() {
+        let mut store = Store::<Fr>::default();
+        let square = store.read("(lambda (n) (* n n))").unwrap();
+        let digest = store.hash_expr(&square);
+        let frozen = store.freeze();
+
+        std::thread::scope(|scope| {
+            let threads = [3u64, 4].map(|n| {
+                let frozen = &frozen;
+                scope.spawn(move || {
+                    assert_eq!(digest, frozen.hash_expr(&square));
+                    let mut overlay = frozen.overlay();
+                    let arg = overlay.num(n);
+                    let expr = overlay.list(&[square, arg]);
+                    let env = empty_sym_env(&overlay);
+                    let lang = Lang::<Fr, Coproc<Fr>>::new();
+                    let (output, ..) = Evaluator::new(expr, env, &mut overlay, 100, &lang)
+                        .eval()
+                        .unwrap();
+                    assert_eq!(overlay.num(n * n), output.expr);
+                })
+            });
+            for thread in threads {
+                thread.join().unwrap();
+            }
+        });
+    }
+
+    #[test]
+    fn overlay_insertions_dont_affect_the_base() {
+        let mut store = Store::<Fr>::default();
+        let shared = store.read("(1 2 3)").unwrap();
+        let frozen = store.freeze();
+        let before = frozen.stats();
+
+        let mut overlay = frozen.overlay();
+        assert_eq!(frozen.hash_expr(&shared), overlay.hash_expr(&shared));
+        assert_eq!(shared, overlay.read("(1 2 3)").unwrap());
+        assert_eq!(0, overlay.intern_stats().misses);
+
+        let new = overlay.read("(4 5 6)").unwrap();
+        assert!(overlay.stats().cons > before.cons);
+        assert_eq!(before, frozen.stats());
+        assert_eq!(None, frozen.fetch_cons(&new));
+
+        // nor do other overlays see them
+        let other = frozen.overlay();
+        assert_eq!(None, other.fetch_cons(&new));
+        assert_eq!(frozen.fetch_cons(&shared), other.fetch_cons(&shared));
+    }
+
+    #[test]
+    fn overlays_share_the_base_and_its_caches() {
+        let mut store = Store::<Fr>::default();
+        let shared = store.read("(1 2 3)").unwrap();
+        let digest = store.hash_expr(&shared).unwrap();
+        let frozen = store.freeze();
+
+        let mut overlay = frozen.overlay();
+        // nothing is copied into the overlay, yet the base's entries and caches are found
+        assert_eq!(frozen.cons_store.len(), overlay.cons_store.len());
+        assert_eq!(Some(shared), overlay.fetch_z_expr_ptr(&digest));
+        assert_eq!(Some(digest), overlay.hash_expr(&shared));
+        assert!(overlay.z_expr_ptr_map.get(&digest).is_none());
+
+        // a new entry on top of the base's hashes like one interned into a single store
+        let new = overlay.read("(0 1 2 3)").unwrap();
+        let mut single = Store::<Fr>::default();
+        let expected = single.read("(0 1 2 3)").unwrap();
+        assert_eq!(single.hash_expr(&expected), overlay.hash_expr(&new));
+        assert_eq!(frozen.cons_store.len() + 1, overlay.cons_store.len());
+    }
+}
@@ -0,0 +1,344 @@
+use std::collections::{HashMap, HashSet};
+
+use once_cell::sync::OnceCell;
+
+use crate::cont::Continuation;
+use crate::expr::Thunk;
+use crate::field::LurkField;
+use crate::ptr::{ContPtr, Ptr, RawPtr, TaggedPtr};
+use crate::store::Store;
+use crate::tag::{ContTag, ExprTag, Tag};
+
+/// The `IndexSet`s of a `Store` that pointers index into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Set {
+    Cons,
+    Comm,
+    Fun,
+    /// Also holds `Nil` and `Key` entries
+    Sym,
+    Num,
+    Str,
+    Thunk,
+    Cont(ContTag),
+    Opaque,
+    OpaqueCont,
+}
+
+/// The set and index of the entry `ptr` points to, or `None` for pointers that don't point into
+/// any set, like characters or `nil`-digest pointers
+fn expr_entry<F: LurkField>(ptr: &Ptr<F>) -> Option<(Set, usize)> {
+    match ptr.raw {
+        RawPtr::Null => None,
+        RawPtr::Opaque(i) => Some((Set::Opaque, i)),
+        RawPtr::Index(i) => {
+            let set = match ptr.tag {
+                ExprTag::Cons => Set::Cons,
+                ExprTag::Comm => Set::Comm,
+                ExprTag::Fun => Set::Fun,
+                ExprTag::Nil | ExprTag::Sym | ExprTag::Key => Set::Sym,
+                ExprTag::Num => Set::Num,
+                ExprTag::Str => Set::Str,
+                ExprTag::Thunk => Set::Thunk,
+                ExprTag::Char | ExprTag::U64 => return None,
+            };
+            Some((set, i))
+        }
+    }
+}
+
+fn cont_entry<F: LurkField>(ptr: &ContPtr<F>) -> Option<(Set, usize)> {
+    match ptr.raw {
+        RawPtr::Null => None,
+        RawPtr::Opaque(i) => Some((Set::OpaqueCont, i)),
+        RawPtr::Index(i) => match ptr.tag {
+            ContTag::Outermost | ContTag::Error | ContTag::Dummy | ContTag::Terminal => None,
+            tag => Some((Set::Cont(tag), i)),
+        },
+    }
+}
+
+/// Where `Store::gc` moved each entry it kept. Pointers held across a collection must be rewritten
+/// with `ptr` or `cont_ptr` before being used with the store again.
+#[derive(Debug, Default)]
+pub struct Relocation {
+    /// For each set, the new index of every old entry, or `None` if it was collected
+    moved: HashMap<Set, Vec<Option<usize>>>,
+}
+
+impl Relocation {
+    fn new_index(&self, set: Set, i: usize) -> Option<usize> {
+        self.moved.get(&set)?.get(i).copied().flatten()
+    }
+
+    fn relocate<F: LurkField, T: Tag>(
+        &self,
+        ptr: TaggedPtr<F, T>,
+        entry: Option<(Set, usize)>,
+    ) -> Option<TaggedPtr<F, T>> {
+        match entry {
+            None => Some(ptr),
+            Some((set @ (Set::Opaque | Set::OpaqueCont), i)) => {
+                Some(TaggedPtr::opaque(ptr.tag, self.new_index(set, i)?))
+            }
+            Some((set, i)) => {
+                let j = self.new_index(set, i)?;
+                Some(ptr.map_raw_index(|_| j))
+            }
+        }
+    }
+
+    /// Returns the pointer to `ptr`'s data after the collection, or `None` if it was collected
+    pub fn ptr<F: LurkField>(&self, ptr: Ptr<F>) -> Option<Ptr<F>> {
+        self.relocate(ptr, expr_entry(&ptr))
+    }
+
+    /// Like `ptr`, for continuations
+    pub fn cont_ptr<F: LurkField>(&self, ptr: ContPtr<F>) -> Option<ContPtr<F>> {
+        self.relocate(ptr, cont_entry(&ptr))
+    }
+}
+
+/// An expression or continuation reachable from some root
+pub(super) enum Node<F: LurkField> {
+    Expr(Ptr<F>),
+    Cont(ContPtr<F>),
+}
+
+impl<F: LurkField> Store<F> {
+    /// The entries reachable from `roots`, by set
+    fn mark(&self, roots: &[Ptr<F>]) -> HashMap<Set, HashSet<usize>> {
+        let mut live: HashMap<Set, HashSet<usize>> = HashMap::new();
+        let mut stack: Vec<_> = roots.iter().map(|ptr| Node::Expr(*ptr)).collect();
+        while let Some(node) = stack.pop() {
+            let entry = match &node {
+                Node::Expr(ptr) => expr_entry(ptr),
+                Node::Cont(ptr) => cont_entry(ptr),
+            };
+            let Some((set, i)) = entry else {
+                continue;
+            };
+            if !live.entry(set).or_default().insert(i) {
+                continue;
+            }
+            let exprs =
+                |ptrs: &[Ptr<F>]| -> Vec<_> { ptrs.iter().map(|ptr| Node::Expr(*ptr)).collect() };
+            let children: Vec<_> = match set {
+                Set::Cons => self.cons_store.get_index(i).map(|(a, b)| exprs(&[*a, *b])),
+                Set::Comm => self.comm_store.get_index(i).map(|(_, a)| exprs(&[*a])),
+                Set::Fun => self
+                    .fun_store
+                    .get_index(i)
+                    .map(|(a, b, c)| exprs(&[*a, *b, *c])),
+                Set::Sym => self.sym_store.get_index(i).map(|(a, b)| exprs(&[*a, *b])),
+                Set::Str => self.str_store.get_index(i).map(|(a, b)| exprs(&[*a, *b])),
+                Set::Thunk => self
+                    .thunk_store
+                    .get_index(i)
+                    .map(|thunk| vec![Node::Expr(thunk.value), Node::Cont(thunk.continuation)]),
+                Set::Cont(tag) => self
+                    .fetch_cont(&ContPtr::index(tag, i))
+                    .map(|cont| Self::cont_children(&cont)),
+                Set::Num | Set::Opaque | Set::OpaqueCont => None,
+            }
+            .unwrap_or_default();
+            stack.extend(children);
+        }
+        live
+    }
+
+    pub(super) fn cont_children(cont: &Continuation<F>) -> Vec<Node<F>> {
+        use Continuation::*;
+        let exprs: Vec<Ptr<F>> = match cont {
+            Call0 { saved_env, .. } | Tail { saved_env, .. } | Lookup { saved_env, .. } => {
+                vec![*saved_env]
+            }
+            Call {
+                unevaled_arg,
+                saved_env,
+                ..
+            } => vec![*unevaled_arg, *saved_env],
+            Call2 {
+                saved_env,
+                function,
+                ..
+            } => vec![*saved_env, *function],
+            Binop {
+                saved_env,
+                unevaled_args,
+                ..
+            } => vec![*saved_env, *unevaled_args],
+            Binop2 { evaled_arg, .. } => vec![*evaled_arg],
+            If { unevaled_args, .. } => vec![*unevaled_args],
+            Let {
+                var,
+                body,
+                saved_env,
+                ..
+            }
+            | LetRec {
+                var,
+                body,
+                saved_env,
+                ..
+            } => vec![*var, *body, *saved_env],
+            Unop { .. } | Emit { .. } | Outermost | Error | Dummy | Terminal => vec![],
+        };
+        exprs
+            .into_iter()
+            .map(Node::Expr)
+            .chain(cont.continuation().map(Node::Cont))
+            .collect()
+    }
+
+    /// Drops every entry that isn't reachable from `roots` and compacts what remains, keeping its
+    /// order. Opaque pointers reachable from `roots` keep their digests. Pointers into this store
+    /// are invalidated, and must be rewritten with the returned `Relocation`.
+    pub fn gc(&mut self, roots: &[Ptr<F>]) -> Relocation {
+        use ContTag::*;
+        let live = self.mark(roots);
+        let mut reloc = Relocation::default();
+        macro_rules! plan {
+            ($set:expr, $kind:expr) => {{
+                let live = live.get(&$kind);
+                let mut next = 0;
+                let moved = (0..$set.len())
+                    .map(|i| {
+                        live.map_or(false, |live| live.contains(&i)).then(|| {
+                            next += 1;
+                            next - 1
+                        })
+                    })
+                    .collect();
+                reloc.moved.insert($kind, moved);
+            }};
+        }
+        plan!(self.cons_store, Set::Cons);
+        plan!(self.comm_store, Set::Comm);
+        plan!(self.fun_store, Set::Fun);
+        plan!(self.sym_store, Set::Sym);
+        plan!(self.num_store, Set::Num);
+        plan!(self.str_store, Set::Str);
+        plan!(self.thunk_store, Set::Thunk);
+        plan!(self.call0_store, Set::Cont(Call0));
+        plan!(self.call_store, Set::Cont(Call));
+        plan!(self.call2_store, Set::Cont(Call2));
+        plan!(self.tail_store, Set::Cont(Tail));
+        plan!(self.lookup_store, Set::Cont(Lookup));
+        plan!(self.unop_store, Set::Cont(Unop));
+        plan!(self.binop_store, Set::Cont(Binop));
+        plan!(self.binop2_store, Set::Cont(Binop2));
+        plan!(self.if_store, Set::Cont(If));
+        plan!(self.let_store, Set::Cont(Let));
+        plan!(self.letrec_store, Set::Cont(LetRec));
+        plan!(self.emit_store, Set::Cont(Emit));
+        plan!(self.opaque_ptrs, Set::Opaque);
+        plan!(self.opaque_cont_ptrs, Set::OpaqueCont);
+
+        // the children of live entries are live, so they always relocate
+        let p = |ptr: &Ptr<F>| reloc.ptr(*ptr).expect("collected a reachable entry");
+        let k = |ptr: &ContPtr<F>| reloc.cont_ptr(*ptr).expect("collected a reachable entry");
+        macro_rules! compact {
+            ($set:expr, $kind:expr, $entry:pat => $new:expr) => {
+                $set = std::mem::take(&mut $set)
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| reloc.new_index($kind, *i).is_some())
+                    .map(|(_, $entry)| $new)
+                    .collect()
+            };
+        }
+        compact!(self.cons_store, Set::Cons, (a, b) => (p(a), p(b)));
+        compact!(self.comm_store, Set::Comm, (secret, a) => (secret.clone(), p(a)));
+        compact!(self.fun_store, Set::Fun, (a, b, c) => (p(a), p(b), p(c)));
+        compact!(self.sym_store, Set::Sym, (a, b) => (p(a), p(b)));
+        compact!(self.num_store, Set::Num, num => *num);
+        compact!(self.str_store, Set::Str, (a, b) => (p(a), p(b)));
+        compact!(self.thunk_store, Set::Thunk, thunk => Thunk {
+            value: p(&thunk.value),
+            continuation: k(&thunk.continuation),
+        });
+        compact!(self.call0_store, Set::Cont(Call0), (a, c) => (p(a), k(c)));
+        compact!(self.call_store, Set::Cont(Call), (a, b, c, d) => (p(a), p(b), k(c), *d));
+        compact!(self.call2_store, Set::Cont(Call2), (a, b, c, d) => (p(a), p(b), k(c), *d));
+        compact!(self.tail_store, Set::Cont(Tail), (a, c) => (p(a), k(c)));
+        compact!(self.lookup_store, Set::Cont(Lookup), (a, c) => (p(a), k(c)));
+        compact!(self.unop_store, Set::Cont(Unop), (op, c) => (*op, k(c)));
+        compact!(self.binop_store, Set::Cont(Binop), (op, a, b, c) => (*op, p(a), p(b), k(c)));
+        compact!(self.binop2_store, Set::Cont(Binop2), (op, a, c) => (*op, p(a), k(c)));
+        compact!(self.if_store, Set::Cont(If), (a, c) => (p(a), k(c)));
+        compact!(self.let_store, Set::Cont(Let), (a, b, c, d) => (p(a), p(b), p(c), k(d)));
+        compact!(self.letrec_store, Set::Cont(LetRec), (a, b, c, d) => (p(a), p(b), p(c), k(d)));
+        compact!(self.emit_store, Set::Cont(Emit), c => k(c));
+        compact!(self.opaque_ptrs, Set::Opaque, z_ptr => *z_ptr);
+        compact!(self.opaque_cont_ptrs, Set::OpaqueCont, z_ptr => *z_ptr);
+
+        // the caches hold old pointers, so they're dropped as when loading a snapshot, along with
+        // those of the frozen store an overlay falls back to
+        self.base = None;
+        self.z_expr_ptr_map = Default::default();
+        self.z_cont_ptr_map = Default::default();
+        self.z_expr_ptr_cache = Default::default();
+        self.z_cont_ptr_cache = Default::default();
+        self.str_cache.clear();
+        self.symbol_cache.clear();
+        self.constants = OnceCell::new();
+        self.dehydrated = (0..self.cons_store.len())
+            .map(|i| Ptr::index(ExprTag::Cons, i))
+            .chain((0..self.comm_store.len()).map(|i| Ptr::index(ExprTag::Comm, i)))
+            .chain((0..self.fun_store.len()).map(|i| Ptr::index(ExprTag::Fun, i)))
+            .chain((0..self.thunk_store.len()).map(|i| Ptr::index(ExprTag::Thunk, i)))
+            .collect();
+        self.dehydrated_cont.clear();
+        self.ensure_constants();
+        reloc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::Write;
+    use blstrs::Scalar as Fr;
+
+    #[test]
+    fn gc_keeps_only_reachable() {
+        let store = &mut Store::<Fr>::default();
+        let kept = store.read("(1 (2 3) \"four\")").unwrap();
+        let dropped = store.read("((5 6) 7 8 9 10)").unwrap();
+        store.hydrate_scalar_cache();
+        let dropped_hash = *store.hash_expr(&dropped).unwrap().value();
+        // only the digest of `dropped` is reachable from the root
+        let opaque = store.intern_opaque_cons(dropped_hash);
+        let root = store.cons(kept, opaque);
+        let root_hash = store.hash_expr(&root).unwrap();
+        let printed = kept.fmt_to_string(store, crate::state::initial_lurk_state());
+        let before = store.stats();
+
+        let reloc = store.gc(&[root]);
+        let after = store.stats();
+        assert!(after.cons < before.cons);
+        assert!(after.num < before.num);
+        assert_eq!(before.opaque, after.opaque);
+        assert_eq!(None, reloc.ptr(dropped));
+
+        let root = reloc.ptr(root).unwrap();
+        let kept = reloc.ptr(kept).unwrap();
+        let opaque = reloc.ptr(opaque).unwrap();
+        assert_eq!((kept, opaque), store.car_cdr(&root).unwrap());
+        assert!(opaque.is_opaque());
+        assert_eq!(
+            printed,
+            kept.fmt_to_string(store, crate::state::initial_lurk_state())
+        );
+        store.hydrate_scalar_cache();
+        assert_eq!(Some(root_hash), store.hash_expr(&root));
+        assert_eq!(dropped_hash, *store.hash_expr(&opaque).unwrap().value());
+
+        // the store keeps working after a collection
+        let two = store.num(2);
+        let (_, rest) = store.car_cdr(&kept).unwrap();
+        let (inner, _) = store.car_cdr(&rest).unwrap();
+        assert_eq!(two, store.car_cdr(&inner).unwrap().0);
+    }
+}
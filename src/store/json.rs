@@ -0,0 +1,237 @@
+use num_bigint::BigUint;
+use serde_json::{json, Value};
+
+use crate::field::LurkField;
+use crate::ptr::Ptr;
+use crate::store::Store;
+use crate::symbol::Symbol;
+use crate::tag::ExprTag;
+use crate::{Num, UInt};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum JsonError {
+    #[error("Malformed expression: {0}")]
+    Malformed(String),
+    #[error("Invalid field element: {0}")]
+    InvalidNumber(String),
+    #[error("Invalid symbol: {0}")]
+    InvalidSymbol(String),
+}
+
+/// Field elements are written as decimal strings, since JSON numbers can't hold them exactly
+fn field_to_json<F: LurkField>(f: F) -> Value {
+    Value::String(BigUint::from_bytes_le(&f.to_bytes_le()).to_string())
+}
+
+fn field_from_json<F: LurkField>(v: &Value) -> Result<F, JsonError> {
+    let invalid = || JsonError::InvalidNumber(v.to_string());
+    let n: BigUint = v
+        .as_str()
+        .ok_or_else(invalid)?
+        .parse()
+        .map_err(|_| invalid())?;
+    let mut bytes = n.to_bytes_le();
+    if bytes.len() > F::bytes_le_len() {
+        return Err(invalid());
+    }
+    bytes.resize(F::bytes_le_len(), 0);
+    F::from_bytes_le(&bytes).ok_or_else(invalid)
+}
+
+impl<F: LurkField> Store<F> {
+    /// Returns a JSON tree for the expression `ptr` points to, in which every node is an object
+    /// with a single key naming its type: `{"cons": [car, cdr]}`, `{"num": "3"}`,
+    /// `{"u64": "3"}`, `{"str": "abc"}`, `{"char": "a"}`, `{"sym": ".lurk.nil"}`,
+    /// `{"comm": [secret, payload]}` or `{"fun": [arg, body, env]}`. Opaque pointers, thunks and
+    /// anything else without a readable form are written as `{"opaque": {"tag": 1, "hash": "..."}}`,
+    /// or `null` if even their hash is unknown.
+    pub fn to_json(&self, ptr: Ptr<F>) -> Value {
+        let known = match ptr.tag {
+            _ if ptr.is_opaque() => None,
+            ExprTag::Cons => self
+                .fetch_cons(&ptr)
+                .map(|(car, cdr)| json!({ "cons": [self.to_json(*car), self.to_json(*cdr)] })),
+            ExprTag::Num => self.fetch_num(&ptr).map(|num| match num {
+                Num::U64(n) => json!({ "num": n.to_string() }),
+                Num::Scalar(f) => json!({ "num": field_to_json(*f) }),
+            }),
+            ExprTag::U64 => self.fetch_uint(&ptr).map(|UInt::U64(n)| json!({ "u64": n.to_string() })),
+            ExprTag::Str => self.fetch_string(&ptr).map(|s| json!({ "str": s })),
+            ExprTag::Char => self
+                .fetch_char(&ptr)
+                .map(|c| json!({ "char": c.to_string() })),
+            ExprTag::Nil | ExprTag::Sym | ExprTag::Key => self
+                .fetch_symbol(&ptr)
+                .map(|sym| json!({ "sym": sym.fmt_to_string() })),
+            ExprTag::Comm => self.fetch_comm(&ptr).map(|(secret, payload)| {
+                json!({ "comm": [field_to_json(secret.0), self.to_json(*payload)] })
+            }),
+            ExprTag::Fun => self.fetch_fun(&ptr).map(|(arg, body, env)| {
+                json!({ "fun": [self.to_json(*arg), self.to_json(*body), self.to_json(*env)] })
+            }),
+            ExprTag::Thunk => None,
+        };
+        known.unwrap_or_else(|| match self.hash_expr(&ptr) {
+            Some(z_ptr) => json!({
+                "opaque": { "tag": u16::from(ptr.tag), "hash": field_to_json(*z_ptr.value()) }
+            }),
+            None => Value::Null,
+        })
+    }
+
+    /// Interns the expression described by a JSON tree in the format written by `to_json`
+    pub fn from_json(&mut self, v: &Value) -> Result<Ptr<F>, JsonError> {
+        let malformed = || JsonError::Malformed(v.to_string());
+        let (key, body) = match v.as_object() {
+            Some(object) if object.len() == 1 => object.iter().next().unwrap(),
+            _ => return Err(malformed()),
+        };
+        let parts = |n: usize| match body.as_array() {
+            Some(parts) if parts.len() == n => Ok(parts),
+            _ => Err(malformed()),
+        };
+        match key.as_str() {
+            "cons" => {
+                let parts = parts(2)?;
+                let car = self.from_json(&parts[0])?;
+                let cdr = self.from_json(&parts[1])?;
+                Ok(self.cons(car, cdr))
+            }
+            "num" => Ok(self.intern_num(Num::Scalar(field_from_json(body)?))),
+            "u64" => match body.as_str().map(str::parse) {
+                Some(Ok(n)) => Ok(self.uint64(n)),
+                _ => Err(malformed()),
+            },
+            "str" => match body.as_str() {
+                Some(s) => Ok(self.intern_string(s)),
+                None => Err(malformed()),
+            },
+            "char" => {
+                let mut chars = body.as_str().ok_or_else(malformed)?.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(self.intern_char(c)),
+                    _ => Err(malformed()),
+                }
+            }
+            "sym" => {
+                let name = body.as_str().ok_or_else(malformed)?;
+                let sym = Symbol::from_str_impl(name)
+                    .ok_or_else(|| JsonError::InvalidSymbol(name.into()))?;
+                Ok(self.intern_symbol(&sym))
+            }
+            "comm" => {
+                let parts = parts(2)?;
+                let secret = field_from_json(&parts[0])?;
+                let payload = self.from_json(&parts[1])?;
+                Ok(self.hide(secret, payload))
+            }
+            "fun" => {
+                let parts = parts(3)?;
+                let arg = self.from_json(&parts[0])?;
+                if arg.tag != ExprTag::Sym {
+                    return Err(malformed());
+                }
+                let body = self.from_json(&parts[1])?;
+                let env = self.from_json(&parts[2])?;
+                Ok(self.intern_fun(arg, body, env))
+            }
+            "opaque" => {
+                let tag = body["tag"]
+                    .as_u64()
+                    .and_then(|tag| u16::try_from(tag).ok())
+                    .and_then(|tag| ExprTag::try_from(tag).ok())
+                    .ok_or_else(malformed)?;
+                let hash = field_from_json(&body["hash"])?;
+                Ok(self.intern_opaque(tag, hash))
+            }
+            _ => Err(malformed()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lurk_sym_ptr;
+    use blstrs::Scalar as Fr;
+    use ff::Field;
+
+    fn roundtrip(store: &mut Store<Fr>, ptr: Ptr<Fr>) -> Value {
+        let json = store.to_json(ptr);
+        assert_eq!(Ok(ptr), store.from_json(&json));
+        json
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let store = &mut Store::<Fr>::default();
+        for src in [
+            "(1 (2 (3 . 4)) ((nil)) \"five\" #\\6 7u64 :eight .lurk.user.nine)",
+            "\"quote \\\" backslash \\\\ newline \\n unicode λ 😀\"",
+            "(lambda (x) (+ x 1))",
+            "\"\"",
+            "nil",
+        ] {
+            let ptr = store.read(src).unwrap();
+            roundtrip(store, ptr);
+        }
+        let two = store.read("(cons 1 2)").unwrap();
+        let comm = store.hide(Fr::from(11), two);
+        roundtrip(store, comm);
+
+        let arg = store.user_sym("x");
+        let nil = lurk_sym_ptr!(store, nil);
+        let fun = store.intern_fun(arg, arg, nil);
+        let json = roundtrip(store, fun);
+        assert!(json.get("fun").is_some());
+
+        store.hydrate_scalar_cache();
+        let hash = *store.hash_expr(&two).unwrap().value();
+        let opaque = store.intern_opaque_cons(hash);
+        let json = roundtrip(store, opaque);
+        assert!(json.get("opaque").is_some());
+
+        let pair = store.read("(1 . \"a\")").unwrap();
+        assert_eq!(
+            json!({ "cons": [{ "num": "1" }, { "str": "a" }] }),
+            store.to_json(pair)
+        );
+    }
+
+    #[test]
+    fn json_numbers_near_modulus() {
+        let store = &mut Store::<Fr>::default();
+        let max = Fr::ZERO - Fr::ONE;
+        let ptr = store.num(Num::Scalar(max));
+        let json = roundtrip(store, ptr);
+        let max_decimal = json["num"].as_str().unwrap().to_owned();
+        assert!(max_decimal.chars().all(|c| c.is_ascii_digit()));
+        for n in [
+            Fr::ZERO,
+            Fr::ONE,
+            max - Fr::ONE,
+            Fr::from(u64::MAX),
+            Fr::from(u64::MAX) + Fr::ONE,
+        ] {
+            let ptr = store.num(Num::Scalar(n));
+            roundtrip(store, ptr);
+        }
+
+        // the modulus itself and anything larger are rejected
+        let modulus = (max_decimal.parse::<BigUint>().unwrap() + 1u32).to_string();
+        for n in [modulus, "1".repeat(100), "-1".into(), "0x10".into()] {
+            assert!(matches!(
+                store.from_json(&json!({ "num": n })),
+                Err(JsonError::InvalidNumber(_))
+            ));
+        }
+        assert!(matches!(
+            store.from_json(&json!({ "num": 1 })),
+            Err(JsonError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            store.from_json(&json!({ "cons": [{ "num": "1" }] })),
+            Err(JsonError::Malformed(_))
+        ));
+    }
+}
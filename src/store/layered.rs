@@ -0,0 +1,102 @@
+use std::hash::Hash;
+use std::sync::Arc;
+
+use crate::store::IndexSet;
+
+/// An `IndexSet` on top of a shared one that is never inserted into, as in an overlay of a
+/// `FrozenStore`. Indices below the length of the shared `base` are its entries, and entries it
+/// doesn't hold are inserted after them, so an index means the same entry in the base and in
+/// every set on top of it. A clone shares the base, copying only the entries inserted on top.
+#[derive(Clone, Debug)]
+pub struct LayeredSet<K> {
+    base: Arc<IndexSet<K>>,
+    own: IndexSet<K>,
+}
+
+impl<K> Default for LayeredSet<K> {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            own: Default::default(),
+        }
+    }
+}
+
+impl<K> LayeredSet<K> {
+    pub fn len(&self) -> usize {
+        self.base.len() + self.own.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get_index(&self, i: usize) -> Option<&K> {
+        match i.checked_sub(self.base.len()) {
+            None => self.base.get_index(i),
+            Some(i) => self.own.get_index(i),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &K> {
+        self.base.iter().chain(self.own.iter())
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.own.reserve(additional)
+    }
+
+    /// A set on top of this frozen one, sharing its entries without copying them. Entries
+    /// inserted into either one afterwards aren't seen by the other.
+    pub(crate) fn layer(&self) -> Self {
+        debug_assert!(self.own.is_empty(), "only a frozen set is layered");
+        Self {
+            base: self.base.clone(),
+            own: Default::default(),
+        }
+    }
+
+    /// Moves the entries inserted into this set onto its base, so that `layer` shares them
+    pub(crate) fn freeze(&mut self)
+    where
+        K: Clone + Hash + Eq,
+    {
+        if !self.own.is_empty() {
+            let own = std::mem::take(&mut self.own);
+            Arc::make_mut(&mut self.base).extend(own);
+        }
+    }
+}
+
+impl<K: Hash + Eq> LayeredSet<K> {
+    pub fn get_index_of(&self, value: &K) -> Option<usize> {
+        self.base
+            .get_index_of(value)
+            .or_else(|| self.own.get_index_of(value).map(|i| self.base.len() + i))
+    }
+
+    /// Returns the index of `value` and whether it was newly inserted, like
+    /// `IndexSet::insert_full`. A value the base holds is never inserted.
+    pub fn insert_full(&mut self, value: K) -> (usize, bool) {
+        if let Some(i) = self.base.get_index_of(&value) {
+            return (i, false);
+        }
+        let (i, inserted) = self.own.insert_full(value);
+        (self.base.len() + i, inserted)
+    }
+}
+
+impl<K: Hash + Eq> From<IndexSet<K>> for LayeredSet<K> {
+    fn from(own: IndexSet<K>) -> Self {
+        Self {
+            base: Default::default(),
+            own,
+        }
+    }
+}
+
+impl<K: Hash + Eq> FromIterator<K> for LayeredSet<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<IndexSet<K>>())
+    }
+}
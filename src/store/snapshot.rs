@@ -0,0 +1,393 @@
+use std::io::{self, Read, Write};
+
+use once_cell::sync::OnceCell;
+
+use crate::expr::Thunk;
+use crate::field::{FWrap, LurkField};
+use crate::num::Num;
+use crate::ptr::{ContPtr, Ptr, RawPtr, TaggedPtr};
+use crate::store::{IndexSet, LayeredSet, Store};
+use crate::tag::{ContTag, ExprTag, Op1, Op2, Tag};
+use crate::z_ptr::ZPtr;
+
+/// Identifies `Store` snapshots
+const MAGIC: &[u8; 4] = b"LURK";
+/// Bumped whenever the layout of a snapshot changes, since old snapshots can't be loaded
+const VERSION: u16 = 1;
+
+fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+/// The little-endian binary encoding of the values held by a `Store`
+trait Snapshot: Sized {
+    fn encode<W: Write>(&self, w: &mut W) -> io::Result<()>;
+    fn decode<R: Read>(r: &mut R) -> io::Result<Self>;
+}
+
+impl Snapshot for u8 {
+    fn encode<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&[*self])
+    }
+
+    fn decode<R: Read>(r: &mut R) -> io::Result<Self> {
+        let mut bytes = [0; 1];
+        r.read_exact(&mut bytes)?;
+        Ok(bytes[0])
+    }
+}
+
+impl Snapshot for u16 {
+    fn encode<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&self.to_le_bytes())
+    }
+
+    fn decode<R: Read>(r: &mut R) -> io::Result<Self> {
+        let mut bytes = [0; 2];
+        r.read_exact(&mut bytes)?;
+        Ok(u16::from_le_bytes(bytes))
+    }
+}
+
+impl Snapshot for u64 {
+    fn encode<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&self.to_le_bytes())
+    }
+
+    fn decode<R: Read>(r: &mut R) -> io::Result<Self> {
+        let mut bytes = [0; 8];
+        r.read_exact(&mut bytes)?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+}
+
+impl Snapshot for usize {
+    fn encode<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        (*self as u64).encode(w)
+    }
+
+    fn decode<R: Read>(r: &mut R) -> io::Result<Self> {
+        usize::try_from(u64::decode(r)?).map_err(|_| invalid_data("index overflows usize"))
+    }
+}
+
+macro_rules! tag_snapshot {
+    ($($tag:ty),*) => {
+        $(
+            impl Snapshot for $tag {
+                fn encode<W: Write>(&self, w: &mut W) -> io::Result<()> {
+                    u16::from(*self).encode(w)
+                }
+
+                fn decode<R: Read>(r: &mut R) -> io::Result<Self> {
+                    let tag = u16::decode(r)?;
+                    Self::try_from(tag)
+                        .map_err(|_| invalid_data(format!("invalid {} {tag}", stringify!($tag))))
+                }
+            }
+        )*
+    };
+}
+
+tag_snapshot!(ExprTag, ContTag, Op1, Op2);
+
+impl<F: LurkField> Snapshot for FWrap<F> {
+    fn encode<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(self.0.to_repr().as_ref())
+    }
+
+    fn decode<R: Read>(r: &mut R) -> io::Result<Self> {
+        let mut repr = F::Repr::default();
+        r.read_exact(repr.as_mut())?;
+        Option::from(F::from_repr(repr))
+            .map(FWrap)
+            .ok_or_else(|| invalid_data("non-canonical field element"))
+    }
+}
+
+impl Snapshot for RawPtr {
+    fn encode<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        match self {
+            RawPtr::Null => 0u8.encode(w),
+            RawPtr::Opaque(x) => {
+                1u8.encode(w)?;
+                x.encode(w)
+            }
+            RawPtr::Index(x) => {
+                2u8.encode(w)?;
+                x.encode(w)
+            }
+        }
+    }
+
+    fn decode<R: Read>(r: &mut R) -> io::Result<Self> {
+        match u8::decode(r)? {
+            0 => Ok(RawPtr::Null),
+            1 => Ok(RawPtr::Opaque(usize::decode(r)?)),
+            2 => Ok(RawPtr::Index(usize::decode(r)?)),
+            kind => Err(invalid_data(format!("invalid RawPtr kind {kind}"))),
+        }
+    }
+}
+
+impl<F: LurkField, T: Tag + Snapshot> Snapshot for TaggedPtr<F, T> {
+    fn encode<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        self.tag.encode(w)?;
+        self.raw.encode(w)
+    }
+
+    fn decode<R: Read>(r: &mut R) -> io::Result<Self> {
+        let tag = T::decode(r)?;
+        Ok(Self::new(tag, RawPtr::decode(r)?))
+    }
+}
+
+impl<F: LurkField, E: Tag + Snapshot> Snapshot for ZPtr<E, F> {
+    fn encode<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        self.0.encode(w)?;
+        FWrap(self.1).encode(w)
+    }
+
+    fn decode<R: Read>(r: &mut R) -> io::Result<Self> {
+        let tag = E::decode(r)?;
+        Ok(ZPtr(tag, FWrap::decode(r)?.0))
+    }
+}
+
+impl<F: LurkField> Snapshot for Num<F> {
+    fn encode<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        match self {
+            Num::U64(x) => {
+                0u8.encode(w)?;
+                x.encode(w)
+            }
+            Num::Scalar(x) => {
+                1u8.encode(w)?;
+                FWrap(*x).encode(w)
+            }
+        }
+    }
+
+    fn decode<R: Read>(r: &mut R) -> io::Result<Self> {
+        match u8::decode(r)? {
+            0 => Ok(Num::U64(u64::decode(r)?)),
+            1 => Ok(Num::Scalar(FWrap::decode(r)?.0)),
+            kind => Err(invalid_data(format!("invalid Num kind {kind}"))),
+        }
+    }
+}
+
+impl<F: LurkField> Snapshot for Thunk<F> {
+    fn encode<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        self.value.encode(w)?;
+        self.continuation.encode(w)
+    }
+
+    fn decode<R: Read>(r: &mut R) -> io::Result<Self> {
+        Ok(Thunk {
+            value: Ptr::decode(r)?,
+            continuation: ContPtr::decode(r)?,
+        })
+    }
+}
+
+macro_rules! tuple_snapshot {
+    ($($t:ident),*) => {
+        impl<$($t: Snapshot),*> Snapshot for ($($t,)*) {
+            #[allow(non_snake_case)]
+            fn encode<W: Write>(&self, w: &mut W) -> io::Result<()> {
+                let ($($t,)*) = self;
+                $($t.encode(w)?;)*
+                Ok(())
+            }
+
+            fn decode<R: Read>(r: &mut R) -> io::Result<Self> {
+                Ok(($($t::decode(r)?,)*))
+            }
+        }
+    };
+}
+
+tuple_snapshot!(A, B);
+tuple_snapshot!(A, B, C);
+tuple_snapshot!(A, B, C, D);
+
+impl<T: Snapshot + Eq + std::hash::Hash> Snapshot for IndexSet<T> {
+    fn encode<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        self.len().encode(w)?;
+        self.iter().try_for_each(|x| x.encode(w))
+    }
+
+    fn decode<R: Read>(r: &mut R) -> io::Result<Self> {
+        let len = usize::decode(r)?;
+        let mut set = IndexSet::default();
+        for _ in 0..len {
+            if !set.insert(T::decode(r)?) {
+                // a duplicate would shift the indices of all later entries
+                return Err(invalid_data("duplicate entry"));
+            }
+        }
+        Ok(set)
+    }
+}
+
+/// Encoded as the `IndexSet` of all of its entries, so a snapshot of an overlay loads as a store
+/// of its own
+impl<T: Snapshot + Eq + std::hash::Hash> Snapshot for LayeredSet<T> {
+    fn encode<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        self.len().encode(w)?;
+        self.iter().try_for_each(|x| x.encode(w))
+    }
+
+    fn decode<R: Read>(r: &mut R) -> io::Result<Self> {
+        IndexSet::<T>::decode(r).map(LayeredSet::from)
+    }
+}
+
+/// Calls `$f` on the name of each of the sets of a `Store`, in snapshot order
+macro_rules! for_each_set {
+    ($f:ident) => {
+        $f!(cons_store);
+        $f!(comm_store);
+        $f!(fun_store);
+        $f!(sym_store);
+        $f!(num_store);
+        $f!(str_store);
+        $f!(thunk_store);
+        $f!(call0_store);
+        $f!(call_store);
+        $f!(call2_store);
+        $f!(tail_store);
+        $f!(lookup_store);
+        $f!(unop_store);
+        $f!(binop_store);
+        $f!(binop2_store);
+        $f!(if_store);
+        $f!(let_store);
+        $f!(letrec_store);
+        $f!(emit_store);
+        $f!(opaque_ptrs);
+        $f!(opaque_cont_ptrs);
+    };
+}
+pub(super) use for_each_set;
+
+impl<F: LurkField> Store<F> {
+    /// Write a snapshot of the store, which `Store::load` reads back. Snapshots preserve the index
+    /// of every entry, so `Ptr`s into this store remain valid for the loaded one. Opaque pointers
+    /// are saved with their digests. Caches aren't saved, and are refilled as needed after loading.
+    pub fn dump(&self, mut w: impl Write) -> io::Result<()> {
+        let w = &mut w;
+        w.write_all(MAGIC)?;
+        VERSION.encode(w)?;
+        macro_rules! dump {
+            ($set:ident) => {
+                self.$set.encode(w)?
+            };
+        }
+        for_each_set!(dump);
+        w.flush()
+    }
+
+    /// Read a snapshot written by `Store::dump`
+    pub fn load(mut r: impl Read) -> io::Result<Store<F>> {
+        let r = &mut r;
+        let mut magic = [0; 4];
+        r.read_exact(&mut magic)?;
+        if magic != *MAGIC {
+            return Err(invalid_data("not a Store snapshot"));
+        }
+        let version = u16::decode(r)?;
+        if version != VERSION {
+            return Err(invalid_data(format!(
+                "unsupported Store snapshot version {version}, expected {VERSION}"
+            )));
+        }
+
+        let mut store = Store::default();
+        macro_rules! load {
+            ($set:ident) => {
+                store.$set = Snapshot::decode(r)?
+            };
+        }
+        for_each_set!(load);
+
+        // `Store::default` filled these in for data that the snapshot replaced
+        store.z_expr_ptr_map = Default::default();
+        store.z_cont_ptr_map = Default::default();
+        store.z_expr_ptr_cache = Default::default();
+        store.z_cont_ptr_cache = Default::default();
+        store.str_cache.clear();
+        store.symbol_cache.clear();
+        store.constants = OnceCell::new();
+
+        // mark the entries which interning would have marked, so they are hashed on hydration
+        store.dehydrated = (0..store.cons_store.len())
+            .map(|i| Ptr::index(ExprTag::Cons, i))
+            .chain((0..store.comm_store.len()).map(|i| Ptr::index(ExprTag::Comm, i)))
+            .chain((0..store.fun_store.len()).map(|i| Ptr::index(ExprTag::Fun, i)))
+            .chain((0..store.thunk_store.len()).map(|i| Ptr::index(ExprTag::Thunk, i)))
+            .collect();
+        store.dehydrated_cont.clear();
+        store.ensure_constants();
+        Ok(store)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lurk_sym_ptr;
+    use blstrs::Scalar as Fr;
+
+    #[test]
+    fn dump_load_roundtrip() {
+        let mut store = Store::<Fr>::default();
+        let expr = store
+            .read("(let ((f (lambda (x) (+ x 1)))) (cons (f 2) \"str\"))")
+            .unwrap();
+        let key = store.read(":key").unwrap();
+        let num = store.num(Num::Scalar(-Fr::from(7)));
+        let opaque = store.intern_opaque_cons(12345.into());
+        let comm = store.hide(Fr::from(3), expr);
+        let expr_hash = store.hash_expr(&expr).unwrap();
+
+        let mut bytes = Vec::new();
+        store.dump(&mut bytes).unwrap();
+        let mut loaded = Store::<Fr>::load(bytes.as_slice()).unwrap();
+
+        for ptr in [expr, key, num, comm] {
+            assert_eq!(store.fetch(&ptr), loaded.fetch(&ptr));
+        }
+        assert_eq!(store.fetch_list(&expr), loaded.fetch_list(&expr));
+        assert_eq!(store.get_opaque_ptr(opaque), loaded.get_opaque_ptr(opaque));
+        assert_eq!(Some(expr_hash), loaded.hash_expr(&expr));
+        assert_eq!(lurk_sym_ptr!(store, lambda), lurk_sym_ptr!(loaded, lambda));
+
+        // interning into the loaded store agrees with the original
+        assert_eq!(
+            expr,
+            loaded
+                .read("(let ((f (lambda (x) (+ x 1)))) (cons (f 2) \"str\"))")
+                .unwrap()
+        );
+        loaded.hydrate_scalar_cache();
+        assert_eq!(Some(expr), loaded.fetch_z_expr_ptr(&expr_hash));
+    }
+
+    #[test]
+    fn load_rejects_bad_snapshots() {
+        let mut bytes = Vec::new();
+        Store::<Fr>::default().dump(&mut bytes).unwrap();
+
+        assert!(Store::<Fr>::load(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert!(Store::<Fr>::load(bad_magic.as_slice()).is_err());
+
+        let mut bad_version = bytes;
+        bad_version[4] = 0xff;
+        assert!(Store::<Fr>::load(bad_version.as_slice()).is_err());
+    }
+}
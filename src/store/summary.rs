@@ -0,0 +1,150 @@
+use std::fmt;
+
+use crate::field::LurkField;
+use crate::ptr::Ptr;
+use crate::state::initial_lurk_state;
+use crate::store::Store;
+use crate::tag::ExprTag;
+use crate::writer::Write;
+
+/// How many entries of each kind a summary prints
+const SAMPLES: usize = 3;
+/// The length past which a sample is cut short
+const SAMPLE_WIDTH: usize = 40;
+
+impl<F: LurkField> Store<F> {
+    /// The number of entries of each kind, with the first few conses, numbers and strings as
+    /// examples, on one line. Unlike a listing of every entry, this stays readable in logs as the
+    /// store grows. It's also what `Store`'s `Debug` prints. `str` counts whole strings, including
+    /// symbols' names, and `strcons` the cells they're made of.
+    pub fn summary(&self) -> String {
+        let conts = self.call0_store.len()
+            + self.call_store.len()
+            + self.call2_store.len()
+            + self.tail_store.len()
+            + self.lookup_store.len()
+            + self.unop_store.len()
+            + self.binop_store.len()
+            + self.binop2_store.len()
+            + self.if_store.len()
+            + self.let_store.len()
+            + self.letrec_store.len()
+            + self.emit_store.len();
+        format!(
+            "Store {{ cons: {}{}, num: {}{}, str: {}{}, strcons: {}, sym: {}, comm: {}, fun: {}, \
+             thunk: {}, conts: {conts}, opaque: {}, opaque conts: {}, dehydrated: {} }}",
+            self.cons_store.len(),
+            self.samples(ExprTag::Cons, self.cons_store.len()),
+            self.num_store.len(),
+            self.samples(ExprTag::Num, self.num_store.len()),
+            self.str_cache.len(),
+            self.string_samples(),
+            self.str_store.len(),
+            self.sym_store.len(),
+            self.comm_store.len(),
+            self.fun_store.len(),
+            self.thunk_store.len(),
+            self.opaque_ptrs.len(),
+            self.opaque_cont_ptrs.len(),
+            self.dehydrated.len() + self.dehydrated_cont.len(),
+        )
+    }
+
+    /// The first of the `len` entries held with `tag`, as in ` [a, b, ...]`
+    fn samples(&self, tag: ExprTag, len: usize) -> String {
+        self.sample_list((0..len.min(SAMPLES)).map(|i| Ptr::index(tag, i)), len)
+    }
+
+    /// The first few whole strings, in order, rather than the `strcons` cells, most of which are
+    /// suffixes of those strings
+    fn string_samples(&self) -> String {
+        let mut strings = self.str_cache.iter().collect::<Vec<_>>();
+        strings.sort_unstable_by_key(|(s, _)| *s);
+        let ptrs = strings.into_iter().take(SAMPLES).map(|(_, ptr)| *ptr);
+        self.sample_list(ptrs, self.str_cache.len())
+    }
+
+    /// `ptrs` as ` [a, b, ...]`, with the ellipsis if there are more than `SAMPLES` of the `len`
+    /// entries
+    fn sample_list(&self, ptrs: impl Iterator<Item = Ptr<F>>, len: usize) -> String {
+        if len == 0 {
+            return String::new();
+        }
+        let state = initial_lurk_state();
+        let mut samples = ptrs
+            .map(|ptr| {
+                let sample = ptr.fmt_to_string(self, state);
+                match sample.char_indices().nth(SAMPLE_WIDTH) {
+                    Some((end, _)) => format!("{}...", &sample[..end]),
+                    None => sample,
+                }
+            })
+            .collect::<Vec<_>>();
+        if len > SAMPLES {
+            samples.push("...".into());
+        }
+        format!(" [{}]", samples.join(", "))
+    }
+}
+
+impl<F: LurkField> fmt::Debug for Store<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.summary())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lurk_sym_ptr;
+    use blstrs::Scalar as Fr;
+
+    #[test]
+    fn summary_counts() {
+        let store = &mut Store::<Fr>::default();
+        let fresh = store.summary();
+        assert!(fresh.starts_with("Store { cons: 0, num: 0, "), "{fresh}");
+
+        store.read("(1 2)").unwrap();
+        store.read("(1 . 3)").unwrap();
+        let x = store.user_sym("x");
+        let nil = lurk_sym_ptr!(store, nil);
+        store.intern_fun(x, x, nil);
+        let summary = store.summary();
+        assert!(summary.contains("cons: 3 ["), "{summary}");
+        assert!(summary.contains("num: 3 [1, 2, 3]"), "{summary}");
+        assert!(summary.contains("comm: 0, fun: 1, thunk: 0"), "{summary}");
+        assert_eq!(summary, format!("{store:?}"));
+
+        // only the first few entries are shown
+        for n in 4..10 {
+            store.num(n);
+        }
+        assert!(store.summary().contains("num: 9 [1, 2, 3, ...]"));
+
+        // and a long one is cut short
+        let store = &mut Store::<Fr>::default();
+        let long = store.intern_string(&"a".repeat(100));
+        let nil = lurk_sym_ptr!(store, nil);
+        store.cons(long, nil);
+        let summary = store.summary();
+        let cut = format!("cons: 1 [(\"{}...]", "a".repeat(SAMPLE_WIDTH - 2));
+        assert!(summary.contains(&cut), "{summary}");
+
+        // strings are counted whole, not by their strcons cells
+        let store = &mut Store::<Fr>::default();
+        let names = store.str_cache.len();
+        let cells = store.str_store.len();
+        // these sort before the names of the constants' symbols
+        store.intern_string(" cd");
+        store.intern_string(" ab");
+        store.intern_string(" b");
+        let summary = store.summary();
+        let strings = format!(
+            "str: {} [\" ab\", \" b\", \" cd\", ...], strcons: ",
+            names + 3
+        );
+        assert!(summary.contains(&strings), "{summary}");
+        assert!(store.str_store.len() >= cells + 3);
+    }
+}
@@ -14,7 +14,7 @@ use serde::{Deserialize, Serialize};
 pub const KEYWORD_MARKER: char = ':';
 pub const SYM_SEPARATOR: char = '.';
 pub const SYM_MARKER: char = '.';
-pub const ESCAPE_CHARS: &str = "|(){}[],.:'\\\"";
+pub const ESCAPE_CHARS: &str = "|(){}[],.:'`\\\"";
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(Arbitrary))]
@@ -7,7 +7,7 @@ use crate::num::Num;
 use crate::package::SymbolRef;
 use crate::parser::position::Pos;
 use crate::ptr::Ptr;
-use crate::state::lurk_sym;
+use crate::state::{lurk_sym, user_sym};
 use crate::store::Store;
 use crate::tag::ExprTag;
 use crate::uint::UInt;
@@ -33,6 +33,8 @@ pub enum Syntax<F: LurkField> {
     List(Pos, Vec<Syntax<F>>),
     // An improper cons-list of expressions: (1 2 . 3)
     Improper(Pos, Vec<Syntax<F>>, Box<Syntax<F>>),
+    // A reader macro literal as written, with the syntax its handler read it as: #hex"ff"
+    ReaderMacro(Pos, String, Box<Syntax<F>>),
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -102,10 +104,106 @@ impl<F: LurkField> fmt::Display for Syntax<F> {
                 }
                 write!(f, ")")
             }
+            Self::ReaderMacro(_, literal, _) => write!(f, "{}", literal),
         }
     }
 }
 
+impl<F: LurkField> Syntax<F> {
+    /// The expression a quasiquotation `` `x `` reads as. It evaluates to `x`, except that each
+    /// `,e` in `x` is replaced by the value of `e`, and each `,@e` in a list of `x` by the elements
+    /// of the list `e` evaluates to. Parts of `x` without unquotes are quoted whole. The reader
+    /// expands nested quasiquotations innermost first, so an unquote within an unquote of the
+    /// inner one is left for the outer one, as in `` `(a `(b ,(c ,x))) ``.
+    pub fn quasiquote(pos: Pos, x: Self) -> Self {
+        if let Some(e) = x.unquoted() {
+            return e.clone();
+        }
+        if !x.has_unquote() {
+            return Self::Quote(pos, Box::new(x));
+        }
+        let (xs, end) = match x {
+            Self::Quote(_, x) => (vec![Self::Symbol(pos, lurk_sym("quote").into()), *x], None),
+            Self::List(_, xs) => (xs, None),
+            Self::Improper(_, xs, end) => (xs, Some(*end)),
+            _ => unreachable!("only lists can hold unquotes"),
+        };
+        let nil = Self::Symbol(pos, lurk_sym("nil").into());
+        let mut acc = match end {
+            Some(end) => Self::quasiquote(pos, end),
+            None => nil.clone(),
+        };
+        for x in xs.into_iter().rev() {
+            acc = match x.form_arg("unquote-splicing") {
+                Some(spliced) if acc == nil => spliced.clone(),
+                Some(spliced) => Self::List(pos, vec![Self::append(pos), spliced.clone(), acc]),
+                None => Self::List(
+                    pos,
+                    vec![
+                        Self::Symbol(pos, lurk_sym("cons").into()),
+                        Self::quasiquote(pos, x),
+                        acc,
+                    ],
+                ),
+            };
+        }
+        acc
+    }
+
+    /// The argument of the form `(name arg)`, where `name` is in the Lurk package
+    fn form_arg(&self, name: &str) -> Option<&Self> {
+        match self {
+            Self::List(_, xs) => match xs.as_slice() {
+                [Self::Symbol(_, sym), arg] if **sym == lurk_sym(name) => Some(arg),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The argument of `,e` or `,@e`. A splice outside of a list is just an unquote.
+    fn unquoted(&self) -> Option<&Self> {
+        self.form_arg("unquote")
+            .or_else(|| self.form_arg("unquote-splicing"))
+    }
+
+    fn has_unquote(&self) -> bool {
+        match self {
+            _ if self.unquoted().is_some() => true,
+            Self::Quote(_, x) => x.has_unquote(),
+            Self::List(_, xs) => xs.iter().any(Self::has_unquote),
+            Self::Improper(_, xs, end) => xs.iter().any(Self::has_unquote) || end.has_unquote(),
+            _ => false,
+        }
+    }
+
+    /// A function appending its two list arguments, which quasiquotations call to splice. It's a
+    /// closed expression, so calling it doesn't capture any of the caller's bindings.
+    fn append(pos: Pos) -> Self {
+        let sym = |name: &str| Self::Symbol(pos, lurk_sym(name).into());
+        let var = |name: &str| Self::Symbol(pos, user_sym(name).into());
+        let list = |xs: Vec<Self>| Self::List(pos, xs);
+        let head = list(vec![sym("car"), var("a")]);
+        let tail = list(vec![sym("cdr"), var("a")]);
+        let body = list(vec![
+            sym("if"),
+            var("a"),
+            list(vec![
+                sym("cons"),
+                head,
+                list(vec![var("append"), tail, var("b")]),
+            ]),
+            var("b"),
+        ]);
+        let lambda = list(vec![sym("lambda"), list(vec![var("a"), var("b")]), body]);
+        list(vec![
+            sym("letrec"),
+            list(vec![list(vec![var("append"), lambda])]),
+            var("append"),
+        ])
+    }
+}
+
 impl<F: LurkField> Store<F> {
     pub fn intern_syntax(&mut self, syn: Syntax<F>) -> Ptr<F> {
         match syn {
@@ -134,6 +232,7 @@ impl<F: LurkField> Store<F> {
                 }
                 cdr
             }
+            Syntax::ReaderMacro(_, _, x) => self.intern_syntax(*x),
         }
     }
 
@@ -21,7 +21,17 @@ pub trait Tag: Into<u16> + TryFrom<u16> + Copy + Sized + Eq + fmt::Debug {
 
 /// A tag for expressions. Note that ExprTag, ContTag, Op1, Op2 all live in the same u16 namespace
 #[derive(
-    Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr, TryFromRepr,
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize_repr,
+    Deserialize_repr,
+    TryFromRepr,
 )]
 #[cfg_attr(not(target_arch = "wasm32"), derive(Arbitrary))]
 #[repr(u16)]
@@ -71,20 +81,16 @@ impl fmt::Display for ExprTag {
 
 impl TypePredicates for ExprTag {
     fn is_fun(&self) -> bool {
-        matches!(self, ExprTag::Fun)
+        ExprTag::is_fun(self)
     }
     fn is_self_evaluating(&self) -> bool {
-        match self {
-            Self::Cons | Self::Thunk | Self::Sym => false,
-            Self::Nil
-            | Self::Fun
-            | Self::Num
-            | Self::Str
-            | Self::Char
-            | Self::Comm
-            | Self::U64
-            | Self::Key => true,
-        }
+        ExprTag::is_self_evaluating(self)
+    }
+    fn is_numeric(&self) -> bool {
+        ExprTag::is_numeric(self)
+    }
+    fn is_callable(&self) -> bool {
+        ExprTag::is_callable(self)
     }
 
     fn is_potentially(&self, tag: Self) -> bool {
@@ -109,9 +115,60 @@ impl Tag for ExprTag {
     }
 }
 
+/// The groups of tags the evaluator branches on. `TypePredicates` delegates to these.
+impl ExprTag {
+    /// Whether expressions with this tag evaluate to themselves. The others are conses, which are
+    /// forms to evaluate, symbols, which are looked up, and thunks.
+    pub const fn is_self_evaluating(&self) -> bool {
+        match self {
+            Self::Cons | Self::Thunk | Self::Sym => false,
+            Self::Nil
+            | Self::Fun
+            | Self::Num
+            | Self::Str
+            | Self::Char
+            | Self::Comm
+            | Self::U64
+            | Self::Key => true,
+        }
+    }
+
+    /// Whether this is the tag of a closure
+    pub const fn is_fun(&self) -> bool {
+        matches!(self, Self::Fun)
+    }
+
+    /// Whether arithmetic operators accept expressions with this tag
+    pub const fn is_numeric(&self) -> bool {
+        matches!(self, Self::Num | Self::U64)
+    }
+
+    /// Whether an expression with this tag can be applied, being a closure or possibly evaluating
+    /// to one
+    pub const fn is_callable(&self) -> bool {
+        self.is_fun() || !self.is_self_evaluating()
+    }
+
+    /// Every `ExprTag`, in order of encoding. Variants are numbered consecutively from `Nil`, so
+    /// new ones are included without listing them here.
+    pub fn all() -> impl Iterator<Item = ExprTag> {
+        (ExprTag::Nil as u16..).map_while(|n| Self::try_from(n).ok())
+    }
+}
+
 /// A tag for continuations. Note that ExprTag, ContTag, Op1, Op2 all live in the same u16 namespace
 #[derive(
-    Serialize_repr, Deserialize_repr, Debug, Copy, Clone, PartialEq, Eq, Hash, TryFromRepr,
+    Serialize_repr,
+    Deserialize_repr,
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    TryFromRepr,
 )]
 #[cfg_attr(not(target_arch = "wasm32"), derive(Arbitrary))]
 #[repr(u16)]
@@ -163,6 +220,13 @@ impl Tag for ContTag {
     }
 }
 
+impl ContTag {
+    /// Every `ContTag`, in order of encoding, like `ExprTag::all`
+    pub fn all() -> impl Iterator<Item = ContTag> {
+        (ContTag::Outermost as u16..).map_while(|n| Self::try_from(n).ok())
+    }
+}
+
 impl fmt::Display for ContTag {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -333,6 +397,8 @@ pub enum Op2 {
     Sum = 0b0011_0000_0000_0000,
     Diff,
     Product,
+    /// `/`, field division on `Num`s and truncating division on `U64`s, which the circuit enforces
+    /// as `a = q * b + r` with `0 <= r < b`
     Quotient,
     Equal,
     NumEqual,
@@ -344,8 +410,14 @@ pub enum Op2 {
     StrCons,
     Begin,
     Hide,
+    /// `%`, the remainder `r` of the same division as `Quotient`, only defined on `U64`s
     Modulo,
     Eval,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
 }
 
 impl From<Op2> for u16 {
@@ -393,6 +465,14 @@ impl Op2 {
                 | Op2::Modulo
         )
     }
+
+    /// The bitwise operators and shifts, which are only defined on `U64`s.
+    pub fn is_bitwise(&self) -> bool {
+        matches!(
+            self,
+            Op2::BitAnd | Op2::BitOr | Op2::BitXor | Op2::ShiftLeft | Op2::ShiftRight
+        )
+    }
 }
 
 impl Op for Op2 {
@@ -414,6 +494,11 @@ impl Op for Op2 {
             Op2::Hide => "hide",
             Op2::Modulo => "%",
             Op2::Eval => "eval",
+            Op2::BitAnd => "bit-and",
+            Op2::BitOr => "bit-or",
+            Op2::BitXor => "bit-xor",
+            Op2::ShiftLeft => "shl",
+            Op2::ShiftRight => "shr",
         }
     }
 
@@ -435,6 +520,11 @@ impl Op for Op2 {
             &Op2::Hide,
             &Op2::Modulo,
             &Op2::Eval,
+            &Op2::BitAnd,
+            &Op2::BitOr,
+            &Op2::BitXor,
+            &Op2::ShiftLeft,
+            &Op2::ShiftRight,
         ]
     }
 
@@ -462,6 +552,11 @@ impl fmt::Display for Op2 {
             Op2::Hide => write!(f, "hide"),
             Op2::Modulo => write!(f, "modulo"),
             Op2::Eval => write!(f, "eval#"),
+            Op2::BitAnd => write!(f, "bit-and#"),
+            Op2::BitOr => write!(f, "bit-or#"),
+            Op2::BitXor => write!(f, "bit-xor#"),
+            Op2::ShiftLeft => write!(f, "shl#"),
+            Op2::ShiftRight => write!(f, "shr#"),
         }
     }
 }
@@ -470,6 +565,7 @@ impl fmt::Display for Op2 {
 pub mod tests {
 
     use super::*;
+    use blstrs::Scalar as Fr;
     use proptest::prelude::*;
 
     proptest! {
@@ -507,4 +603,102 @@ pub mod tests {
         assert_eq!(x, x2)
     }
     }
+
+    fn field_round_trips<T: Tag>() -> usize {
+        let mut count = 0;
+        for n in 0..=u16::MAX {
+            let Ok(tag) = T::try_from(n) else {
+                assert_eq!(None, T::from_field(&Fr::from(n as u64)));
+                continue;
+            };
+            count += 1;
+            let f = tag.to_field::<Fr>();
+            assert_eq!(Fr::from(n as u64), f);
+            assert_eq!(Some(tag), T::from_field(&f));
+        }
+        count
+    }
+
+    #[test]
+    fn expr_tag_field_round_trip() {
+        assert_eq!(11, field_round_trips::<ExprTag>());
+        assert_eq!(None, ExprTag::from_field(&Fr::from(u16::MAX as u64 + 1)));
+        assert_eq!(None, ExprTag::from_field(&-Fr::from(1)));
+        assert_eq!(
+            None,
+            ExprTag::from_field(&ContTag::Outermost.to_field::<Fr>())
+        );
+    }
+
+    #[test]
+    fn cont_tag_field_round_trip() {
+        assert_eq!(16, field_round_trips::<ContTag>());
+        assert_eq!(None, ContTag::from_field(&Fr::from(u16::MAX as u64 + 1)));
+        assert_eq!(None, ContTag::from_field(&-Fr::from(1)));
+        assert_eq!(None, ContTag::from_field(&ExprTag::Nil.to_field::<Fr>()));
+    }
+
+    #[test]
+    fn all_tags() {
+        let expr_tags: Vec<_> = ExprTag::all().collect();
+        assert_eq!(11, expr_tags.len());
+        assert_eq!(Some(&ExprTag::Nil), expr_tags.first());
+        assert_eq!(Some(&ExprTag::Key), expr_tags.last());
+        assert!(expr_tags.windows(2).all(|w| w[0] < w[1]));
+
+        let cont_tags: Vec<_> = ContTag::all().collect();
+        assert_eq!(16, cont_tags.len());
+        assert_eq!(Some(&ContTag::Outermost), cont_tags.first());
+        assert_eq!(Some(&ContTag::Emit), cont_tags.last());
+        let distinct: std::collections::HashSet<u16> =
+            cont_tags.iter().map(|tag| (*tag).into()).collect();
+        assert_eq!(cont_tags.len(), distinct.len());
+    }
+
+    proptest! {
+    #[test]
+    fn prop_all_tags_contain(x in any::<ExprTag>(), y in any::<ContTag>()) {
+        assert_eq!(1, ExprTag::all().filter(|tag| *tag == x).count());
+        assert_eq!(1, ContTag::all().filter(|tag| *tag == y).count());
+    }
+    }
+
+    #[test]
+    fn expr_tag_groups() {
+        use ExprTag::*;
+        // (tag, self-evaluating, fun, numeric, callable)
+        let matrix = [
+            (Nil, true, false, false, false),
+            (Cons, false, false, false, true),
+            (Sym, false, false, false, true),
+            (Fun, true, true, false, true),
+            (Num, true, false, true, false),
+            (Thunk, false, false, false, true),
+            (Str, true, false, false, false),
+            (Char, true, false, false, false),
+            (Comm, true, false, false, false),
+            (U64, true, false, true, false),
+            (Key, true, false, false, false),
+        ];
+        assert_eq!(
+            ExprTag::all().collect::<Vec<_>>(),
+            matrix.iter().map(|row| row.0).collect::<Vec<_>>()
+        );
+        for (tag, self_evaluating, fun, numeric, callable) in matrix {
+            assert_eq!(self_evaluating, tag.is_self_evaluating(), "{tag}");
+            assert_eq!(fun, tag.is_fun(), "{tag}");
+            assert_eq!(numeric, tag.is_numeric(), "{tag}");
+            assert_eq!(callable, tag.is_callable(), "{tag}");
+
+            // the trait agrees, and the groups are consistent with each other
+            assert_eq!(self_evaluating, TypePredicates::is_self_evaluating(&tag));
+            assert_eq!(fun, TypePredicates::is_fun(&tag));
+            assert_eq!(numeric, TypePredicates::is_numeric(&tag));
+            assert_eq!(callable, TypePredicates::is_callable(&tag));
+            assert_eq!(callable, tag.is_potentially(Fun));
+            assert!(!numeric || self_evaluating);
+            assert!(!fun || callable);
+            assert!(!(fun && numeric));
+        }
+    }
 }
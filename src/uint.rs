@@ -5,7 +5,7 @@ use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 use std::{
     fmt::Display,
-    ops::{Add, Div, Mul, Rem, Sub},
+    ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Shl, Shr, Sub},
 };
 
 /// Unsigned fixed-width integer type for Lurk.
@@ -87,3 +87,103 @@ impl Rem for UInt {
         }
     }
 }
+
+impl BitAnd for UInt {
+    type Output = Self;
+    fn bitand(self, other: Self) -> Self {
+        match (self, other) {
+            (UInt::U64(a), UInt::U64(b)) => UInt::U64(a & b),
+        }
+    }
+}
+
+impl BitOr for UInt {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        match (self, other) {
+            (UInt::U64(a), UInt::U64(b)) => UInt::U64(a | b),
+        }
+    }
+}
+
+impl BitXor for UInt {
+    type Output = Self;
+    fn bitxor(self, other: Self) -> Self {
+        match (self, other) {
+            (UInt::U64(a), UInt::U64(b)) => UInt::U64(a ^ b),
+        }
+    }
+}
+
+/// Shifting by the width or more shifts every bit out, giving zero, rather than masking the
+/// amount as Rust's `wrapping_shl` does.
+impl Shl for UInt {
+    type Output = Self;
+    fn shl(self, other: Self) -> Self {
+        match (self, other) {
+            (UInt::U64(a), UInt::U64(b)) => UInt::U64(
+                u32::try_from(b)
+                    .ok()
+                    .and_then(|b| a.checked_shl(b))
+                    .unwrap_or(0),
+            ),
+        }
+    }
+}
+
+/// Like `Shl`, shifting by the width or more gives zero.
+impl Shr for UInt {
+    type Output = Self;
+    fn shr(self, other: Self) -> Self {
+        match (self, other) {
+            (UInt::U64(a), UInt::U64(b)) => UInt::U64(
+                u32::try_from(b)
+                    .ok()
+                    .and_then(|b| a.checked_shr(b))
+                    .unwrap_or(0),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn prop_bitwise_ops(a: u64, b: u64) {
+            let (x, y) = (UInt::from(a), UInt::from(b));
+            prop_assert_eq!(UInt::from(a & b), x & y);
+            prop_assert_eq!(UInt::from(a | b), x | y);
+            prop_assert_eq!(UInt::from(a ^ b), x ^ y);
+        }
+
+        #[test]
+        fn prop_shifts(a: u64, b in 0u64..64) {
+            let (x, y) = (UInt::from(a), UInt::from(b));
+            prop_assert_eq!(UInt::from(a << b), x << y);
+            prop_assert_eq!(UInt::from(a >> b), x >> y);
+        }
+
+        #[test]
+        fn prop_shifts_past_width(a: u64, b in 64u64..) {
+            let (x, y) = (UInt::from(a), UInt::from(b));
+            prop_assert!((x << y).is_zero());
+            prop_assert!((x >> y).is_zero());
+        }
+    }
+
+    #[test]
+    fn shift_boundaries() {
+        let max = UInt::from(u64::MAX);
+        assert_eq!(UInt::from(1 << 63), UInt::from(1) << UInt::from(63));
+        assert_eq!(UInt::from(1), max >> UInt::from(63));
+        assert_eq!(max, max << UInt::from(0));
+        for amount in [64, 65, 1 << 32, u64::MAX] {
+            assert_eq!(UInt::from(0), max << UInt::from(amount));
+            assert_eq!(UInt::from(0), max >> UInt::from(amount));
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Bindings for evaluating Lurk from JavaScript, over the Pallas scalar field. Proving isn't
+//! exposed yet.
+
+use pasta_curves::pallas::Scalar;
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::eval::{eval_str, lang::Coproc};
+use crate::parser::read_syntax;
+use crate::state::initial_lurk_state;
+use crate::store::Store;
+use crate::syntax::Syntax;
+use crate::writer::Write;
+
+/// Evaluates the first expression of `src`, returning the printed result. Read and evaluation
+/// errors are thrown as strings.
+#[wasm_bindgen]
+pub fn lurk_eval(src: &str) -> Result<JsValue, JsValue> {
+    let store = &mut Store::<Scalar>::default();
+    let output = eval_str::<Scalar, Coproc<Scalar>>(store, src)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    Ok(JsValue::from_str(
+        &output.fmt_to_string(store, initial_lurk_state()),
+    ))
+}
+
+/// Reads the first expression of `src`, returning its syntax tree as a JSON string. Read errors
+/// are thrown as strings.
+#[wasm_bindgen]
+pub fn lurk_parse(src: &str) -> Result<JsValue, JsValue> {
+    let syntax = read_syntax::<Scalar>(src).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    Ok(JsValue::from_str(&syntax_json(&syntax).to_string()))
+}
+
+fn syntax_json(syntax: &Syntax<Scalar>) -> Value {
+    let state = initial_lurk_state();
+    match syntax {
+        Syntax::Num(_, x) => json!({ "type": "num", "value": x.to_string() }),
+        Syntax::UInt(_, x) => json!({ "type": "u64", "value": x.to_string() }),
+        Syntax::Symbol(_, x) => json!({ "type": "symbol", "value": state.fmt_to_string(x) }),
+        Syntax::String(_, x) => json!({ "type": "string", "value": x }),
+        Syntax::Char(_, x) => json!({ "type": "char", "value": x.to_string() }),
+        Syntax::Quote(_, x) => json!({ "type": "quote", "value": syntax_json(x) }),
+        Syntax::List(_, xs) => json!({
+            "type": "list",
+            "elements": xs.iter().map(syntax_json).collect::<Vec<_>>(),
+        }),
+        Syntax::Improper(_, xs, tail) => json!({
+            "type": "list",
+            "elements": xs.iter().map(syntax_json).collect::<Vec<_>>(),
+            "tail": syntax_json(tail),
+        }),
+        Syntax::ReaderMacro(_, literal, _) => json!({ "type": "reader-macro", "value": literal }),
+    }
+}
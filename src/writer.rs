@@ -60,6 +60,17 @@ fn write_symbol<W: io::Write>(w: &mut W, sym: Symbol, state: &State) -> io::Resu
     write!(w, "{}", state.fmt_to_string(&SymbolRef::new(sym)))
 }
 
+/// The rest argument and the body form of `body`, if it is a variadic function's, the form
+/// (LAMBDA (REST) BODY). An ordinary function's body is a list of its single form.
+fn variadic_body<F: LurkField>(store: &Store<F>, body: &Ptr<F>) -> Option<(Ptr<F>, Ptr<F>)> {
+    let (head, inner) = store.car_cdr(body).ok()?;
+    if head != lurk_sym_ptr!(store, lambda) || inner.is_nil() {
+        return None;
+    }
+    let (formals, body) = store.car_cdr(&inner).ok()?;
+    Some((store.car(&formals).ok()?, store.car(&body).ok()?))
+}
+
 impl<F: LurkField> Write<F> for Expression<F> {
     fn fmt<W: io::Write>(&self, store: &Store<F>, state: &State, w: &mut W) -> io::Result<()> {
         use Expression::*;
@@ -82,7 +93,18 @@ impl<F: LurkField> Write<F> for Expression<F> {
             Str(car, cdr) => {
                 let head = store.fetch_char(car).expect("missing string head");
                 let tail = store.fetch_string(cdr).expect("missing string tail");
-                write!(w, "\"{head}{tail}\"")
+                write!(w, "{}", escape_string(&format!("{head}{tail}")))
+            }
+            Fun(arg, body, _closed_env) if variadic_body(store, body).is_some() => {
+                // See `reduce_cons` for how a variadic function is built.
+                let (rest, expr) = variadic_body(store, body).unwrap();
+                write!(w, "<FUNCTION (")?;
+                store.fetch(arg).unwrap().fmt(store, state, w)?;
+                write!(w, " . ")?;
+                store.fetch(&rest).unwrap().fmt(store, state, w)?;
+                write!(w, ") ")?;
+                store.fetch(&expr).unwrap().fmt(store, state, w)?;
+                write!(w, ">")
             }
             Fun(arg, body, _closed_env) => {
                 let is_zero_arg = *arg == lurk_sym_ptr!(store, dummy);
@@ -210,6 +232,7 @@ impl<F: LurkField> Write<F> for Continuation<F> {
                 unevaled_arg,
                 saved_env,
                 continuation,
+                expanded,
             } => {
                 write!(w, "Call{{ unevaled_arg: ")?;
                 unevaled_arg.fmt(store, state, w)?;
@@ -217,12 +240,13 @@ impl<F: LurkField> Write<F> for Continuation<F> {
                 saved_env.fmt(store, state, w)?;
                 write!(w, ", continuation: ")?;
                 continuation.fmt(store, state, w)?;
-                write!(w, " }}")
+                write!(w, ", expanded: {expanded} }}")
             }
             Continuation::Call2 {
                 function,
                 saved_env,
                 continuation,
+                expanded,
             } => {
                 write!(w, "Call2{{ function: ")?;
                 function.fmt(store, state, w)?;
@@ -230,7 +254,7 @@ impl<F: LurkField> Write<F> for Continuation<F> {
                 saved_env.fmt(store, state, w)?;
                 write!(w, ", continuation: ")?;
                 continuation.fmt(store, state, w)?;
-                write!(w, " }}")
+                write!(w, ", expanded: {expanded} }}")
             }
             Continuation::Tail {
                 saved_env,
@@ -344,12 +368,142 @@ impl<F: LurkField> Write<F> for Continuation<F> {
     }
 }
 
+/// A layout tree for `pretty`: either an atom, printed as-is, or a list of elements with an
+/// optional dotted tail.
+enum Doc {
+    Atom(String),
+    List(Vec<Doc>, Option<Box<Doc>>),
+}
+
+impl Doc {
+    fn build<F: LurkField>(ptr: &Ptr<F>, store: &Store<F>, state: &State) -> Self {
+        match store.fetch(ptr) {
+            Some(Expression::Cons(car, cdr)) => {
+                let mut items = vec![Self::build(&car, store, state)];
+                let mut tail = cdr;
+                loop {
+                    match store.fetch(&tail) {
+                        Some(Expression::Cons(car, cdr)) => {
+                            items.push(Self::build(&car, store, state));
+                            tail = cdr;
+                        }
+                        Some(Expression::Nil) => return Doc::List(items, None),
+                        _ => {
+                            let tail = Box::new(Self::build(&tail, store, state));
+                            return Doc::List(items, Some(tail));
+                        }
+                    }
+                }
+            }
+            _ => Doc::Atom(ptr.fmt_to_string(store, state)),
+        }
+    }
+
+    /// The length of this document when printed on a single line
+    fn flat_len(&self) -> usize {
+        match self {
+            Doc::Atom(atom) => atom.chars().count(),
+            Doc::List(items, tail) => {
+                let items_len: usize = items.iter().map(Doc::flat_len).sum();
+                let tail_len = tail.as_ref().map_or(0, |tail| tail.flat_len() + 3);
+                // parens and the spaces between elements
+                items_len + tail_len + items.len() + 1
+            }
+        }
+    }
+
+    fn render_flat(&self, out: &mut String) {
+        match self {
+            Doc::Atom(atom) => out.push_str(atom),
+            Doc::List(items, tail) => {
+                out.push('(');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(' ');
+                    }
+                    item.render_flat(out);
+                }
+                if let Some(tail) = tail {
+                    out.push_str(" . ");
+                    tail.render_flat(out);
+                }
+                out.push(')');
+            }
+        }
+    }
+
+    /// Renders starting at `column`, followed by `closing` parens of enclosing lists on the same
+    /// line. Lists that don't fit in the remaining width are broken with one element per line,
+    /// each aligned one column past the opening paren.
+    fn render(&self, column: usize, closing: usize, width: usize, out: &mut String) {
+        match self {
+            Doc::List(items, tail) if column + self.flat_len() + closing > width => {
+                let indent = column + 1;
+                out.push('(');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        newline(indent, out);
+                    }
+                    let is_last = i + 1 == items.len() && tail.is_none();
+                    item.render(indent, if is_last { closing + 1 } else { 0 }, width, out);
+                }
+                if let Some(tail) = tail {
+                    newline(indent, out);
+                    out.push_str(". ");
+                    tail.render(indent + 2, closing + 1, width, out);
+                }
+                out.push(')');
+            }
+            _ => self.render_flat(out),
+        }
+    }
+}
+
+fn newline(indent: usize, out: &mut String) {
+    out.push('\n');
+    out.extend(std::iter::repeat(' ').take(indent));
+}
+
+/// Quotes `string` as a Lurk string literal, escaping what the reader would otherwise misparse,
+/// so that printed strings read back as the same string
+fn escape_string(string: &str) -> String {
+    let mut out = String::from('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Prints `ptr` across multiple lines, trying to keep to `width` columns. Lists that fit in the
+/// remaining width stay on one line; otherwise each element goes on its own line, indented under
+/// the first.
+pub fn pretty<F: LurkField>(ptr: &Ptr<F>, store: &Store<F>, state: &State, width: usize) -> String {
+    let mut out = String::new();
+    Doc::build(ptr, store, state).render(0, 0, width, &mut out);
+    out
+}
+
 #[cfg(test)]
 pub mod test {
     use crate::state::initial_lurk_state;
 
     use super::*;
+    use crate::num::Num;
+    use crate::parser::position::Pos;
+    use crate::state::user_sym;
+    use crate::syntax::Syntax;
+    use crate::uint::UInt;
     use pasta_curves::pallas::Scalar as Fr;
+    use proptest::prelude::*;
 
     #[test]
     fn print_expr() {
@@ -378,4 +532,84 @@ pub mod test {
         let res = num.fmt_to_string(&store, initial_lurk_state());
         assert_eq!(&res, &"5");
     }
+
+    #[test]
+    fn test_pretty() {
+        let s = &mut Store::<Fr>::default();
+        let state = initial_lurk_state();
+
+        let small = s.read("(1 2 (3 . 4))").unwrap();
+        assert_eq!("(1 2 (3 . 4))", pretty(&small, s, state, 80));
+
+        let src = "(letrec ((fact (lambda (n) (if (= n 0) 1 (* n (fact (- n 1)))))))
+                     (cons (fact 5) (cons \"a \\\"quoted\\\"\\nstring\" (1 2 . 3))))";
+        let large = s.read(src).unwrap();
+        let printed = pretty(&large, s, state, 30);
+        assert!(printed.lines().count() > 1);
+        assert!(printed.lines().all(|line| line.len() <= 30));
+        assert_eq!(large, s.read(&printed).unwrap());
+
+        // a narrow width breaks every list, but still reads back the same
+        let printed = pretty(&large, s, state, 1);
+        assert_eq!(large, s.read(&printed).unwrap());
+    }
+
+    /// Expressions the reader can produce: unlike `any::<Syntax>`, symbols stay in packages that
+    /// exist in the initial Lurk state.
+    fn readable_syntax() -> impl Strategy<Value = Syntax<Fr>> {
+        let leaf = prop_oneof![
+            any::<Num<Fr>>().prop_map(|x| Syntax::Num(Pos::No, x)),
+            any::<UInt>().prop_map(|x| Syntax::UInt(Pos::No, x)),
+            any::<String>().prop_map(|x| Syntax::String(Pos::No, x)),
+            any::<char>().prop_map(|x| Syntax::Char(Pos::No, x)),
+            any::<String>().prop_map(|x| Syntax::Symbol(Pos::No, user_sym(&x).into())),
+            any::<String>().prop_map(|x| Syntax::Symbol(Pos::No, Symbol::key(&[x]).into())),
+        ];
+        leaf.prop_recursive(4, 64, 8, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..8).prop_map(|x| Syntax::List(Pos::No, x)),
+                prop::collection::vec(inner, 2..8).prop_map(|mut xs| {
+                    let x = xs.pop().unwrap();
+                    Syntax::Improper(Pos::No, xs, Box::new(x))
+                })
+            ]
+        })
+    }
+
+    #[test]
+    fn test_print_read_edge_cases() {
+        let s = &mut Store::<Fr>::default();
+        let mut state = State::init_lurk_state();
+        // a local name that would read back as a number
+        let one = state.intern("1");
+        let one = s.intern_symbol(&one);
+        let printed = one.fmt_to_string(s, &state);
+        assert_eq!(".lurk.user.1", printed);
+        assert_eq!(one, s.read(&printed).unwrap());
+
+        for src in [
+            "\"a \\\"quoted\\\" string\\n\"",
+            "\"back\\\\slash\"",
+            "#\\(",
+            "#\\\\",
+            "#\\u",
+            ":foo",
+            "-1",
+            "'(a . b)",
+        ] {
+            let ptr = s.read(src).unwrap();
+            let printed = ptr.fmt_to_string(s, initial_lurk_state());
+            assert_eq!(ptr, s.read(&printed).unwrap(), "{src} printed as {printed}");
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_print_read_roundtrip(x in readable_syntax()) {
+            let s = &mut Store::<Fr>::default();
+            let ptr = s.intern_syntax(x);
+            let printed = ptr.fmt_to_string(s, initial_lurk_state());
+            assert_eq!(ptr, s.read(&printed).unwrap());
+        }
+    }
 }
@@ -27,17 +27,19 @@ pub enum ZCont<F: LurkField> {
         saved_env: ZExprPtr<F>,
         continuation: ZContPtr<F>,
     },
-    /// A function call with one argument
+    /// A function call with one argument, possibly expanded from a multi-arg call
     Call {
         saved_env: ZExprPtr<F>,
         unevaled_arg: ZExprPtr<F>,
         continuation: ZContPtr<F>,
+        expanded: bool,
     },
     /// A nested function call
     Call2 {
         saved_env: ZExprPtr<F>,
         function: ZExprPtr<F>,
         continuation: ZContPtr<F>,
+        expanded: bool,
     },
     Tail {
         saved_env: ZExprPtr<F>,
@@ -95,6 +97,7 @@ impl<F: LurkField> ZCont<F> {
                 saved_env,
                 unevaled_arg,
                 continuation,
+                expanded,
             } => [
                 saved_env.0.to_field(),
                 saved_env.1,
@@ -102,13 +105,14 @@ impl<F: LurkField> ZCont<F> {
                 unevaled_arg.1,
                 continuation.0.to_field(),
                 continuation.1,
-                F::ZERO,
+                F::from(u64::from(*expanded)),
                 F::ZERO,
             ],
             Self::Call2 {
                 saved_env,
                 function,
                 continuation,
+                expanded,
             } => [
                 saved_env.0.to_field(),
                 saved_env.1,
@@ -116,7 +120,7 @@ impl<F: LurkField> ZCont<F> {
                 function.1,
                 continuation.0.to_field(),
                 continuation.1,
-                F::ZERO,
+                F::from(u64::from(*expanded)),
                 F::ZERO,
             ],
             Self::Call0 {
@@ -267,18 +271,20 @@ impl<F: LurkField> Arbitrary for ZCont<F> {
                     continuation,
                 }
             }),
-            any::<(ZExprPtr<F>, ZExprPtr<F>, ZContPtr<F>)>().prop_map(
-                |(saved_env, unevaled_arg, continuation)| ZCont::Call {
+            any::<(ZExprPtr<F>, ZExprPtr<F>, ZContPtr<F>, bool)>().prop_map(
+                |(saved_env, unevaled_arg, continuation, expanded)| ZCont::Call {
                     saved_env,
                     unevaled_arg,
-                    continuation
+                    continuation,
+                    expanded
                 }
             ),
-            any::<(ZExprPtr<F>, ZExprPtr<F>, ZContPtr<F>)>().prop_map(
-                |(saved_env, function, continuation)| ZCont::Call2 {
+            any::<(ZExprPtr<F>, ZExprPtr<F>, ZContPtr<F>, bool)>().prop_map(
+                |(saved_env, function, continuation, expanded)| ZCont::Call2 {
                     saved_env,
                     function,
-                    continuation
+                    continuation,
+                    expanded
                 }
             ),
             any::<(ZExprPtr<F>, ZContPtr<F>)>().prop_map(|(saved_env, continuation)| ZCont::Tail {
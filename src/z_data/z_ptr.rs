@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::hash::Hash;
+use subtle::{Choice, ConstantTimeEq};
 
 #[cfg(not(target_arch = "wasm32"))]
 use proptest::prelude::*;
@@ -80,6 +81,15 @@ impl<E: Tag, F: LurkField> Hash for ZPtr<E, F> {
     }
 }
 
+/// Unlike `==`, compares in time independent of where the values differ, for comparing digests of
+/// commitments and other secrets
+impl<E: Tag, F: LurkField> ConstantTimeEq for ZPtr<E, F> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let (tag, other_tag): (u16, u16) = (self.0.into(), other.0.into());
+        tag.ct_eq(&other_tag) & self.1.ct_eq(&other.1)
+    }
+}
+
 impl<E: Tag, F: LurkField> ZPtr<E, F> {
     /// Creates a ZPtr from a tag and a value
     pub fn from_parts(tag: E, value: F) -> Self {
@@ -162,6 +172,16 @@ mod tests {
         fn prop_base32_z_expr_ptr(x in any::<ZExprPtr<Scalar>>()) {
             assert_eq!(x, ZPtr::from_base32(&x.to_base32()).unwrap());
         }
+
+        #[test]
+        fn prop_ct_eq_z_expr_ptr(x in any::<ZExprPtr<Scalar>>(), y in any::<ZExprPtr<Scalar>>()) {
+            assert_eq!(x == y, bool::from(x.ct_eq(&y)));
+            assert!(bool::from(x.ct_eq(&x)));
+            let same_value = ZExprPtr::from_parts(y.tag(), *x.value());
+            assert_eq!(x == same_value, bool::from(x.ct_eq(&same_value)));
+            let same_tag = ZExprPtr::from_parts(x.tag(), *y.value());
+            assert_eq!(x == same_tag, bool::from(x.ct_eq(&same_tag)));
+        }
     }
 
     #[test]
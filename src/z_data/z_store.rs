@@ -37,6 +37,24 @@ use crate::field::LurkField;
 pub struct ZStore<F: LurkField> {
     pub expr_map: BTreeMap<ZExprPtr<F>, Option<ZExpr<F>>>,
     pub cont_map: BTreeMap<ZContPtr<F>, Option<ZCont<F>>>,
+    /// The digests of the roots given to `Store::to_zstore`, which `ZStore::into_store` returns
+    /// the pointers to. Empty for `ZStore`s built otherwise, and for those serialized before
+    /// `ZStore`s had roots.
+    #[serde(default)]
+    pub roots: Vec<ZExprPtr<F>>,
+}
+
+/// A `ZStore::merge` of a preimage other than the one already held for the same pointer
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum MergeError<F: LurkField> {
+    #[error("Conflicting preimages of expression {0:?}")]
+    Expr(ZExprPtr<F>),
+    #[error("Conflicting preimages of continuation {0:?}")]
+    Cont(ZContPtr<F>),
+    #[error("Preimage of expression {0:?} hashes to another pointer")]
+    ExprPreimage(ZExprPtr<F>),
+    #[error("Preimage of continuation {0:?} hashes to another pointer")]
+    ContPreimage(ZContPtr<F>),
 }
 
 impl<F: LurkField> ZStore<F> {
@@ -45,6 +63,7 @@ impl<F: LurkField> ZStore<F> {
         ZStore {
             expr_map: BTreeMap::new(),
             cont_map: BTreeMap::new(),
+            roots: Vec::new(),
         }
     }
 
@@ -131,6 +150,67 @@ impl<F: LurkField> ZStore<F> {
         }
     }
 
+    /// Adds the entries of `other`, as when a computation's data arrives in parts. An entry
+    /// without a preimage, as for an opaque pointer, takes the preimage `other` holds for it, and
+    /// never replaces one. Since `other` may come from anywhere, that preimage must hash to the
+    /// pointer, which is checked with `poseidon_cache`. Fails without changing `self` if it
+    /// doesn't, or if both hold different preimages for the same pointer, which can't both hash
+    /// to it. The `roots` of `other` that `self` doesn't have are appended to its own.
+    pub fn merge(
+        &mut self,
+        other: ZStore<F>,
+        poseidon_cache: &PoseidonCache<F>,
+    ) -> Result<(), MergeError<F>> {
+        for (ptr, expr) in &other.expr_map {
+            match (self.expr_map.get(ptr), expr) {
+                (Some(Some(held)), Some(expr)) => {
+                    if held != expr {
+                        return Err(MergeError::Expr(*ptr));
+                    }
+                }
+                (_, Some(expr)) => {
+                    if expr.z_ptr(poseidon_cache) != *ptr {
+                        return Err(MergeError::ExprPreimage(*ptr));
+                    }
+                }
+                (_, None) => (),
+            }
+        }
+        for (ptr, cont) in &other.cont_map {
+            match (self.cont_map.get(ptr), cont) {
+                (Some(Some(held)), Some(cont)) => {
+                    if held != cont {
+                        return Err(MergeError::Cont(*ptr));
+                    }
+                }
+                (_, Some(cont)) => {
+                    if cont.z_ptr(poseidon_cache) != *ptr {
+                        return Err(MergeError::ContPreimage(*ptr));
+                    }
+                }
+                (_, None) => (),
+            }
+        }
+        for (ptr, expr) in other.expr_map {
+            let entry = self.expr_map.entry(ptr).or_insert(None);
+            if entry.is_none() {
+                *entry = expr;
+            }
+        }
+        for (ptr, cont) in other.cont_map {
+            let entry = self.cont_map.entry(ptr).or_insert(None);
+            if entry.is_none() {
+                *entry = cont;
+            }
+        }
+        for root in other.roots {
+            if !self.roots.contains(&root) {
+                self.roots.push(root);
+            }
+        }
+        Ok(())
+    }
+
     /// Returns the owned `ZCont` corresponding to `ptr` if the former exists
     pub fn get_cont(&self, ptr: &ZContPtr<F>) -> Option<ZCont<F>> {
         self.cont_map.get(ptr).cloned()?
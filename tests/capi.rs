@@ -0,0 +1,63 @@
+#![cfg(feature = "capi")]
+
+//! Drives the C interface the way a C host would: only through raw pointers, handles and status
+//! codes.
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use lurk::capi::*;
+
+unsafe fn eval_to_string(store: *mut LurkStore, src: &str) -> Result<String, LurkStatus> {
+    let src = CString::new(src).unwrap();
+    let (mut expr, mut result): (LurkExpr, LurkExpr) = (0, 0);
+    let mut printed: *mut c_char = ptr::null_mut();
+    for status in [
+        lurk_read(store, src.as_ptr(), &mut expr),
+        lurk_eval(store, expr, &mut result),
+        lurk_print(store, result, &mut printed),
+    ] {
+        if status != LurkStatus::Ok {
+            return Err(status);
+        }
+    }
+    let string = CStr::from_ptr(printed).to_str().unwrap().to_owned();
+    lurk_string_free(printed);
+    Ok(string)
+}
+
+#[test]
+fn c_caller() {
+    unsafe {
+        let store = lurk_store_new();
+        assert_eq!(
+            Ok("42".into()),
+            eval_to_string(store, "(let ((x 2)) (* x 21))")
+        );
+        assert_eq!(
+            Ok("\"ab\"".into()),
+            eval_to_string(store, "(strcons #\\a \"b\")")
+        );
+        assert_eq!(Err(LurkStatus::ReadError), eval_to_string(store, "(+ 1"));
+        assert_eq!(Err(LurkStatus::EvalError), eval_to_string(store, "(+ x 1)"));
+
+        let mut out: LurkExpr = 0;
+        assert_eq!(LurkStatus::InvalidHandle, lurk_eval(store, 1000, &mut out));
+        let src = CString::new("1").unwrap();
+        assert_eq!(
+            LurkStatus::NullArgument,
+            lurk_read(ptr::null_mut(), src.as_ptr(), &mut out)
+        );
+        assert_eq!(
+            LurkStatus::NullArgument,
+            lurk_read(store, ptr::null(), &mut out)
+        );
+        let invalid = [0xff_u8, 0];
+        assert_eq!(
+            LurkStatus::InvalidUtf8,
+            lurk_read(store, invalid.as_ptr().cast(), &mut out)
+        );
+
+        lurk_store_free(store);
+    }
+}
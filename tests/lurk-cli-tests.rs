@@ -105,3 +105,69 @@ fn test_prove_and_verify() {
 
     cmd.assert().success();
 }
+
+#[test]
+fn test_prove_and_verify_artifact() {
+    let tmp_dir = Builder::new().prefix("tmp").tempdir().unwrap();
+    let tmp_dir = Utf8Path::from_path(tmp_dir.path()).unwrap();
+    let public_param_dir = tmp_dir.join("public_params");
+    let proof_dir = tmp_dir.join("proofs");
+    let commit_dir = tmp_dir.join("commits");
+    let lurk_file = tmp_dir.join("prove_verify_artifact.lurk");
+
+    let mut file = File::create(lurk_file.clone()).unwrap();
+    file.write_all(b"(+ 1 1)\n").unwrap();
+    file.write_all(b"!(prove \"two.proof\")\n").unwrap();
+    file.write_all(b"!(verify \"two.proof\")\n").unwrap();
+
+    let mut cmd = lurk_cmd();
+    cmd.arg("load");
+    cmd.arg(lurk_file.into_string());
+    cmd.arg("--public-params-dir");
+    cmd.arg(public_param_dir);
+    cmd.arg("--proofs-dir");
+    cmd.arg(proof_dir);
+    cmd.arg("--commits-dir");
+    cmd.arg(commit_dir);
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    assert!(tmp_dir.join("two.proof").is_file());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("✓ Proof artifact"), "{stdout}");
+}
+
+#[test]
+fn test_trace() {
+    let tmp_dir = Builder::new().prefix("tmp").tempdir().unwrap();
+    let tmp_dir = Utf8Path::from_path(tmp_dir.path()).unwrap();
+    let lurk_file = tmp_dir.join("trace.lurk");
+
+    let mut file = File::create(lurk_file.clone()).unwrap();
+    file.write_all(b"!(:trace (if t 1 2))\n").unwrap();
+    file.write_all(b"!(:trace 1 (if t 1 2))\n").unwrap();
+
+    let mut cmd = lurk_cmd();
+    cmd.arg("load");
+    cmd.arg(lurk_file.into_string());
+    cmd.arg("--public-params-dir");
+    cmd.arg(tmp_dir.join("public_params"));
+    cmd.arg("--proofs-dir");
+    cmd.arg(tmp_dir.join("proofs"));
+    cmd.arg("--commits-dir");
+    cmd.arg(tmp_dir.join("commits"));
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // the step that evaluates the branch taken
+    assert_eq!(
+        1,
+        stdout
+            .lines()
+            .filter(|line| line.ends_with("[outermost#]: 1"))
+            .count(),
+        "{stdout}"
+    );
+    assert!(stdout.contains(" more frames"), "{stdout}");
+}
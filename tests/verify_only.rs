@@ -0,0 +1,82 @@
+//! A build without the default `prover` feature, named by the `verify-only` feature, leaves the
+//! Nova prover out, so it can't make the proof it verifies. The proof is a fixture instead, which
+//! a full build writes with
+//!
+//! ```text
+//! cargo test --test verify_only write_proof_fixture -- --ignored
+//! ```
+//!
+//! and which both builds verify:
+//!
+//! ```text
+//! cargo test --test verify_only -- --ignored
+//! cargo test --no-default-features --features verify-only --test verify_only -- --ignored
+//! ```
+//!
+//! The fixture isn't committed yet, so `verify_proof_fixture` is ignored until it is.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use lurk::eval::lang::{Coproc, Lang};
+use lurk::proof::nova::{public_params, Proof, PublicParams};
+use lurk::store::Store;
+use pasta_curves::pallas::Scalar as S1;
+
+const REDUCTION_COUNT: usize = 5;
+
+fn fixture_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/verify_only_proof.bin")
+}
+
+fn params() -> (
+    Arc<Lang<S1, Coproc<S1>>>,
+    PublicParams<'static, S1, Coproc<S1>>,
+) {
+    let lang = Arc::new(Lang::new());
+    let pp = public_params(REDUCTION_COUNT, lang.clone());
+    (lang, pp)
+}
+
+#[cfg(feature = "prover")]
+#[test]
+#[ignore]
+fn write_proof_fixture() {
+    use lurk::eval::empty_sym_env;
+    use lurk::proof::{nova::NovaProver, Prover};
+
+    let (lang, pp) = params();
+    let s = &mut Store::<S1>::default();
+    let expr = s.read("(+ 1 2)").unwrap();
+    let env = empty_sym_env(s);
+    let prover = NovaProver::new(REDUCTION_COUNT, (*lang).clone());
+    let (proof, ..) = prover
+        .evaluate_and_prove(&pp, expr, env, s, 10, lang)
+        .unwrap();
+    let proof = proof.compress(&pp).unwrap();
+    assert!(proof.verify_claim(&pp).unwrap());
+    let path = fixture_path();
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, proof.to_bytes(&pp).unwrap()).unwrap();
+}
+
+#[test]
+#[ignore]
+fn verify_proof_fixture() {
+    let (_, pp) = params();
+    let bytes = std::fs::read(fixture_path()).expect("the proof fixture is committed");
+    let proof = Proof::from_bytes(&bytes, &pp).unwrap();
+    assert!(proof.verify_claim(&pp).unwrap());
+
+    let s = &mut Store::<S1>::default();
+    let three = s.num(3);
+    assert_eq!(s.hash_expr(&three), proof.claim().output());
+
+    // a proof doesn't verify against another claim
+    let claim = proof.claim();
+    let mut zi = claim.zi();
+    zi[1] += S1::from(1);
+    assert!(!proof
+        .verify(&pp, claim.num_steps(), &claim.z0(), &zi)
+        .unwrap());
+}
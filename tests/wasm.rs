@@ -0,0 +1,19 @@
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use lurk::wasm::{lurk_eval, lurk_parse};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn eval_prints_result() {
+    assert_eq!(Some("3".into()), lurk_eval("(+ 1 2)").unwrap().as_string());
+    assert!(lurk_eval("(+ 1").is_err());
+}
+
+#[wasm_bindgen_test]
+fn parse_returns_json() {
+    let json = lurk_parse("(+ 1 \"a\")").unwrap().as_string().unwrap();
+    let ast: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!("list", ast["type"]);
+    assert_eq!("num", ast["elements"][1]["type"]);
+    assert_eq!("a", ast["elements"][2]["value"]);
+}